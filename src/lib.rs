@@ -2,3 +2,4 @@ pub mod models;
 pub mod services;
 pub mod state;
 pub mod ui;
+pub mod util;
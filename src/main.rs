@@ -1,4 +1,5 @@
-use github_starcleaner::services::ConfigService;
+use github_starcleaner::models::WindowState;
+use github_starcleaner::services::{CacheService, ConfigService};
 use github_starcleaner::state::AppState;
 use github_starcleaner::ui::AppView;
 use gpui::*;
@@ -23,7 +24,12 @@ fn main() {
     app.run(|cx: &mut App| {
             // Load config and initialize state
             let config = ConfigService::load().unwrap_or_default();
-            let state = AppState::from_config(config);
+            let mut state = AppState::from_config(config);
+            match CacheService::load_repos(state.config.active_profile_name()) {
+                Ok(Some(cache)) => state.restore_cache(cache),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load star cache: {}", e),
+            }
             cx.set_global(state);
 
             // Open main window
@@ -31,24 +37,44 @@ fn main() {
         });
 }
 
+/// Where the window opens on first run, or when the saved spot is gone
+fn default_window_bounds() -> Bounds<Pixels> {
+    Bounds {
+        origin: point(px(100.), px(100.)),
+        size: size(px(1200.), px(800.)),
+    }
+}
+
+/// The last session's window bounds, if they still fit on a connected display
+fn restored_window_bounds(cx: &App) -> Option<Bounds<Pixels>> {
+    let saved = cx.global::<AppState>().config.window.filter(WindowState::is_usable)?;
+    let bounds = Bounds {
+        origin: point(px(saved.x), px(saved.y)),
+        size: size(px(saved.width), px(saved.height)),
+    };
+    // A monitor that's since been unplugged would leave the window offscreen
+    cx.displays()
+        .iter()
+        .any(|display| display.bounds().intersects(&bounds))
+        .then_some(bounds)
+}
+
 fn open_main_window(cx: &mut App) {
+    let bounds = restored_window_bounds(cx).unwrap_or_else(default_window_bounds);
     cx.open_window(
         WindowOptions {
             titlebar: Some(TitlebarOptions {
                 title: Some("GitHub StarCleaner".into()),
                 ..Default::default()
             }),
-            window_bounds: Some(WindowBounds::Windowed(Bounds {
-                origin: point(px(100.), px(100.)),
-                size: size(px(1200.), px(800.)),
-            })),
+            window_bounds: Some(WindowBounds::Windowed(bounds)),
             focus: true,
             show: true,
             kind: WindowKind::Normal,
             is_movable: true,
             ..Default::default()
         },
-        |_window, cx| cx.new(|cx| AppView::new(cx)),
+        |window, cx| cx.new(|cx| AppView::new(window, cx)),
     )
     .expect("Failed to open window");
 }
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Repositories with no commit or release for this long count as dormant
+pub const DORMANT_AFTER_DAYS: i64 = 365;
+
+/// Latest commit and release of a repository, for spotting ones that have
+/// gone quiet
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RepoActivity {
+    /// Commit date of the newest commit on the default branch, None for an
+    /// empty repository
+    pub last_commit_at: Option<DateTime<Utc>>,
+    /// When the latest release was published, None without releases
+    pub last_release_at: Option<DateTime<Utc>>,
+}
+
+impl RepoActivity {
+    /// The newer of the last commit and the last release
+    pub fn last_active(&self) -> Option<DateTime<Utc>> {
+        self.last_commit_at.max(self.last_release_at)
+    }
+
+    /// No commit or release in `DORMANT_AFTER_DAYS`, or none at all
+    pub fn is_dormant(&self, now: DateTime<Utc>) -> bool {
+        self.last_active()
+            .is_none_or(|at| now - at >= Duration::days(DORMANT_AFTER_DAYS))
+    }
+
+    /// Badge text, "Active" or "Dormant"
+    pub fn label(&self, now: DateTime<Utc>) -> &'static str {
+        if self.is_dormant(now) {
+            "Dormant"
+        } else {
+            "Active"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_activity_dormancy() {
+        let now = Utc::now();
+        let mut activity = RepoActivity::default();
+        assert!(activity.is_dormant(now));
+
+        activity.last_commit_at = Some(now - Duration::days(400));
+        assert!(activity.is_dormant(now));
+        assert_eq!(activity.label(now), "Dormant");
+
+        // A recent release counts even if the last commit is old
+        activity.last_release_at = Some(now - Duration::days(30));
+        assert_eq!(activity.last_active(), activity.last_release_at);
+        assert!(!activity.is_dormant(now));
+        assert_eq!(activity.label(now), "Active");
+    }
+}
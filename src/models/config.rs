@@ -1,14 +1,288 @@
+use crate::models::{FilterPreset, RowDensity, SortDirection, SortField, ThemeMode, ViewMode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Largest page size the GitHub API accepts
+pub const MAX_PER_PAGE: u8 = 100;
+
+/// Profile every config starts with, and the one older versions' token belongs to
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    /// Last-used sort field
+    #[serde(default)]
+    pub sort_field: SortField,
+    /// Last-used sort direction
+    #[serde(default)]
+    pub sort_direction: SortDirection,
+    /// Keep fetching pages in the background until every star is loaded
+    #[serde(default)]
+    pub load_all: bool,
+    /// Reload the list when the window regains focus, if it's older than
+    /// `reload_on_focus_after_mins`
+    #[serde(default)]
+    pub reload_on_focus: bool,
+    #[serde(default = "default_reload_on_focus_after_mins")]
+    pub reload_on_focus_after_mins: u32,
+    /// Log out after this many minutes without a click or key press, for
+    /// shared machines. Off when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_logout_minutes: Option<u32>,
+    /// Load stars over the GraphQL API, falling back to REST if that fails
+    #[serde(default)]
+    pub graphql_stars: bool,
+    /// Search box matches names as fuzzy subsequences, best match first
+    #[serde(default)]
+    pub fuzzy_search: bool,
+    /// Dark or light color theme
+    #[serde(default)]
+    pub theme: ThemeMode,
+    /// Comfortable or compact repository rows
+    #[serde(default)]
+    pub row_density: RowDensity,
+    /// Repository cards or a table with sortable column headers
+    #[serde(default)]
+    pub view_mode: ViewMode,
+    /// Repositories fetched per API request (1..=100)
+    #[serde(default = "default_per_page")]
+    pub per_page: u8,
+    /// Index into `profiles` of the account in use
+    #[serde(default)]
+    pub active_profile: usize,
+    /// Credentials of the active profile
     pub github: GitHubConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub unstar: UnstarConfig,
+    /// Saved filter combinations, applied from the toolbar
+    #[serde(default)]
+    pub presets: Vec<FilterPreset>,
+    /// Repositories pinned as keepers: listed first and skipped by bulk selection
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub pinned_ids: HashSet<u64>,
+    /// GitHub accounts that can be switched between from the header
+    #[serde(default = "default_profiles")]
+    pub profiles: Vec<Profile>,
+    /// Main window position and size from the last session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<WindowState>,
+}
+
+fn default_per_page() -> u8 {
+    MAX_PER_PAGE
+}
+
+fn default_reload_on_focus_after_mins() -> u32 {
+    10
+}
+
+fn default_profiles() -> Vec<Profile> {
+    vec![Profile::new(DEFAULT_PROFILE)]
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            sort_field: SortField::default(),
+            sort_direction: SortDirection::default(),
+            load_all: false,
+            reload_on_focus: false,
+            reload_on_focus_after_mins: default_reload_on_focus_after_mins(),
+            auto_logout_minutes: None,
+            graphql_stars: false,
+            fuzzy_search: false,
+            theme: ThemeMode::default(),
+            row_density: RowDensity::default(),
+            view_mode: ViewMode::default(),
+            per_page: default_per_page(),
+            github: GitHubConfig::default(),
+            cache: CacheConfig::default(),
+            unstar: UnstarConfig::default(),
+            presets: Vec::new(),
+            pinned_ids: HashSet::new(),
+            active_profile: 0,
+            profiles: default_profiles(),
+            window: None,
+        }
+    }
+}
+
+/// Saved main window bounds, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl WindowState {
+    /// Smallest window size restored; anything smaller is treated as corrupt
+    pub const MIN_WIDTH: f32 = 400.;
+    pub const MIN_HEIGHT: f32 = 300.;
+
+    /// Whether these bounds are worth restoring
+    pub fn is_usable(&self) -> bool {
+        [self.x, self.y, self.width, self.height].iter().all(|v| v.is_finite())
+            && self.width >= Self::MIN_WIDTH
+            && self.height >= Self::MIN_HEIGHT
+    }
+}
+
+/// A named GitHub account. Its token lives in the keychain, or in `[github]`
+/// while the profile is active.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Token of an inactive profile when the keychain is unavailable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub personal_access_token: Option<String>,
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            personal_access_token: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct GitHubConfig {
     pub personal_access_token: Option<String>,
+    /// Whether the token came from (and is saved to) the OS keychain
+    /// rather than this file
+    #[serde(skip)]
+    pub token_in_keychain: bool,
+    /// HTTP proxy for GitHub requests, e.g. "http://proxy.corp:8080".
+    /// When unset, `HTTPS_PROXY`/`HTTP_PROXY` are used if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+/// Settings for the on-disk star list cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Gzip-compress the cache file
+    #[serde(default = "default_cache_compress")]
+    pub compress: bool,
+    /// Only compress once the list has at least this many repositories
+    #[serde(default = "default_cache_compress_threshold")]
+    pub compress_threshold: usize,
+    /// Cached lists older than this many hours are ignored on startup
+    #[serde(default = "default_cache_ttl_hours")]
+    pub ttl_hours: u32,
+}
+
+fn default_cache_compress() -> bool {
+    true
+}
+
+fn default_cache_compress_threshold() -> usize {
+    1000
+}
+
+fn default_cache_ttl_hours() -> u32 {
+    24
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            compress: default_cache_compress(),
+            compress_threshold: default_cache_compress_threshold(),
+            ttl_hours: default_cache_ttl_hours(),
+        }
+    }
+}
+
+impl CacheConfig {
+    /// How long a cached list stays usable
+    pub fn ttl(&self) -> chrono::Duration {
+        chrono::Duration::hours(self.ttl_hours.into())
+    }
+
+    /// Whether a list of `repo_count` repositories should be written compressed
+    pub fn should_compress(&self, repo_count: usize) -> bool {
+        self.compress && repo_count >= self.compress_threshold
+    }
+}
+
+/// Settings for unstar operations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnstarConfig {
+    /// Bulk unstars of at least this many repos require typing to confirm
+    #[serde(default = "default_type_to_confirm_threshold")]
+    pub type_to_confirm_threshold: usize,
+    /// How many unstar requests to run in parallel during bulk unstars
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// Ask before unstarring a single repo
+    #[serde(default = "default_confirm_single_unstar")]
+    pub confirm_single_unstar: bool,
+    /// Bulk unstars of at least this many repos ask first, smaller ones go
+    /// straight through; 1 always asks
+    #[serde(default = "default_batch_confirm_threshold")]
+    pub batch_confirm_threshold: usize,
+    /// Log unstars instead of sending them, to preview a cleanup
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Bulk unstars are sent in chunks of this many repos
+    #[serde(default = "default_chunk_size")]
+    pub chunk_size: usize,
+    /// Pause between chunks in milliseconds, to stay under GitHub's
+    /// secondary rate limits
+    #[serde(default = "default_chunk_delay_ms")]
+    pub chunk_delay_ms: u64,
+}
+
+fn default_type_to_confirm_threshold() -> usize {
+    500
+}
+
+fn default_concurrency() -> usize {
+    6
+}
+
+fn default_confirm_single_unstar() -> bool {
+    true
+}
+
+fn default_batch_confirm_threshold() -> usize {
+    1
+}
+
+fn default_chunk_size() -> usize {
+    25
+}
+
+fn default_chunk_delay_ms() -> u64 {
+    1000
+}
+
+impl UnstarConfig {
+    /// Whether unstarring `count` selected repos should show the confirmation dialog
+    pub fn needs_batch_confirmation(&self, count: usize) -> bool {
+        count >= self.batch_confirm_threshold
+    }
+}
+
+impl Default for UnstarConfig {
+    fn default() -> Self {
+        Self {
+            type_to_confirm_threshold: default_type_to_confirm_threshold(),
+            concurrency: default_concurrency(),
+            confirm_single_unstar: default_confirm_single_unstar(),
+            batch_confirm_threshold: default_batch_confirm_threshold(),
+            dry_run: false,
+            chunk_size: default_chunk_size(),
+            chunk_delay_ms: default_chunk_delay_ms(),
+        }
+    }
 }
 
 impl AppConfig {
@@ -24,6 +298,91 @@ impl AppConfig {
         Self::config_dir().join("config.toml")
     }
 
+    /// Get the cache directory path
+    pub fn cache_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("github-starcleaner")
+    }
+
+    /// Pull out-of-range values back into what the API accepts
+    pub fn clamp_values(&mut self) {
+        self.per_page = self.per_page.clamp(1, MAX_PER_PAGE);
+        self.unstar.chunk_size = self.unstar.chunk_size.max(1);
+        self.unstar.batch_confirm_threshold = self.unstar.batch_confirm_threshold.max(1);
+        // Never reload more than once a minute, however often focus changes
+        self.reload_on_focus_after_mins = self.reload_on_focus_after_mins.max(1);
+        self.auto_logout_minutes = self.auto_logout_minutes.map(|minutes| minutes.max(1));
+        if self.profiles.is_empty() {
+            self.profiles = default_profiles();
+        }
+        self.active_profile = self.active_profile.min(self.profiles.len() - 1);
+    }
+
+    /// Pin or unpin a repository, returns whether it's now pinned
+    pub fn toggle_pin(&mut self, id: u64) -> bool {
+        if self.pinned_ids.remove(&id) {
+            false
+        } else {
+            self.pinned_ids.insert(id);
+            true
+        }
+    }
+
+    /// Name of the profile in use
+    pub fn active_profile_name(&self) -> &str {
+        self.profiles
+            .get(self.active_profile)
+            .map_or(DEFAULT_PROFILE, |p| p.name.as_str())
+    }
+
+    /// Index of the profile named `name`, adding it if it doesn't exist yet
+    pub fn add_profile(&mut self, name: &str) -> usize {
+        let name = name.trim();
+        match self.profiles.iter().position(|p| p.name.eq_ignore_ascii_case(name)) {
+            Some(index) => index,
+            None => {
+                self.profiles.push(Profile::new(name));
+                self.profiles.len() - 1
+            }
+        }
+    }
+
+    /// Make profile `index` active. The current token is set aside on its
+    /// profile unless it's in the keychain, and `[github]` takes the new
+    /// profile's token: `keychain_token` if it has one there, otherwise
+    /// whatever was set aside for it.
+    pub fn activate_profile(&mut self, index: usize, keychain_token: Option<String>) {
+        if index >= self.profiles.len() {
+            return;
+        }
+
+        let current = std::mem::take(&mut self.github);
+        if let Some(profile) = self.profiles.get_mut(self.active_profile) {
+            profile.personal_access_token = if current.token_in_keychain {
+                None
+            } else {
+                current.personal_access_token
+            };
+        }
+
+        self.active_profile = index;
+        let set_aside = self.profiles[index].personal_access_token.take();
+        // The proxy belongs to this machine, not the account
+        self.github = match keychain_token {
+            Some(token) => GitHubConfig {
+                personal_access_token: Some(token),
+                token_in_keychain: true,
+                proxy: current.proxy,
+            },
+            None => GitHubConfig {
+                personal_access_token: set_aside,
+                token_in_keychain: false,
+                proxy: current.proxy,
+            },
+        };
+    }
+
     /// Check if a valid token is configured
     pub fn has_token(&self) -> bool {
         self.github
@@ -54,12 +413,29 @@ mod tests {
         assert!(config.get_token().is_none());
     }
 
+    #[test]
+    fn test_toggle_pin() {
+        let mut config = AppConfig::default();
+        assert!(config.toggle_pin(7));
+        assert!(config.pinned_ids.contains(&7));
+
+        let toml = toml::to_string(&config).unwrap();
+        let parsed: AppConfig = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.pinned_ids, HashSet::from([7]));
+
+        assert!(!config.toggle_pin(7));
+        assert!(config.pinned_ids.is_empty());
+        assert!(!toml::to_string(&config).unwrap().contains("pinned_ids"));
+    }
+
     #[test]
     fn test_has_token_with_valid_token() {
         let config = AppConfig {
             github: GitHubConfig {
                 personal_access_token: Some("ghp_test_token".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
         assert!(config.has_token());
     }
@@ -69,7 +445,9 @@ mod tests {
         let config = AppConfig {
             github: GitHubConfig {
                 personal_access_token: Some("".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
         assert!(!config.has_token());
     }
@@ -79,7 +457,9 @@ mod tests {
         let config = AppConfig {
             github: GitHubConfig {
                 personal_access_token: None,
+                ..Default::default()
             },
+            ..Default::default()
         };
         assert!(!config.has_token());
     }
@@ -89,7 +469,9 @@ mod tests {
         let config = AppConfig {
             github: GitHubConfig {
                 personal_access_token: Some("ghp_test_token".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
         assert_eq!(config.get_token(), Some("ghp_test_token"));
     }
@@ -99,11 +481,102 @@ mod tests {
         let config = AppConfig {
             github: GitHubConfig {
                 personal_access_token: Some("".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
         assert!(config.get_token().is_none());
     }
 
+    #[test]
+    fn test_token_in_keychain_is_not_serialized() {
+        let config = AppConfig {
+            github: GitHubConfig {
+                personal_access_token: None,
+                token_in_keychain: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let serialized = toml::to_string(&config).unwrap();
+        assert!(!serialized.contains("token_in_keychain"));
+    }
+
+    #[test]
+    fn test_config_without_profiles_gets_default() {
+        let mut config: AppConfig = toml::from_str("[github]\n").unwrap();
+        assert_eq!(config.active_profile_name(), DEFAULT_PROFILE);
+
+        config.profiles.clear();
+        config.active_profile = 3;
+        config.clamp_values();
+        assert_eq!(config.profiles, vec![Profile::new(DEFAULT_PROFILE)]);
+        assert_eq!(config.active_profile, 0);
+    }
+
+    #[test]
+    fn test_add_profile_reuses_existing_name() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.add_profile(" work "), 1);
+        assert_eq!(config.add_profile("Work"), 1);
+        assert_eq!(config.profiles.len(), 2);
+        assert_eq!(config.profiles[1].name, "work");
+    }
+
+    #[test]
+    fn test_activate_profile_swaps_plaintext_tokens() {
+        let mut config = AppConfig {
+            github: GitHubConfig {
+                personal_access_token: Some("ghp_personal".to_string()),
+                token_in_keychain: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let work = config.add_profile("work");
+
+        config.activate_profile(work, None);
+        assert_eq!(config.active_profile_name(), "work");
+        assert!(!config.has_token());
+        assert_eq!(config.profiles[0].personal_access_token.as_deref(), Some("ghp_personal"));
+
+        config.activate_profile(0, None);
+        assert_eq!(config.get_token(), Some("ghp_personal"));
+        assert!(config.profiles[0].personal_access_token.is_none());
+    }
+
+    #[test]
+    fn test_activate_profile_keeps_proxy() {
+        let mut config: AppConfig =
+            toml::from_str("[github]\nproxy = \"http://proxy.corp:8080\"\n").unwrap();
+        let work = config.add_profile("work");
+
+        config.activate_profile(work, Some("ghp_work".to_string()));
+        assert_eq!(config.github.proxy.as_deref(), Some("http://proxy.corp:8080"));
+    }
+
+    #[test]
+    fn test_activate_profile_leaves_keychain_tokens_out_of_the_file() {
+        let mut config = AppConfig {
+            github: GitHubConfig {
+                personal_access_token: Some("ghp_personal".to_string()),
+                token_in_keychain: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let work = config.add_profile("work");
+
+        config.activate_profile(work, Some("ghp_work".to_string()));
+        assert_eq!(config.get_token(), Some("ghp_work"));
+        assert!(config.github.token_in_keychain);
+        assert!(config.profiles[0].personal_access_token.is_none());
+
+        // Out-of-range indexes are ignored
+        config.activate_profile(5, None);
+        assert_eq!(config.active_profile, work);
+    }
+
     #[test]
     fn test_config_dir_ends_with_app_name() {
         let dir = AppConfig::config_dir();
@@ -121,7 +594,9 @@ mod tests {
         let config = AppConfig {
             github: GitHubConfig {
                 personal_access_token: Some("test_token".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
         let serialized = toml::to_string(&config).unwrap();
         assert!(serialized.contains("personal_access_token"));
@@ -137,4 +612,192 @@ personal_access_token = "my_token"
         let config: AppConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.get_token(), Some("my_token"));
     }
+
+    #[test]
+    fn test_config_without_cache_section_uses_defaults() {
+        let toml_str = r#"
+[github]
+personal_access_token = "my_token"
+"#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        assert!(config.cache.compress);
+        assert_eq!(config.cache.compress_threshold, 1000);
+        assert_eq!(config.cache.ttl(), chrono::Duration::hours(24));
+    }
+
+    #[test]
+    fn test_cache_should_compress() {
+        let cache = CacheConfig::default();
+        assert!(!cache.should_compress(10));
+        assert!(cache.should_compress(1000));
+
+        let disabled = CacheConfig {
+            compress: false,
+            ..Default::default()
+        };
+        assert!(!disabled.should_compress(20_000));
+    }
+
+    #[test]
+    fn test_unstar_config_deserialization() {
+        let toml_str = r#"
+[github]
+personal_access_token = "my_token"
+
+[unstar]
+type_to_confirm_threshold = 50
+"#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.unstar.type_to_confirm_threshold, 50);
+        assert_eq!(AppConfig::default().unstar.type_to_confirm_threshold, 500);
+        assert_eq!(config.unstar.concurrency, 6);
+        assert!(!config.load_all);
+        assert!(config.unstar.confirm_single_unstar);
+        assert!(!config.unstar.dry_run);
+        assert_eq!(config.unstar.chunk_size, 25);
+        assert_eq!(config.unstar.chunk_delay_ms, 1000);
+        assert_eq!(config.unstar.batch_confirm_threshold, 1);
+        assert!(!config.reload_on_focus);
+        assert!(!config.graphql_stars);
+        assert_eq!(config.reload_on_focus_after_mins, 10);
+        assert_eq!(config.auto_logout_minutes, None);
+        assert_eq!(config.per_page, 100);
+    }
+
+    #[test]
+    fn test_window_state_roundtrips() {
+        let mut config = AppConfig::default();
+        assert!(!toml::to_string(&config).unwrap().contains("[window]"));
+
+        config.window = Some(WindowState {
+            x: 40.,
+            y: 60.5,
+            width: 1440.,
+            height: 900.,
+        });
+        let parsed: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.window, config.window);
+
+        let first_run: AppConfig = toml::from_str("[github]\n").unwrap();
+        assert_eq!(first_run.window, None);
+    }
+
+    #[test]
+    fn test_window_state_is_usable() {
+        let window = WindowState {
+            x: -1200.,
+            y: 0.,
+            width: 1200.,
+            height: 800.,
+        };
+        assert!(window.is_usable());
+        assert!(!WindowState { width: 10., ..window }.is_usable());
+        assert!(!WindowState { height: f32::NAN, ..window }.is_usable());
+    }
+
+    #[test]
+    fn test_batch_confirm_threshold() {
+        let mut unstar = UnstarConfig::default();
+        assert!(unstar.needs_batch_confirmation(1));
+
+        unstar.batch_confirm_threshold = 6;
+        assert!(!unstar.needs_batch_confirmation(5));
+        assert!(unstar.needs_batch_confirmation(6));
+        assert!(unstar.needs_batch_confirmation(500));
+
+        let mut config: AppConfig = toml::from_str("[github]\n[unstar]\nbatch_confirm_threshold = 0\n").unwrap();
+        config.clamp_values();
+        assert_eq!(config.unstar.batch_confirm_threshold, 1);
+    }
+
+    #[test]
+    fn test_sort_serialization_is_human_readable() {
+        let config = AppConfig {
+            sort_field: SortField::Starred,
+            sort_direction: SortDirection::Desc,
+            ..Default::default()
+        };
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("sort_field = \"starred\""));
+        assert!(serialized.contains("sort_direction = \"desc\""));
+
+        let parsed: AppConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.sort_field, SortField::Starred);
+        assert_eq!(parsed.sort_direction, SortDirection::Desc);
+    }
+
+    #[test]
+    fn test_row_density_roundtrips() {
+        let config = AppConfig {
+            row_density: RowDensity::Compact,
+            ..Default::default()
+        };
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("row_density = \"compact\""));
+
+        let parsed: AppConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.row_density, RowDensity::Compact);
+        let legacy: AppConfig = toml::from_str("[github]\n").unwrap();
+        assert_eq!(legacy.row_density, RowDensity::Comfortable);
+    }
+
+    #[test]
+    fn test_view_mode_roundtrips() {
+        let config = AppConfig {
+            view_mode: ViewMode::Table,
+            ..Default::default()
+        };
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(serialized.contains("view_mode = \"table\""));
+
+        let parsed: AppConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.view_mode, ViewMode::Table);
+        let legacy: AppConfig = toml::from_str("[github]\n").unwrap();
+        assert_eq!(legacy.view_mode, ViewMode::Cards);
+    }
+
+    #[test]
+    fn test_config_without_sort_uses_defaults() {
+        let toml_str = r#"
+[github]
+personal_access_token = "my_token"
+"#;
+        let config: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.sort_field, SortField::Pushed);
+        assert_eq!(config.sort_direction, SortDirection::Asc);
+    }
+
+    #[test]
+    fn test_per_page_is_clamped_to_api_range() {
+        let mut config: AppConfig = toml::from_str("per_page = 250\n[github]\n").unwrap();
+        config.clamp_values();
+        assert_eq!(config.per_page, 100);
+
+        let mut config: AppConfig = toml::from_str("per_page = 0\n[github]\n").unwrap();
+        config.clamp_values();
+        assert_eq!(config.per_page, 1);
+
+        let mut config: AppConfig = toml::from_str("per_page = 30\n[github]\n").unwrap();
+        config.clamp_values();
+        assert_eq!(config.per_page, 30);
+    }
+
+    #[test]
+    fn test_auto_logout_minutes() {
+        assert!(!toml::to_string(&AppConfig::default()).unwrap().contains("auto_logout_minutes"));
+
+        let mut config: AppConfig = toml::from_str("auto_logout_minutes = 15\n[github]\n").unwrap();
+        config.clamp_values();
+        assert_eq!(config.auto_logout_minutes, Some(15));
+
+        let mut config: AppConfig = toml::from_str("auto_logout_minutes = 0\n[github]\n").unwrap();
+        config.clamp_values();
+        assert_eq!(config.auto_logout_minutes, Some(1));
+    }
+
+    #[test]
+    fn test_cache_dir_ends_with_app_name() {
+        let dir = AppConfig::cache_dir();
+        assert!(dir.ends_with("github-starcleaner"));
+    }
 }
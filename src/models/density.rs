@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// How much vertical space each repository row takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RowDensity {
+    /// Name, description, stats and topics on separate lines
+    #[default]
+    Comfortable,
+    /// Name, language and stats on one line, without description or topics
+    Compact,
+}
+
+impl RowDensity {
+    pub fn toggle(&self) -> Self {
+        match self {
+            RowDensity::Comfortable => RowDensity::Compact,
+            RowDensity::Compact => RowDensity::Comfortable,
+        }
+    }
+
+    pub fn is_compact(&self) -> bool {
+        *self == RowDensity::Compact
+    }
+
+    /// Label for the button that switches to the other density
+    pub fn toggle_label(&self) -> &'static str {
+        match self {
+            RowDensity::Comfortable => "Compact",
+            RowDensity::Compact => "Comfortable",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_density_toggle() {
+        assert_eq!(RowDensity::default(), RowDensity::Comfortable);
+        assert_eq!(RowDensity::Comfortable.toggle(), RowDensity::Compact);
+        assert_eq!(RowDensity::Compact.toggle(), RowDensity::Comfortable);
+        assert!(RowDensity::Compact.is_compact());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::models::Repository;
+use serde::{Deserialize, Serialize};
+
+/// Toolbar filter on whether repositories are forks
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkFilter {
+    #[default]
+    All,
+    /// Only show forks
+    Only,
+    /// Hide forks
+    Exclude,
+}
+
+impl ForkFilter {
+    pub fn matches(&self, repo: &Repository) -> bool {
+        match self {
+            ForkFilter::All => true,
+            ForkFilter::Only => repo.fork,
+            ForkFilter::Exclude => !repo.fork,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ForkFilter::All => "All",
+            ForkFilter::Only => "Forks only",
+            ForkFilter::Exclude => "Exclude forks",
+        }
+    }
+}
+
+/// A named combination of filters saved to the config, e.g. "archived forks
+/// older than 2 years". The free-text search isn't part of a preset.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FilterPreset {
+    pub name: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Only repositories not pushed to in this many days
+    #[serde(default)]
+    pub stale_threshold_days: Option<u32>,
+    #[serde(default)]
+    pub archived_only: bool,
+    #[serde(default)]
+    pub no_description_only: bool,
+    #[serde(default)]
+    pub fork_filter: ForkFilter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archived_forks_older_than_two_years() -> FilterPreset {
+        FilterPreset {
+            name: "Old archived forks".to_string(),
+            stale_threshold_days: Some(730),
+            archived_only: true,
+            fork_filter: ForkFilter::Only,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_preset_roundtrips_through_toml() {
+        let preset = archived_forks_older_than_two_years();
+        let toml = toml::to_string(&preset).unwrap();
+        assert!(toml.contains("fork_filter = \"only\""));
+
+        let parsed: FilterPreset = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed, preset);
+    }
+}
@@ -1,5 +1,21 @@
+pub mod activity;
 pub mod config;
+pub mod density;
+pub mod filter_preset;
+pub mod rate_limit;
 pub mod repository;
+pub mod sort;
+pub mod star_list;
+pub mod theme;
+pub mod view_mode;
 
+pub use activity::*;
 pub use config::*;
+pub use density::*;
+pub use filter_preset::*;
+pub use rate_limit::*;
 pub use repository::*;
+pub use sort::*;
+pub use star_list::*;
+pub use theme::*;
+pub use view_mode::*;
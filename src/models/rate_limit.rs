@@ -0,0 +1,59 @@
+use chrono::{DateTime, Local, Utc};
+
+/// Core REST API rate limit for the authenticated user
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    pub remaining: u32,
+    pub limit: u32,
+    pub reset_at: DateTime<Utc>,
+}
+
+impl RateLimit {
+    /// Whether no requests are left until the window resets
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0 && Utc::now() < self.reset_at
+    }
+
+    /// Short header label, e.g. "API: 4200/5000"
+    pub fn label(&self) -> String {
+        format!("API: {}/{}", self.remaining, self.limit)
+    }
+
+    /// Reset time in local time, e.g. "Resets at 14:05"
+    pub fn reset_label(&self) -> String {
+        format!("Resets at {}", self.reset_at.with_timezone(&Local).format("%H:%M"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_rate_limit(remaining: u32) -> RateLimit {
+        RateLimit {
+            remaining,
+            limit: 5000,
+            reset_at: Utc::now() + chrono::Duration::minutes(30),
+        }
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(create_rate_limit(4200).label(), "API: 4200/5000");
+    }
+
+    #[test]
+    fn test_is_exhausted() {
+        assert!(create_rate_limit(0).is_exhausted());
+        assert!(!create_rate_limit(1).is_exhausted());
+    }
+
+    #[test]
+    fn test_not_exhausted_after_reset() {
+        let rate_limit = RateLimit {
+            reset_at: Utc::now() - chrono::Duration::seconds(1),
+            ..create_rate_limit(0)
+        };
+        assert!(!rate_limit.is_exhausted());
+    }
+}
@@ -1,6 +1,7 @@
-use chrono::{DateTime, Utc};
+use crate::util::fuzzy_score;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Repository {
@@ -18,39 +19,269 @@ pub struct Repository {
     pub updated_at: DateTime<Utc>,
     pub pushed_at: Option<DateTime<Utc>>,
     pub html_url: String,
+    /// Project website, if the owner set one
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// Whether the owner has archived the repository
+    #[serde(default)]
+    pub archived: bool,
+    /// Whether the repository is a fork of another
+    #[serde(default)]
+    pub fork: bool,
     /// Order in which the repo was starred (from API response order)
     #[serde(default)]
     pub starred_order: u32,
+    /// When the user starred it, None for repositories fetched outside the
+    /// starred list (e.g. after a rename)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub starred_at: Option<DateTime<Utc>>,
+}
+
+/// Drop blank homepages (GitHub keeps "" once one is removed) and add a
+/// scheme to bare domains like "example.com" so they open in a browser
+pub fn normalize_homepage(homepage: Option<String>) -> Option<String> {
+    let homepage = homepage?.trim().to_string();
+    if homepage.is_empty() {
+        None
+    } else if homepage.contains("://") {
+        Some(homepage)
+    } else {
+        Some(format!("https://{}", homepage))
+    }
+}
+
+/// "owner/name" from a REST API URL like https://api.github.com/repos/owner/name
+fn full_name_from_api_url(url: &str) -> Option<String> {
+    let (_, path) = url.split_once("/repos/")?;
+    let mut segments = path.split('/');
+    match (segments.next(), segments.next()) {
+        (Some(owner), Some(name)) if !owner.is_empty() && !name.is_empty() => Some(format!("{}/{}", owner, name)),
+        _ => None,
+    }
 }
 
 impl Repository {
-    /// Convert from octocrab Repository model with starred order
-    pub fn from_octocrab_with_order(repo: octocrab::models::Repository, starred_order: u32) -> Self {
-        Self {
+    /// Convert from octocrab Repository model with starred order and time.
+    /// Missing names and links are rebuilt from whatever GitHub did send;
+    /// None if there's nothing to identify the repository by.
+    pub fn from_octocrab_with_order(
+        repo: octocrab::models::Repository,
+        starred_order: u32,
+        starred_at: Option<DateTime<Utc>>,
+    ) -> Option<Self> {
+        let login = repo
+            .owner
+            .as_ref()
+            .map(|o| o.login.clone())
+            .filter(|login| !login.is_empty());
+        let full_name = repo
+            .full_name
+            .clone()
+            .filter(|full_name| full_name.contains('/'))
+            .or_else(|| {
+                let login = login.as_ref().filter(|_| !repo.name.is_empty())?;
+                Some(format!("{}/{}", login, repo.name))
+            })
+            .or_else(|| full_name_from_api_url(repo.url.as_str()));
+        let Some(full_name) = full_name else {
+            tracing::warn!("Skipping repository {} with no name or owner", repo.id.0);
+            return None;
+        };
+        if repo.full_name.as_deref() != Some(full_name.as_str()) {
+            tracing::warn!("Repository {} had no full name, using {}", repo.id.0, full_name);
+        }
+        let (owner, name) = full_name.split_once('/').unwrap_or_default();
+        let owner = login.unwrap_or_else(|| owner.to_string());
+        let name = if repo.name.is_empty() { name.to_string() } else { repo.name.clone() };
+        let html_url = repo
+            .html_url
+            .as_ref()
+            .map(|u| u.to_string())
+            .unwrap_or_else(|| format!("https://github.com/{}", full_name));
+
+        Some(Self {
             id: repo.id.0,
-            name: repo.name,
-            full_name: repo.full_name.clone().unwrap_or_default(),
-            owner: repo
-                .owner
-                .as_ref()
-                .map(|o| o.login.clone())
-                .unwrap_or_default(),
+            name,
+            full_name,
+            owner,
             description: repo.description.clone(),
             language: repo
                 .language
                 .as_ref()
                 .and_then(|v| v.as_str().map(|s| s.to_string())),
-            stargazers_count: repo.stargazers_count.unwrap_or(0) as u32,
-            forks_count: repo.forks_count.unwrap_or(0) as u32,
-            open_issues_count: repo.open_issues_count.unwrap_or(0) as u32,
+            stargazers_count: repo.stargazers_count.unwrap_or(0),
+            forks_count: repo.forks_count.unwrap_or(0),
+            open_issues_count: repo.open_issues_count.unwrap_or(0),
             license: repo.license.as_ref().map(|l| l.name.clone()),
             topics: repo.topics.clone().unwrap_or_default(),
             updated_at: repo.updated_at.unwrap_or_else(Utc::now),
             pushed_at: repo.pushed_at,
-            html_url: repo.html_url.map(|u| u.to_string()).unwrap_or_default(),
+            html_url,
+            homepage: normalize_homepage(repo.homepage),
+            archived: repo.archived.unwrap_or(false),
+            fork: repo.fork.unwrap_or(false),
             starred_order,
+            starred_at,
+        })
+    }
+
+    /// Link to the repository's issues page
+    pub fn issues_url(&self) -> String {
+        format!("{}/issues", self.html_url.trim_end_matches('/'))
+    }
+
+    /// Case-insensitive substring match against name, description, and topics.
+    /// `query` must already be lowercase.
+    pub fn matches_query(&self, query: &str) -> bool {
+        if query.is_empty() {
+            return true;
+        }
+
+        self.full_name.to_lowercase().contains(query)
+            || self
+                .description
+                .as_ref()
+                .map(|d| d.to_lowercase().contains(query))
+                .unwrap_or(false)
+            || self.topics.iter().any(|t| t.to_lowercase().contains(query))
+    }
+
+    /// Best fuzzy score of `query` against the full name and topics, None
+    /// if neither matches. Descriptions are left out: in a sentence almost
+    /// any short query matches as a subsequence.
+    pub fn fuzzy_query_score(&self, query: &str) -> Option<i64> {
+        std::iter::once(&self.full_name)
+            .chain(&self.topics)
+            .filter_map(|target| fuzzy_score(query, target))
+            .max()
+    }
+
+    /// Case-insensitive match against the repo's primary language
+    pub fn has_language(&self, language: &str) -> bool {
+        self.language
+            .as_deref()
+            .is_some_and(|l| l.eq_ignore_ascii_case(language))
+    }
+
+    /// Whether the repo has a non-blank description
+    pub fn has_description(&self) -> bool {
+        self.description
+            .as_deref()
+            .map(|d| !d.trim().is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Whether the repo hasn't been pushed to for more than `threshold_days`.
+    /// Repos that were never pushed to count as stale.
+    pub fn is_stale(&self, threshold_days: u32, now: DateTime<Utc>) -> bool {
+        match self.pushed_at {
+            Some(pushed_at) => now - pushed_at > Duration::days(threshold_days.into()),
+            None => true,
+        }
+    }
+}
+
+/// Split a `language:rust` term out of a search, returning the remaining
+/// query and the language, if any
+pub fn split_language_filter(input: &str) -> (String, Option<String>) {
+    let mut language = None;
+    let mut terms = Vec::new();
+    for term in input.split_whitespace() {
+        match term.strip_prefix("language:") {
+            Some(value) if !value.is_empty() => language = Some(value.to_string()),
+            _ => terms.push(term),
+        }
+    }
+    (terms.join(" "), language)
+}
+
+/// Parse `owner/repo` or a GitHub URL (https or SSH) into (owner, repo)
+pub fn parse_repo_reference(input: &str) -> Option<(String, String)> {
+    let mut rest = input.trim();
+    for prefix in ["https://", "http://", "git@"] {
+        rest = rest.strip_prefix(prefix).unwrap_or(rest);
+    }
+    rest = rest.strip_prefix("www.").unwrap_or(rest);
+    for prefix in ["github.com/", "github.com:"] {
+        rest = rest.strip_prefix(prefix).unwrap_or(rest);
+    }
+
+    // Ignore anything after the repo, e.g. `/tree/main`, `?tab=readme`, `#usage`
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = rest.split('/');
+    let owner = segments.next()?;
+    let repo = segments.next()?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+
+    let is_valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    (is_valid(owner) && is_valid(repo)).then(|| (owner.to_string(), repo.to_string()))
+}
+
+/// Descriptions shorter than this once normalized, e.g. "WIP", are too
+/// generic to suggest two repositories are the same project
+const MIN_DUPLICATE_DESCRIPTION_CHARS: usize = 20;
+
+/// Lowercased words of a description, so punctuation and spacing don't
+/// hide a match
+fn normalize_description(description: &str) -> String {
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Likely duplicate stars, e.g. a project starred at both its old and new
+/// owner after a transfer. Repositories sharing an id, a name (ignoring
+/// case) or a normalized description end up in one group. Groups have at
+/// least two repositories, keep list order and are ordered by their first one.
+pub fn find_duplicate_groups(repos: &[Repository]) -> Vec<Vec<&Repository>> {
+    fn root(parents: &mut [usize], mut i: usize) -> usize {
+        while parents[i] != i {
+            parents[i] = parents[parents[i]];
+            i = parents[i];
         }
+        i
     }
+
+    let mut parents: Vec<usize> = (0..repos.len()).collect();
+    let mut first_seen: HashMap<(u8, String), usize> = HashMap::new();
+    for (i, repo) in repos.iter().enumerate() {
+        let description = repo
+            .description
+            .as_deref()
+            .map(normalize_description)
+            .filter(|d| d.chars().count() >= MIN_DUPLICATE_DESCRIPTION_CHARS);
+        let keys = [
+            Some((0, repo.id.to_string())),
+            Some((1, repo.name.to_lowercase())),
+            description.map(|d| (2, d)),
+        ];
+        for key in keys.into_iter().flatten() {
+            let first = *first_seen.entry(key).or_insert(i);
+            let (a, b) = (root(&mut parents, first), root(&mut parents, i));
+            // The earlier repository stays the root, so groups sort by it
+            parents[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut groups: Vec<Vec<&Repository>> = Vec::new();
+    let mut group_of_root: HashMap<usize, usize> = HashMap::new();
+    for (i, repo) in repos.iter().enumerate() {
+        let group = *group_of_root.entry(root(&mut parents, i)).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[group].push(repo);
+    }
+    groups.retain(|group| group.len() > 1);
+    groups
 }
 
 /// Selection state for batch operations
@@ -78,6 +309,13 @@ impl RepositorySelection {
         self.selected_ids = repos.iter().map(|r| r.id).collect();
     }
 
+    /// Flip selection for each of the given repositories
+    pub fn invert(&mut self, repos: &[Repository]) {
+        for repo in repos {
+            self.toggle(repo.id);
+        }
+    }
+
     /// Clear all selections
     pub fn clear(&mut self) {
         self.selected_ids.clear();
@@ -101,29 +339,117 @@ impl RepositorySelection {
     }
 }
 
+/// A bare repository `owner/name` for tests to fill in with struct update
+/// syntax, e.g. `Repository { fork: true, ..create_test_repo(1, "bat", "sharkdp") }`
+#[cfg(test)]
+pub(crate) fn create_test_repo(id: u64, name: &str, owner: &str) -> Repository {
+    Repository {
+        id,
+        name: name.to_string(),
+        full_name: format!("{}/{}", owner, name),
+        owner: owner.to_string(),
+        description: None,
+        language: None,
+        stargazers_count: 0,
+        forks_count: 0,
+        open_issues_count: 0,
+        license: None,
+        topics: vec![],
+        updated_at: Utc::now(),
+        pushed_at: None,
+        html_url: format!("https://github.com/{}/{}", owner, name),
+        homepage: None,
+        archived: false,
+        fork: false,
+        starred_order: 0,
+        starred_at: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
 
-    fn create_test_repo(id: u64, name: &str) -> Repository {
-        Repository {
-            id,
-            name: name.to_string(),
-            full_name: format!("owner/{}", name),
-            owner: "owner".to_string(),
-            description: Some("Test description".to_string()),
-            language: Some("Rust".to_string()),
-            stargazers_count: 100,
-            forks_count: 10,
-            open_issues_count: 5,
-            license: Some("MIT".to_string()),
-            topics: vec!["rust".to_string(), "cli".to_string()],
-            updated_at: Utc::now(),
-            pushed_at: Some(Utc::now()),
-            html_url: format!("https://github.com/owner/{}", name),
-            starred_order: 0,
-        }
+    fn octocrab_repo(json: serde_json::Value) -> octocrab::models::Repository {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_from_octocrab_rebuilds_missing_full_name_and_url() {
+        let repo = octocrab_repo(serde_json::json!({
+            "id": 1,
+            "name": "ripgrep",
+            "url": "https://api.github.com/repos/BurntSushi/ripgrep",
+        }));
+
+        let repo = Repository::from_octocrab_with_order(repo, 3, None).unwrap();
+        assert_eq!(repo.full_name, "BurntSushi/ripgrep");
+        assert_eq!(repo.owner, "BurntSushi");
+        assert_eq!(repo.name, "ripgrep");
+        assert_eq!(repo.html_url, "https://github.com/BurntSushi/ripgrep");
+        assert_eq!(repo.starred_order, 3);
+    }
+
+    #[test]
+    fn test_from_octocrab_keeps_complete_data() {
+        let repo = octocrab_repo(serde_json::json!({
+            "id": 1,
+            "name": "fd",
+            "full_name": "sharkdp/fd",
+            "url": "https://api.github.com/repos/sharkdp/fd",
+            "html_url": "https://github.com/sharkdp/fd",
+        }));
+
+        let repo = Repository::from_octocrab_with_order(repo, 0, None).unwrap();
+        assert_eq!(repo.full_name, "sharkdp/fd");
+        assert_eq!(repo.owner, "sharkdp");
+        assert_eq!(repo.html_url, "https://github.com/sharkdp/fd");
+    }
+
+    #[test]
+    fn test_from_octocrab_skips_unidentifiable_repo() {
+        let repo = octocrab_repo(serde_json::json!({
+            "id": 1,
+            "name": "",
+            "full_name": "",
+            "url": "https://api.github.com/",
+        }));
+        assert!(Repository::from_octocrab_with_order(repo, 0, None).is_none());
+    }
+
+    fn repo_json(extra: &str) -> String {
+        format!(
+            r#"{{
+                "id": 1, "name": "old", "full_name": "owner/old", "owner": "owner",
+                "description": null, "language": null, "stargazers_count": 0,
+                "forks_count": 0, "open_issues_count": 0, "license": null, "topics": [],
+                "updated_at": "2020-01-01T00:00:00Z", "pushed_at": null,
+                "html_url": "https://github.com/owner/old"{}
+            }}"#,
+            extra
+        )
+    }
+
+    #[test]
+    fn test_deserialize_archived_flag() {
+        let repo: Repository = serde_json::from_str(&repo_json(r#", "archived": true"#)).unwrap();
+        assert!(repo.archived);
+    }
+
+    #[test]
+    fn test_deserialize_without_archived_defaults_to_false() {
+        let repo: Repository = serde_json::from_str(&repo_json("")).unwrap();
+        assert!(!repo.archived);
+    }
+
+    #[test]
+    fn test_deserialize_fork_flag() {
+        let repo: Repository = serde_json::from_str(&repo_json(r#", "fork": true"#)).unwrap();
+        assert!(repo.fork);
+
+        let repo: Repository = serde_json::from_str(&repo_json("")).unwrap();
+        assert!(!repo.fork);
     }
 
     #[test]
@@ -166,9 +492,9 @@ mod tests {
     fn test_repository_selection_select_all() {
         let mut selection = RepositorySelection::new();
         let repos = vec![
-            create_test_repo(1, "repo1"),
-            create_test_repo(2, "repo2"),
-            create_test_repo(3, "repo3"),
+            create_test_repo(1, "repo1", "owner"),
+            create_test_repo(2, "repo2", "owner"),
+            create_test_repo(3, "repo3", "owner"),
         ];
 
         selection.select_all(&repos);
@@ -211,9 +537,78 @@ mod tests {
         assert!(!selection.is_selected(4));
     }
 
+    #[test]
+    fn test_matches_query() {
+        let repo = Repository {
+            description: Some("Test description".to_string()),
+            topics: vec!["rust".to_string(), "cli".to_string()],
+            ..create_test_repo(1, "Ripgrep", "owner")
+        };
+
+        assert!(repo.matches_query(""));
+        assert!(repo.matches_query("ripgrep"));
+        assert!(repo.matches_query("owner/rip"));
+        assert!(repo.matches_query("test desc"));
+        assert!(repo.matches_query("cli"));
+        assert!(!repo.matches_query("python"));
+    }
+
+    #[test]
+    fn test_normalize_homepage() {
+        assert_eq!(normalize_homepage(None), None);
+        assert_eq!(normalize_homepage(Some("  ".to_string())), None);
+        assert_eq!(
+            normalize_homepage(Some("example.com".to_string())),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            normalize_homepage(Some("http://example.com/docs ".to_string())),
+            Some("http://example.com/docs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_issues_url() {
+        let mut repo = create_test_repo(1, "test-repo", "owner");
+        assert_eq!(repo.issues_url(), "https://github.com/owner/test-repo/issues");
+
+        repo.html_url.push('/');
+        assert_eq!(repo.issues_url(), "https://github.com/owner/test-repo/issues");
+    }
+
+    #[test]
+    fn test_has_language() {
+        let mut repo = create_test_repo(1, "repo", "owner");
+        repo.language = Some("Rust".to_string());
+        assert!(repo.has_language("rust"));
+        assert!(!repo.has_language("go"));
+
+        repo.language = None;
+        assert!(!repo.has_language("rust"));
+    }
+
+    #[test]
+    fn test_split_language_filter() {
+        assert_eq!(split_language_filter("cli tool"), ("cli tool".to_string(), None));
+        assert_eq!(
+            split_language_filter("cli language:rust  tool"),
+            ("cli tool".to_string(), Some("rust".to_string()))
+        );
+        assert_eq!(split_language_filter("language:go"), (String::new(), Some("go".to_string())));
+        assert_eq!(split_language_filter("language:"), ("language:".to_string(), None));
+    }
+
+    #[test]
+    fn test_matches_query_without_description() {
+        let mut repo = create_test_repo(1, "repo", "owner");
+        repo.description = None;
+
+        assert!(!repo.matches_query("description"));
+    }
+
     #[test]
     fn test_repository_serialization() {
-        let repo = create_test_repo(123, "test-repo");
+        let repo = create_test_repo(123, "test-repo", "owner");
         let json = serde_json::to_string(&repo).unwrap();
 
         assert!(json.contains("\"id\":123"));
@@ -276,4 +671,152 @@ mod tests {
         assert!(repo.pushed_at.is_none());
         assert_eq!(repo.starred_order, 0); // default value
     }
+
+    #[test]
+    fn test_parse_repo_reference() {
+        let expected = Some(("sharkdp".to_string(), "bat".to_string()));
+        assert_eq!(parse_repo_reference("sharkdp/bat"), expected);
+        assert_eq!(parse_repo_reference("  sharkdp/bat  "), expected);
+        assert_eq!(parse_repo_reference("https://github.com/sharkdp/bat"), expected);
+        assert_eq!(parse_repo_reference("https://www.github.com/sharkdp/bat/"), expected);
+        assert_eq!(parse_repo_reference("github.com/sharkdp/bat/tree/master/src"), expected);
+        assert_eq!(parse_repo_reference("https://github.com/sharkdp/bat?tab=readme"), expected);
+        assert_eq!(parse_repo_reference("https://github.com/sharkdp/bat.git"), expected);
+        assert_eq!(parse_repo_reference("git@github.com:sharkdp/bat.git"), expected);
+        assert_eq!(
+            parse_repo_reference("rust-lang/rust.vim"),
+            Some(("rust-lang".to_string(), "rust.vim".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_repo_reference_rejects_invalid_input() {
+        assert_eq!(parse_repo_reference(""), None);
+        assert_eq!(parse_repo_reference("sharkdp"), None);
+        assert_eq!(parse_repo_reference("https://github.com/sharkdp"), None);
+        assert_eq!(parse_repo_reference("sharkdp/"), None);
+        assert_eq!(parse_repo_reference("shark dp/bat"), None);
+    }
+
+    fn create_owned_repo(id: u64, owner: &str, name: &str, description: Option<&str>) -> Repository {
+        Repository {
+            full_name: format!("{}/{}", owner, name),
+            owner: owner.to_string(),
+            description: description.map(str::to_string),
+            ..create_test_repo(id, name, "owner")
+        }
+    }
+
+    fn group_names(groups: Vec<Vec<&Repository>>) -> Vec<Vec<String>> {
+        groups
+            .into_iter()
+            .map(|group| group.into_iter().map(|r| r.full_name.clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_find_duplicate_groups_by_name() {
+        let repos = vec![
+            create_owned_repo(1, "old-org", "tool", None),
+            create_owned_repo(2, "sharkdp", "bat", None),
+            create_owned_repo(3, "new-org", "Tool", None),
+        ];
+        assert_eq!(group_names(find_duplicate_groups(&repos)), vec![vec!["old-org/tool", "new-org/Tool"]]);
+    }
+
+    #[test]
+    fn test_find_duplicate_groups_by_description() {
+        let repos = vec![
+            create_owned_repo(1, "a", "fd", Some("A simple, fast alternative to find.")),
+            create_owned_repo(2, "b", "fd-find", Some("a simple fast alternative to  FIND")),
+            create_owned_repo(3, "c", "x", Some("WIP")),
+            create_owned_repo(4, "d", "y", Some("wip")),
+        ];
+        // Short descriptions are too generic to group on
+        assert_eq!(group_names(find_duplicate_groups(&repos)), vec![vec!["a/fd", "b/fd-find"]]);
+    }
+
+    #[test]
+    fn test_find_duplicate_groups_merges_chains() {
+        let repos = vec![
+            create_owned_repo(1, "a", "one", Some("Library for parsing configuration files")),
+            create_owned_repo(2, "b", "two", None),
+            create_owned_repo(3, "c", "two", Some("Library for parsing configuration files")),
+            create_owned_repo(2, "b", "renamed", None),
+        ];
+        // 1 and 3 share a description, 3 and 2 a name, 2 and 4 an id
+        assert_eq!(
+            group_names(find_duplicate_groups(&repos)),
+            vec![vec!["a/one", "b/two", "c/two", "b/renamed"]]
+        );
+        assert!(find_duplicate_groups(&repos[..2]).is_empty());
+        assert!(find_duplicate_groups(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let now = Utc::now();
+        let mut repo = create_test_repo(1, "repo1", "owner");
+
+        repo.pushed_at = Some(now - Duration::days(400));
+        assert!(repo.is_stale(365, now));
+        assert!(!repo.is_stale(730, now));
+
+        repo.pushed_at = Some(now - Duration::days(10));
+        assert!(!repo.is_stale(180, now));
+
+        repo.pushed_at = None;
+        assert!(repo.is_stale(730, now));
+    }
+
+    #[test]
+    fn test_has_description() {
+        let mut repo = create_test_repo(1, "test", "owner");
+        repo.description = Some("Test description".to_string());
+        assert!(repo.has_description());
+
+        repo.description = Some("  ".to_string());
+        assert!(!repo.has_description());
+
+        repo.description = None;
+        assert!(!repo.has_description());
+    }
+
+    #[test]
+    fn test_invert_empty_selection_selects_all() {
+        let repos = vec![create_test_repo(1, "a", "owner"), create_test_repo(2, "b", "owner")];
+        let mut selection = RepositorySelection::new();
+
+        selection.invert(&repos);
+        assert_eq!(selection.count(), 2);
+    }
+
+    #[test]
+    fn test_invert_all_selected_clears() {
+        let repos = vec![create_test_repo(1, "a", "owner"), create_test_repo(2, "b", "owner")];
+        let mut selection = RepositorySelection::new();
+        selection.select_all(&repos);
+
+        selection.invert(&repos);
+        assert_eq!(selection.count(), 0);
+    }
+
+    #[test]
+    fn test_invert_partial_selection() {
+        let repos = vec![
+            create_test_repo(1, "a", "owner"),
+            create_test_repo(2, "b", "owner"),
+            create_test_repo(3, "c", "owner"),
+        ];
+        let mut selection = RepositorySelection::new();
+        selection.toggle(2);
+        // Selections outside the given repos are left alone
+        selection.toggle(99);
+
+        selection.invert(&repos);
+        assert!(selection.is_selected(1));
+        assert!(!selection.is_selected(2));
+        assert!(selection.is_selected(3));
+        assert!(selection.is_selected(99));
+    }
 }
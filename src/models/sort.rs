@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+/// Sort field for repositories
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortField {
+    /// When the repository was starred (API: created)
+    Starred,
+    /// When the repository was last pushed to (API: updated)
+    #[default]
+    Pushed,
+    /// Stargazers count (client-side)
+    Stars,
+    /// Forks count (client-side)
+    Forks,
+    /// Repository name (client-side)
+    Name,
+    /// Open issues count (client-side)
+    Issues,
+    /// Primary language, repositories without one last (client-side)
+    Language,
+}
+
+impl SortField {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortField::Starred => "Starred",
+            SortField::Pushed => "Pushed",
+            SortField::Stars => "Stars",
+            SortField::Forks => "Forks",
+            SortField::Name => "Name",
+            SortField::Issues => "Issues",
+            SortField::Language => "Language",
+        }
+    }
+
+    /// API parameter value; client-side fields fetch in starred order
+    pub fn api_value(&self) -> &'static str {
+        match self {
+            SortField::Starred => "created",
+            SortField::Pushed => "updated",
+            SortField::Stars | SortField::Forks | SortField::Name | SortField::Issues | SortField::Language => {
+                "created"
+            }
+        }
+    }
+
+    /// Whether this field is sorted locally instead of by the API
+    pub fn is_client_side(&self) -> bool {
+        matches!(
+            self,
+            SortField::Stars | SortField::Forks | SortField::Name | SortField::Issues | SortField::Language
+        )
+    }
+
+    pub fn all() -> &'static [SortField] {
+        &[
+            SortField::Starred,
+            SortField::Pushed,
+            SortField::Stars,
+            SortField::Forks,
+            SortField::Name,
+            SortField::Issues,
+            SortField::Language,
+        ]
+    }
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn toggle(&self) -> Self {
+        match self {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "↑",
+            SortDirection::Desc => "↓",
+        }
+    }
+
+    /// API parameter value
+    pub fn api_value(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_field_label() {
+        assert_eq!(SortField::Starred.label(), "Starred");
+        assert_eq!(SortField::Pushed.label(), "Pushed");
+        assert_eq!(SortField::Stars.label(), "Stars");
+        assert_eq!(SortField::Forks.label(), "Forks");
+        assert_eq!(SortField::Name.label(), "Name");
+        assert_eq!(SortField::Issues.label(), "Issues");
+        assert_eq!(SortField::Language.label(), "Language");
+    }
+
+    #[test]
+    fn test_sort_field_api_value() {
+        assert_eq!(SortField::Starred.api_value(), "created");
+        assert_eq!(SortField::Pushed.api_value(), "updated");
+    }
+
+    #[test]
+    fn test_sort_field_all() {
+        let all = SortField::all();
+        assert_eq!(all.len(), 7);
+        assert!(all.contains(&SortField::Starred));
+        assert!(all.contains(&SortField::Pushed));
+        assert!(all.contains(&SortField::Stars));
+        assert!(all.contains(&SortField::Forks));
+        assert!(all.contains(&SortField::Name));
+        assert!(all.contains(&SortField::Issues));
+        assert!(all.contains(&SortField::Language));
+    }
+
+    #[test]
+    fn test_sort_field_is_client_side() {
+        assert!(!SortField::Starred.is_client_side());
+        assert!(!SortField::Pushed.is_client_side());
+        assert!(SortField::Stars.is_client_side());
+        assert!(SortField::Forks.is_client_side());
+        assert!(SortField::Name.is_client_side());
+        assert!(SortField::Issues.is_client_side());
+        assert!(SortField::Language.is_client_side());
+    }
+
+    #[test]
+    fn test_sort_direction_toggle() {
+        assert_eq!(SortDirection::Asc.toggle(), SortDirection::Desc);
+        assert_eq!(SortDirection::Desc.toggle(), SortDirection::Asc);
+    }
+
+    #[test]
+    fn test_sort_direction_label() {
+        assert_eq!(SortDirection::Asc.label(), "↑");
+        assert_eq!(SortDirection::Desc.label(), "↓");
+    }
+
+    #[test]
+    fn test_sort_direction_api_value() {
+        assert_eq!(SortDirection::Asc.api_value(), "asc");
+        assert_eq!(SortDirection::Desc.api_value(), "desc");
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// A user-defined list that starred repositories can be organized into
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StarList {
+    /// GraphQL node ID, used to add repositories to the list
+    pub id: String,
+    pub name: String,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Which color theme the UI uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub fn toggle(&self) -> Self {
+        match self {
+            ThemeMode::Dark => ThemeMode::Light,
+            ThemeMode::Light => ThemeMode::Dark,
+        }
+    }
+
+    /// Label for the button that switches to the other theme
+    pub fn toggle_label(&self) -> &'static str {
+        match self {
+            ThemeMode::Dark => "☀ Light",
+            ThemeMode::Light => "☾ Dark",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_mode_toggle() {
+        assert_eq!(ThemeMode::default(), ThemeMode::Dark);
+        assert_eq!(ThemeMode::Dark.toggle(), ThemeMode::Light);
+        assert_eq!(ThemeMode::Light.toggle(), ThemeMode::Dark);
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// How the repository list is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewMode {
+    /// A card per repository, sized by `RowDensity`
+    #[default]
+    Cards,
+    /// One line per repository in columns, sorted by clicking the headers
+    Table,
+}
+
+impl ViewMode {
+    pub fn toggle(&self) -> Self {
+        match self {
+            ViewMode::Cards => ViewMode::Table,
+            ViewMode::Table => ViewMode::Cards,
+        }
+    }
+
+    pub fn is_table(&self) -> bool {
+        *self == ViewMode::Table
+    }
+
+    /// Label for the button that switches to the other layout
+    pub fn toggle_label(&self) -> &'static str {
+        match self {
+            ViewMode::Cards => "Table",
+            ViewMode::Table => "Cards",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_mode_toggle() {
+        assert_eq!(ViewMode::default(), ViewMode::Cards);
+        assert_eq!(ViewMode::Cards.toggle(), ViewMode::Table);
+        assert_eq!(ViewMode::Table.toggle(), ViewMode::Cards);
+        assert!(ViewMode::Table.is_table());
+        assert_eq!(ViewMode::Cards.toggle_label(), "Table");
+    }
+}
@@ -0,0 +1,421 @@
+use crate::models::{AppConfig, CacheConfig, Repository, DEFAULT_PROFILE};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+const CACHE_FILE: &str = "stars.json";
+const COMPRESSED_CACHE_FILE: &str = "stars.json.gz";
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A cached star list and when it was fetched
+#[derive(Debug, Clone, Deserialize)]
+pub struct StarCache {
+    pub fetched_at: DateTime<Utc>,
+    pub repositories: Vec<Repository>,
+    /// Whether every star was loaded, rather than just the first pages
+    #[serde(default)]
+    pub complete: bool,
+}
+
+impl StarCache {
+    /// Whether the cache is older than `ttl`
+    pub fn is_expired(&self, ttl: Duration, now: DateTime<Utc>) -> bool {
+        now - self.fetched_at > ttl
+    }
+}
+
+/// On-disk layout, borrowing the list so saving doesn't clone it
+#[derive(Serialize)]
+struct StarCacheRef<'a> {
+    fetched_at: DateTime<Utc>,
+    repositories: &'a [Repository],
+    complete: bool,
+}
+
+/// Cache files are either a `StarCache` or, from older versions, a bare list
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredCache {
+    Current(StarCache),
+    Legacy(Vec<Repository>),
+}
+
+impl From<StoredCache> for StarCache {
+    fn from(stored: StoredCache) -> Self {
+        match stored {
+            StoredCache::Current(cache) => cache,
+            // No timestamp was recorded, so treat it as already expired
+            StoredCache::Legacy(repositories) => StarCache {
+                fetched_at: DateTime::UNIX_EPOCH,
+                repositories,
+                complete: false,
+            },
+        }
+    }
+}
+
+/// Repositories starred and unstarred between two star lists
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepoDiff {
+    pub added: HashSet<u64>,
+    pub removed: HashSet<u64>,
+}
+
+impl RepoDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Short summary, e.g. "+3 new stars, -1 removed"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            let noun = if self.added.len() == 1 { "star" } else { "stars" };
+            parts.push(format!("+{} new {}", self.added.len(), noun));
+        }
+        if !self.removed.is_empty() {
+            parts.push(format!("-{} removed", self.removed.len()));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Compare a cached star list with a freshly fetched one. Both need to be
+/// complete lists, or unloaded pages show up as changes.
+pub fn diff_repos(old: &[Repository], new: &[Repository]) -> RepoDiff {
+    let old_ids: HashSet<u64> = old.iter().map(|r| r.id).collect();
+    let new_ids: HashSet<u64> = new.iter().map(|r| r.id).collect();
+    RepoDiff {
+        added: new_ids.difference(&old_ids).copied().collect(),
+        removed: old_ids.difference(&new_ids).copied().collect(),
+    }
+}
+
+/// Directory holding a profile's star cache. The default profile keeps the
+/// directory older versions used, so upgrading keeps the cache warm.
+fn profile_cache_dir(base: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        return base.to_path_buf();
+    }
+    // Escape anything that isn't safe in a file name, keeping names distinct
+    let name: String = profile
+        .bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' => (b as char).to_string(),
+            b => format!("_{:02x}", b),
+        })
+        .collect();
+    base.join("profiles").join(name)
+}
+
+pub struct CacheService;
+
+impl CacheService {
+    /// Load `profile`'s cached star list, returns None if no cache exists
+    pub fn load_repos(profile: &str) -> Result<Option<StarCache>> {
+        Self::load_from(&profile_cache_dir(&AppConfig::cache_dir(), profile))
+    }
+
+    /// Save `profile`'s star list to the cache, compressing it if configured.
+    /// `complete` records whether every star was loaded.
+    pub fn save_repos(profile: &str, repos: &[Repository], complete: bool, config: &CacheConfig) -> Result<()> {
+        Self::save_to(
+            &profile_cache_dir(&AppConfig::cache_dir(), profile),
+            repos,
+            Utc::now(),
+            complete,
+            config.should_compress(repos.len()),
+        )?;
+        Ok(())
+    }
+
+    /// Remove `profile`'s cached star list, if any
+    pub fn clear(profile: &str) -> Result<()> {
+        Self::clear_in(&profile_cache_dir(&AppConfig::cache_dir(), profile))
+    }
+
+    /// Load the cache from a directory, preferring the compressed file
+    pub fn load_from(dir: &Path) -> Result<Option<StarCache>> {
+        let Some(path) = Self::existing_cache_path(dir) else {
+            return Ok(None);
+        };
+
+        let mut reader = BufReader::new(File::open(&path).context("Failed to open cache file")?);
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .context("Failed to read cache file")?;
+
+        // Sniff the content rather than trusting the extension, so a cache
+        // written by either format is always readable
+        let stored: StoredCache = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut json = Vec::new();
+            GzDecoder::new(bytes.as_slice())
+                .read_to_end(&mut json)
+                .context("Failed to decompress cache file")?;
+            serde_json::from_slice(&json)
+        } else {
+            serde_json::from_slice(&bytes)
+        }
+        .context("Failed to parse cache file")?;
+
+        Ok(Some(stored.into()))
+    }
+
+    /// Write the cache into a directory, returns the path written
+    pub fn save_to(
+        dir: &Path,
+        repos: &[Repository],
+        fetched_at: DateTime<Utc>,
+        complete: bool,
+        compress: bool,
+    ) -> Result<PathBuf> {
+        fs::create_dir_all(dir).context("Failed to create cache directory")?;
+
+        let (path, stale) = if compress {
+            (dir.join(COMPRESSED_CACHE_FILE), dir.join(CACHE_FILE))
+        } else {
+            (dir.join(CACHE_FILE), dir.join(COMPRESSED_CACHE_FILE))
+        };
+
+        let cache = StarCacheRef {
+            fetched_at,
+            repositories: repos,
+            complete,
+        };
+        let writer = BufWriter::new(File::create(&path).context("Failed to create cache file")?);
+        if compress {
+            let mut encoder = GzEncoder::new(writer, Compression::fast());
+            serde_json::to_writer(&mut encoder, &cache).context("Failed to serialize cache")?;
+            encoder
+                .finish()
+                .and_then(|mut w| w.flush())
+                .context("Failed to write cache file")?;
+        } else {
+            let mut writer = writer;
+            serde_json::to_writer(&mut writer, &cache).context("Failed to serialize cache")?;
+            writer.flush().context("Failed to write cache file")?;
+        }
+
+        // Drop the other format so a stale copy is never loaded later
+        if stale.exists() {
+            fs::remove_file(&stale).context("Failed to remove stale cache file")?;
+        }
+
+        Ok(path)
+    }
+
+    /// Remove both cache formats from a directory
+    pub fn clear_in(dir: &Path) -> Result<()> {
+        for name in [CACHE_FILE, COMPRESSED_CACHE_FILE] {
+            let path = dir.join(name);
+            if path.exists() {
+                fs::remove_file(&path).context("Failed to remove cache file")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn existing_cache_path(dir: &Path) -> Option<PathBuf> {
+        [COMPRESSED_CACHE_FILE, CACHE_FILE]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.exists())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::create_test_repo;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn numbered_repo(id: u64) -> Repository {
+        Repository {
+            description: Some("A fairly typical repository description".to_string()),
+            language: Some("Rust".to_string()),
+            topics: vec!["rust".to_string(), "cli".to_string()],
+            starred_order: id as u32,
+            ..create_test_repo(id, &format!("repo-{}", id), "owner")
+        }
+    }
+
+    fn create_test_repos(count: u64) -> Vec<Repository> {
+        (0..count).map(numbered_repo).collect()
+    }
+
+    #[test]
+    fn test_load_missing_cache_returns_none() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(CacheService::load_from(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_uncompressed_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let repos = create_test_repos(3);
+
+        let path = CacheService::save_to(temp_dir.path(), &repos, Utc::now(), false, false).unwrap();
+        assert!(path.ends_with(CACHE_FILE));
+
+        let loaded = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.repositories.len(), 3);
+        assert_eq!(loaded.repositories[2].full_name, "owner/repo-2");
+    }
+
+    #[test]
+    fn test_compressed_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let repos = create_test_repos(3);
+
+        let path = CacheService::save_to(temp_dir.path(), &repos, Utc::now(), false, true).unwrap();
+        assert!(path.ends_with(COMPRESSED_CACHE_FILE));
+        assert!(fs::read(&path).unwrap().starts_with(&GZIP_MAGIC));
+
+        let loaded = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.repositories.len(), 3);
+        assert_eq!(loaded.repositories[0].id, 0);
+    }
+
+    #[test]
+    fn test_switching_format_removes_stale_file() {
+        let temp_dir = TempDir::new().unwrap();
+
+        CacheService::save_to(temp_dir.path(), &create_test_repos(1), Utc::now(), false, false).unwrap();
+        CacheService::save_to(temp_dir.path(), &create_test_repos(2), Utc::now(), false, true).unwrap();
+
+        assert!(!temp_dir.path().join(CACHE_FILE).exists());
+        let loaded = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.repositories.len(), 2);
+    }
+
+    #[test]
+    fn test_reads_uncompressed_content_with_gz_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let json = serde_json::to_vec(&create_test_repos(2)).unwrap();
+        fs::write(temp_dir.path().join(COMPRESSED_CACHE_FILE), json).unwrap();
+
+        let loaded = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.repositories.len(), 2);
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_fetched_at() {
+        let temp_dir = TempDir::new().unwrap();
+        let fetched_at = Utc::now() - Duration::hours(3);
+
+        CacheService::save_to(temp_dir.path(), &create_test_repos(1), fetched_at, false, false).unwrap();
+
+        let loaded = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.fetched_at, fetched_at);
+        assert!(!loaded.is_expired(Duration::hours(24), Utc::now()));
+        assert!(loaded.is_expired(Duration::hours(1), Utc::now()));
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_complete_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        CacheService::save_to(temp_dir.path(), &create_test_repos(2), Utc::now(), true, false).unwrap();
+        assert!(CacheService::load_from(temp_dir.path()).unwrap().unwrap().complete);
+
+        CacheService::save_to(temp_dir.path(), &create_test_repos(2), Utc::now(), false, true).unwrap();
+        assert!(!CacheService::load_from(temp_dir.path()).unwrap().unwrap().complete);
+    }
+
+    #[test]
+    fn test_diff_repos() {
+        let old = create_test_repos(4);
+        let mut new: Vec<Repository> = old[1..].to_vec();
+        new.push(numbered_repo(10));
+        new.push(numbered_repo(11));
+
+        let diff = diff_repos(&old, &new);
+        assert_eq!(diff.added, HashSet::from([10, 11]));
+        assert_eq!(diff.removed, HashSet::from([0]));
+        assert_eq!(diff.summary(), "+2 new stars, -1 removed");
+    }
+
+    #[test]
+    fn test_diff_repos_unchanged_is_empty() {
+        let repos = create_test_repos(3);
+        let diff = diff_repos(&repos, &repos);
+        assert!(diff.is_empty());
+        assert_eq!(diff.summary(), "");
+
+        let diff = diff_repos(&[], &create_test_repos(1));
+        assert_eq!(diff.summary(), "+1 new star");
+    }
+
+    #[test]
+    fn test_legacy_list_loads_as_expired() {
+        let temp_dir = TempDir::new().unwrap();
+        let json = serde_json::to_vec(&create_test_repos(2)).unwrap();
+        fs::write(temp_dir.path().join(CACHE_FILE), json).unwrap();
+
+        let loaded = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.repositories.len(), 2);
+        assert!(loaded.is_expired(Duration::hours(24), Utc::now()));
+        assert!(!loaded.complete);
+    }
+
+    #[test]
+    fn test_profile_cache_dirs_are_separate() {
+        let base = Path::new("/cache");
+        // Where caches were kept before profiles existed
+        assert_eq!(profile_cache_dir(base, DEFAULT_PROFILE), base);
+        assert_eq!(profile_cache_dir(base, "work-2"), base.join("profiles/work-2"));
+        assert_eq!(profile_cache_dir(base, "../me"), base.join("profiles/_2e_2e_2fme"));
+        assert_ne!(profile_cache_dir(base, "a b"), profile_cache_dir(base, "a_b"));
+    }
+
+    #[test]
+    fn test_profiles_keep_their_own_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = profile_cache_dir(temp_dir.path(), "work");
+        CacheService::save_to(temp_dir.path(), &create_test_repos(1), Utc::now(), true, false).unwrap();
+        CacheService::save_to(&work, &create_test_repos(3), Utc::now(), true, false).unwrap();
+
+        CacheService::clear_in(&work).unwrap();
+        assert!(CacheService::load_from(&work).unwrap().is_none());
+        let default = CacheService::load_from(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(default.repositories.len(), 1);
+    }
+
+    #[test]
+    fn test_clear_removes_both_formats() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(CACHE_FILE), "[]").unwrap();
+        fs::write(temp_dir.path().join(COMPRESSED_CACHE_FILE), "[]").unwrap();
+
+        CacheService::clear_in(temp_dir.path()).unwrap();
+
+        assert!(CacheService::load_from(temp_dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_compression_shrinks_large_lists() {
+        let plain_dir = TempDir::new().unwrap();
+        let gz_dir = TempDir::new().unwrap();
+        let repos = create_test_repos(5_000);
+
+        let plain_path = CacheService::save_to(plain_dir.path(), &repos, Utc::now(), false, false).unwrap();
+        let gz_path = CacheService::save_to(gz_dir.path(), &repos, Utc::now(), false, true).unwrap();
+
+        let plain_size = fs::metadata(plain_path).unwrap().len();
+        let gz_size = fs::metadata(gz_path).unwrap().len();
+        assert!(
+            gz_size * 4 < plain_size,
+            "gzip cache is {} bytes, plain JSON {} bytes",
+            gz_size,
+            plain_size
+        );
+    }
+}
@@ -1,12 +1,198 @@
-use crate::models::AppConfig;
+use crate::models::{AppConfig, DEFAULT_PROFILE};
 use anyhow::{Context, Result};
 use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Service name the token is stored under in the OS keychain
+const KEYRING_SERVICE: &str = "github-starcleaner";
+const KEYRING_USER: &str = "personal_access_token";
+
+/// Why the config file couldn't be written
+#[derive(Debug)]
+pub enum ConfigSaveError {
+    /// The config directory or file isn't writable
+    PermissionDenied(PathBuf),
+    /// The settings couldn't be turned into TOML
+    Serialize(toml::ser::Error),
+    /// Any other write failure, e.g. a full disk
+    Io(io::Error),
+}
+
+impl ConfigSaveError {
+    fn from_io(path: &Path, err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => {
+                ConfigSaveError::PermissionDenied(path.to_path_buf())
+            }
+            _ => ConfigSaveError::Io(err),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigSaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSaveError::PermissionDenied(path) => write!(f, "{} is not writable", path.display()),
+            ConfigSaveError::Serialize(e) => write!(f, "invalid settings: {}", e),
+            ConfigSaveError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigSaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigSaveError::PermissionDenied(_) => None,
+            ConfigSaveError::Serialize(e) => Some(e),
+            ConfigSaveError::Io(e) => Some(e),
+        }
+    }
+}
+
+/// Non-fatal warning for a failed config save, e.g. "Couldn't save settings:
+/// /home/me/.config/github-starcleaner is not writable"
+pub fn config_save_warning(err: &anyhow::Error) -> String {
+    let reason = err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<ConfigSaveError>())
+        .map_or_else(|| err.to_string(), ToString::to_string);
+    format!("Couldn't save settings: {}", reason)
+}
 
 pub struct ConfigService;
 
 impl ConfigService {
-    /// Load config from file, returns default if not exists
+    /// Load config from file, returns default if not exists.
+    /// The token is taken from the OS keychain when present; a plaintext
+    /// token left in the TOML by older versions is migrated there.
     pub fn load() -> Result<AppConfig> {
+        let mut config = Self::load_file()?;
+
+        match Self::load_token(config.active_profile_name()) {
+            Ok(Some(token)) => {
+                config.github.personal_access_token = Some(token);
+                config.github.token_in_keychain = true;
+            }
+            Ok(None) => {
+                if let Some(token) = config.get_token().map(str::to_string) {
+                    Self::migrate_plaintext_token(&mut config, &token);
+                }
+            }
+            Err(e) => tracing::warn!("Keychain unavailable, using config file token: {}", e),
+        }
+
+        Ok(config)
+    }
+
+    /// Save config to file, creating directory if needed. Fails with a
+    /// `ConfigSaveError` telling an unwritable directory apart from other errors.
+    pub fn save(config: &AppConfig) -> Result<()> {
+        Self::save_to(&AppConfig::config_dir(), &AppConfig::config_path(), config, |path, content| {
+            fs::write(path, content)
+        })?;
+        Ok(())
+    }
+
+    /// Serialize `config` and hand it to `write`, which stands in for
+    /// `fs::write` in tests
+    fn save_to(
+        dir: &Path,
+        path: &Path,
+        config: &AppConfig,
+        write: impl FnOnce(&Path, &str) -> io::Result<()>,
+    ) -> std::result::Result<(), ConfigSaveError> {
+        fs::create_dir_all(dir).map_err(|e| ConfigSaveError::from_io(dir, e))?;
+        let content = Self::serialize(config).map_err(ConfigSaveError::Serialize)?;
+        write(path, &content).map_err(|e| ConfigSaveError::from_io(path, e))
+    }
+
+    /// Save PAT for the active profile to the keychain (falling back to the
+    /// config file). Returns the config with the token set, and a warning if
+    /// it couldn't be written, in which case the token lasts for this session.
+    pub fn save_token(token: &str) -> (AppConfig, Option<String>) {
+        let mut config = Self::load().unwrap_or_default();
+        config.github.personal_access_token = Some(token.to_string());
+        let stored = Self::keyring_entry(config.active_profile_name())
+            .and_then(|entry| entry.set_password(token).context("Failed to write keychain entry"));
+        config.github.token_in_keychain = match stored {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Failed to store token in keychain, saving to config file: {:#}", e);
+                false
+            }
+        };
+        let warning = Self::save(&config).err().map(|e| {
+            tracing::warn!("Failed to save token: {:#}", e);
+            if config.github.token_in_keychain {
+                config_save_warning(&e)
+            } else {
+                format!("{}. You'll need to sign in again next time.", config_save_warning(&e))
+            }
+        });
+        (config, warning)
+    }
+
+    /// Switch to profile `index`, returns the config with that profile's
+    /// token (if any) loaded, and a warning if the switch couldn't be saved
+    pub fn switch_profile(index: usize) -> Result<(AppConfig, Option<String>)> {
+        Self::activate_profile(Self::load()?, index)
+    }
+
+    /// Add a profile named `name` (or find the existing one) and switch to it
+    pub fn add_profile(name: &str) -> Result<(AppConfig, Option<String>)> {
+        let mut config = Self::load()?;
+        let index = config.add_profile(name);
+        Self::activate_profile(config, index)
+    }
+
+    fn activate_profile(mut config: AppConfig, index: usize) -> Result<(AppConfig, Option<String>)> {
+        let name = config
+            .profiles
+            .get(index)
+            .context("No such profile")?
+            .name
+            .clone();
+        let token = Self::load_token(&name).unwrap_or_else(|e| {
+            tracing::warn!("Keychain unavailable, using config file token: {}", e);
+            None
+        });
+        config.activate_profile(index, token);
+        // The switch still applies for this session
+        let warning = Self::save(&config).err().map(|e| {
+            tracing::warn!("Failed to save account switch: {:#}", e);
+            format!("{}. The app will start as the previous account next time.", config_save_warning(&e))
+        });
+        Ok((config, warning))
+    }
+
+    /// Read a profile's token from the OS keychain
+    pub fn load_token(profile: &str) -> Result<Option<String>> {
+        match Self::keyring_entry(profile)?.get_password() {
+            Ok(token) if !token.is_empty() => Ok(Some(token)),
+            Ok(_) | Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).context("Failed to read token from keychain"),
+        }
+    }
+
+    /// Clear the active profile's token from both the keychain and the
+    /// config file, leaving other profiles signed in
+    pub fn clear_token() -> Result<()> {
+        let mut config = Self::load_file().unwrap_or_default();
+        // A keychain that can't be opened has nothing of ours to delete
+        match Self::keyring_entry(config.active_profile_name()) {
+            Ok(entry) => match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(e) => tracing::warn!("Failed to remove token from keychain: {}", e),
+            },
+            Err(e) => tracing::warn!("Keychain unavailable, clearing config file token only: {:#}", e),
+        }
+
+        config.github.personal_access_token = None;
+        Self::save(&config)
+    }
+
+    fn load_file() -> Result<AppConfig> {
         let path = AppConfig::config_path();
 
         if !path.exists() {
@@ -16,38 +202,54 @@ impl ConfigService {
         let content =
             fs::read_to_string(&path).context("Failed to read config file")?;
 
-        let config: AppConfig =
+        let mut config: AppConfig =
             toml::from_str(&content).context("Failed to parse config file")?;
+        config.clamp_values();
 
         Ok(config)
     }
 
-    /// Save config to file, creating directory if needed
-    pub fn save(config: &AppConfig) -> Result<()> {
-        let dir = AppConfig::config_dir();
-        fs::create_dir_all(&dir).context("Failed to create config directory")?;
-
-        let content =
-            toml::to_string_pretty(config).context("Failed to serialize config")?;
+    /// Serialize config for disk, leaving out a token that lives in the keychain
+    fn serialize(config: &AppConfig) -> std::result::Result<String, toml::ser::Error> {
+        let mut on_disk = config.clone();
+        if on_disk.github.token_in_keychain {
+            on_disk.github.personal_access_token = None;
+        }
+        toml::to_string_pretty(&on_disk)
+    }
 
-        let path = AppConfig::config_path();
-        fs::write(&path, content).context("Failed to write config file")?;
+    /// Move a plaintext token into the keychain and null it out in the TOML
+    fn migrate_plaintext_token(config: &mut AppConfig, token: &str) {
+        let stored = Self::keyring_entry(config.active_profile_name()).and_then(|entry| {
+            entry
+                .set_password(token)
+                .context("Failed to store token in keychain")
+        });
 
-        Ok(())
+        match stored {
+            Ok(()) => {
+                config.github.token_in_keychain = true;
+                if let Err(e) = Self::save(config) {
+                    tracing::warn!("Failed to remove plaintext token from config: {}", e);
+                }
+                tracing::info!("Migrated token from config file to keychain");
+            }
+            Err(e) => tracing::warn!("Failed to migrate token to keychain: {}", e),
+        }
     }
 
-    /// Save PAT to config
-    pub fn save_token(token: &str) -> Result<()> {
-        let mut config = Self::load().unwrap_or_default();
-        config.github.personal_access_token = Some(token.to_string());
-        Self::save(&config)
+    fn keyring_entry(profile: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(KEYRING_SERVICE, &keyring_user(profile)).context("Failed to open keychain entry")
     }
+}
 
-    /// Clear the saved token
-    pub fn clear_token() -> Result<()> {
-        let mut config = Self::load().unwrap_or_default();
-        config.github.personal_access_token = None;
-        Self::save(&config)
+/// Keychain account a profile's token is stored under. The default profile
+/// keeps the account older versions used, so upgrading doesn't sign anyone out.
+fn keyring_user(profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        KEYRING_USER.to_string()
+    } else {
+        format!("{}:{}", KEYRING_USER, profile)
     }
 }
 
@@ -75,7 +277,9 @@ mod tests {
             let config = AppConfig {
                 github: crate::models::GitHubConfig {
                     personal_access_token: Some("test_token_123".to_string()),
+                    ..Default::default()
                 },
+                ..Default::default()
             };
 
             // Save directly to temp file
@@ -95,7 +299,9 @@ mod tests {
         let config = AppConfig {
             github: crate::models::GitHubConfig {
                 personal_access_token: Some("ghp_abcdef123456".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
 
         let content = toml::to_string_pretty(&config).unwrap();
@@ -118,7 +324,9 @@ mod tests {
         let config = AppConfig {
             github: crate::models::GitHubConfig {
                 personal_access_token: Some("my_secret_token".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
 
         let content = toml::to_string_pretty(&config).unwrap();
@@ -132,7 +340,9 @@ mod tests {
         let config = AppConfig {
             github: crate::models::GitHubConfig {
                 personal_access_token: Some("".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
 
         let content = toml::to_string_pretty(&config).unwrap();
@@ -142,12 +352,137 @@ mod tests {
         assert!(!parsed.has_token());
     }
 
+    #[test]
+    fn test_serialize_omits_keychain_token() {
+        let config = AppConfig {
+            github: crate::models::GitHubConfig {
+                personal_access_token: Some("ghp_in_keychain".to_string()),
+                token_in_keychain: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let content = ConfigService::serialize(&config).unwrap();
+        assert!(!content.contains("ghp_in_keychain"));
+
+        let parsed: AppConfig = toml::from_str(&content).unwrap();
+        assert!(!parsed.has_token());
+    }
+
+    #[test]
+    fn test_serialize_keeps_fallback_plaintext_token() {
+        let config = AppConfig {
+            github: crate::models::GitHubConfig {
+                personal_access_token: Some("ghp_plaintext".to_string()),
+                token_in_keychain: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let content = ConfigService::serialize(&config).unwrap();
+        assert!(content.contains("ghp_plaintext"));
+    }
+
+    #[test]
+    fn test_keyring_user_per_profile() {
+        assert_eq!(keyring_user(DEFAULT_PROFILE), KEYRING_USER);
+        assert_eq!(keyring_user("work"), "personal_access_token:work");
+    }
+
+    #[test]
+    fn test_serialize_keeps_profiles_and_active_index() {
+        let mut config = AppConfig {
+            github: crate::models::GitHubConfig {
+                personal_access_token: Some("ghp_personal".to_string()),
+                token_in_keychain: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let work = config.add_profile("work");
+        config.activate_profile(work, Some("ghp_work".to_string()));
+
+        let content = ConfigService::serialize(&config).unwrap();
+        assert!(!content.contains("ghp_work"));
+
+        let parsed: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.active_profile_name(), "work");
+        assert_eq!(parsed.profiles[0].personal_access_token.as_deref(), Some("ghp_personal"));
+    }
+
+    #[test]
+    fn test_serialize_keeps_presets() {
+        let config = AppConfig {
+            presets: vec![crate::models::FilterPreset {
+                name: "Old forks".to_string(),
+                stale_threshold_days: Some(730),
+                fork_filter: crate::models::ForkFilter::Only,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let content = ConfigService::serialize(&config).unwrap();
+        let parsed: AppConfig = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.presets, config.presets);
+    }
+
+    #[test]
+    fn test_save_to_writes_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("github-starcleaner");
+        let path = dir.join("config.toml");
+
+        ConfigService::save_to(&dir, &path, &AppConfig::default(), |path, content| {
+            fs::write(path, content)
+        })
+        .unwrap();
+
+        let parsed: AppConfig = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(!parsed.has_token());
+    }
+
+    #[test]
+    fn test_save_to_maps_permission_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+
+        let err = ConfigService::save_to(temp_dir.path(), &path, &AppConfig::default(), |_, _| {
+            Err(io::Error::from(io::ErrorKind::PermissionDenied))
+        })
+        .unwrap_err();
+        assert!(matches!(&err, ConfigSaveError::PermissionDenied(p) if *p == path));
+
+        let warning = config_save_warning(&anyhow::Error::from(err));
+        assert_eq!(warning, format!("Couldn't save settings: {} is not writable", path.display()));
+    }
+
+    #[test]
+    fn test_save_to_keeps_other_io_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+
+        let err = ConfigService::save_to(temp_dir.path(), &path, &AppConfig::default(), |_, _| {
+            Err(io::Error::other("disk full"))
+        })
+        .unwrap_err();
+        assert!(matches!(err, ConfigSaveError::Io(_)));
+        assert_eq!(
+            config_save_warning(&anyhow::Error::from(err).context("Failed to save token")),
+            "Couldn't save settings: disk full"
+        );
+    }
+
     #[test]
     fn test_config_roundtrip() {
         let original = AppConfig {
             github: crate::models::GitHubConfig {
                 personal_access_token: Some("token_with_special_chars_!@#$%".to_string()),
+                ..Default::default()
             },
+            ..Default::default()
         };
 
         let serialized = toml::to_string_pretty(&original).unwrap();
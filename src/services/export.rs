@@ -0,0 +1,179 @@
+use crate::models::Repository;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+const CSV_HEADER: &str = "full_name,html_url,description,language,stargazers_count,pushed_at";
+
+/// JSON lines exports flush after this many repositories
+const JSONL_FLUSH_EVERY: usize = 500;
+
+pub struct ExportService;
+
+impl ExportService {
+    /// Export to CSV if the path ends in `.csv`, JSON lines for `.jsonl`,
+    /// otherwise to JSON
+    pub fn export(repos: &[Repository], path: &Path) -> Result<()> {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("csv") => Self::export_csv(repos, path),
+            Some("jsonl") => {
+                let file = File::create(path).context("Failed to create export file")?;
+                Self::export_jsonl(repos, BufWriter::new(file))
+            }
+            _ => Self::export_json(repos, path),
+        }
+    }
+
+    /// Write the full repository records as pretty-printed JSON
+    pub fn export_json(repos: &[Repository], path: &Path) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path).context("Failed to create export file")?);
+        serde_json::to_writer_pretty(&mut writer, repos).context("Failed to serialize export")?;
+        writer.flush().context("Failed to write export file")?;
+        Ok(())
+    }
+
+    /// Write one full repository record per line, flushing as it goes so
+    /// very large lists are never held as a single JSON document
+    pub fn export_jsonl(repos: &[Repository], mut writer: impl Write) -> Result<()> {
+        for (index, repo) in repos.iter().enumerate() {
+            serde_json::to_writer(&mut writer, repo).context("Failed to serialize export")?;
+            writer.write_all(b"\n").context("Failed to write export file")?;
+            if (index + 1).is_multiple_of(JSONL_FLUSH_EVERY) {
+                writer.flush().context("Failed to write export file")?;
+            }
+        }
+        writer.flush().context("Failed to write export file")?;
+        Ok(())
+    }
+
+    /// Write a summary of each repository as CSV
+    pub fn export_csv(repos: &[Repository], path: &Path) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path).context("Failed to create export file")?);
+        writeln!(writer, "{}", CSV_HEADER).context("Failed to write export file")?;
+
+        for repo in repos {
+            let fields = [
+                repo.full_name.clone(),
+                repo.html_url.clone(),
+                repo.description.clone().unwrap_or_default(),
+                repo.language.clone().unwrap_or_default(),
+                repo.stargazers_count.to_string(),
+                repo.pushed_at.map(|dt| dt.to_rfc3339()).unwrap_or_default(),
+            ];
+            let row: Vec<String> = fields.iter().map(|field| escape_csv_field(field)).collect();
+            writeln!(writer, "{}", row.join(",")).context("Failed to write export file")?;
+        }
+
+        writer.flush().context("Failed to write export file")?;
+        Ok(())
+    }
+}
+
+/// Quote a field if it contains a delimiter, quote, or line break
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::create_test_repo;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn described_repo(id: u64, description: Option<&str>) -> Repository {
+        Repository {
+            description: description.map(|d| d.to_string()),
+            language: Some("Rust".to_string()),
+            stargazers_count: 42,
+            topics: vec!["cli".to_string()],
+            starred_order: id as u32,
+            ..create_test_repo(id, &format!("repo-{}", id), "owner")
+        }
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("stars.json");
+        let repos = vec![described_repo(1, Some("First")), described_repo(2, None)];
+
+        ExportService::export_json(&repos, &path).unwrap();
+
+        let loaded: Vec<Repository> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].full_name, "owner/repo-1");
+        assert_eq!(loaded[0].description.as_deref(), Some("First"));
+        assert_eq!(loaded[1].description, None);
+        assert_eq!(loaded[1].topics, vec!["cli"]);
+    }
+
+    #[test]
+    fn test_jsonl_lines_parse_back() {
+        let repos = vec![
+            described_repo(1, Some("First\nwith a line break")),
+            described_repo(2, None),
+            described_repo(3, Some("Third")),
+        ];
+        let mut output = Vec::new();
+
+        ExportService::export_jsonl(&repos, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let loaded: Vec<Repository> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[0].description.as_deref(), Some("First\nwith a line break"));
+        assert_eq!(loaded[1].full_name, "owner/repo-2");
+        assert_eq!(loaded[2].starred_order, 3);
+    }
+
+    #[test]
+    fn test_export_writes_jsonl_for_jsonl_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("stars.JSONL");
+
+        ExportService::export(&[described_repo(1, None), described_repo(2, None)], &path).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        assert!(contents.starts_with('{'));
+    }
+
+    #[test]
+    fn test_csv_export_escapes_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("stars.csv");
+        let repos = vec![described_repo(1, Some("Fast, \"simple\" tool"))];
+
+        ExportService::export(&repos, &path).unwrap();
+
+        let csv = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "owner/repo-1,https://github.com/owner/repo-1,\"Fast, \"\"simple\"\" tool\",Rust,42,"
+        );
+    }
+
+    #[test]
+    fn test_export_picks_format_from_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("stars.json");
+
+        ExportService::export(&[described_repo(1, None)], &path).unwrap();
+
+        assert!(fs::read_to_string(&path).unwrap().trim_start().starts_with('['));
+    }
+}
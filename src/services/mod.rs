@@ -1,5 +1,9 @@
+pub mod cache;
 pub mod config;
+pub mod export;
 pub mod github;
 
+pub use cache::*;
 pub use config::*;
+pub use export::*;
 pub use github::*;
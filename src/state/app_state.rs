@@ -1,6 +1,15 @@
-use crate::models::{AppConfig, Repository, RepositorySelection};
-use crate::services::{is_token_expired_error, ConfigService, GitHubService};
+pub use crate::models::{ForkFilter, SortDirection, SortField};
+use crate::models::{find_duplicate_groups, split_language_filter, AppConfig, FilterPreset, RateLimit, RepoActivity, Repository, RepositorySelection, RowDensity, StarList, ViewMode, WindowState};
+use crate::services::{config_save_warning, diff_repos, has_starring_scope, is_token_expired_error, proxy_error_message, CacheService, ConfigService, GitHubService, RepoDiff, StarCache};
+use chrono::{DateTime, Utc};
 use gpui::Global;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Current view/screen in the application
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -8,6 +17,9 @@ pub enum AppScreen {
     #[default]
     Setup,
     Loading,
+    /// Loading failed for a reason other than the token, which is kept so
+    /// the load can be retried
+    LoadFailed,
     RepositoryList,
 }
 
@@ -18,73 +30,452 @@ pub enum PendingAction {
     UnstarSingle(u64, String, String, String),
     /// Unstar multiple selected repos: count
     UnstarSelected(usize),
+    /// Unstar the loaded repos that matched a filter when the dialog opened:
+    /// (filter, their ids)
+    UnstarByFilter(FilterSpec, Vec<u64>),
+    /// Open more than `OPEN_SELECTED_CONFIRM_THRESHOLD` selected repos in the browser: count
+    OpenSelected(usize),
     /// Logout
     Logout,
 }
 
-/// Sort field for repositories (API-supported options only)
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub enum SortField {
-    /// When the repository was starred (API: created)
-    Starred,
-    /// When the repository was last pushed to (API: updated)
-    #[default]
-    Pushed,
+/// Snapshot of the list filters, used both for display and for unstarring
+/// everything that matches
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FilterSpec {
+    /// Lowercased, trimmed search query
+    pub query: String,
+    /// From a `language:` term in the search box
+    pub language: Option<String>,
+    pub topic: Option<String>,
+    pub stale_threshold_days: Option<u32>,
+    pub archived_only: bool,
+    pub no_description_only: bool,
+    pub fork_filter: ForkFilter,
+    /// Only repositories owned by this login, from the "Mine" toggle
+    pub owner: Option<String>,
+    /// License name, or `NO_LICENSE` for repositories without one
+    pub license: Option<String>,
+    /// Match `query` fuzzily against names and topics instead of as a substring
+    pub fuzzy: bool,
+    /// Only likely duplicate stars, from "Find duplicates". Depends on the
+    /// whole list, so `AppState::apply_filters` applies it rather than `matches`.
+    pub duplicates_only: bool,
 }
 
-impl SortField {
-    pub fn label(&self) -> &'static str {
-        match self {
-            SortField::Starred => "Starred",
-            SortField::Pushed => "Pushed",
+impl FilterSpec {
+    pub fn matches(&self, repo: &Repository, now: DateTime<Utc>) -> bool {
+        self.matches_query(repo)
+            && self.language.as_ref().is_none_or(|language| repo.has_language(language))
+            && self.topic.as_ref().is_none_or(|topic| repo.topics.contains(topic))
+            && self.stale_threshold_days.is_none_or(|days| repo.is_stale(days, now))
+            && (!self.archived_only || repo.archived)
+            && (!self.no_description_only || !repo.has_description())
+            && self.fork_filter.matches(repo)
+            && self.owner.as_ref().is_none_or(|owner| repo.owner.eq_ignore_ascii_case(owner))
+            && self.license.as_ref().is_none_or(|license| match license.as_str() {
+                NO_LICENSE => repo.license.is_none(),
+                license => repo.license.as_deref() == Some(license),
+            })
+    }
+
+    fn matches_query(&self, repo: &Repository) -> bool {
+        if self.fuzzy && !self.query.is_empty() {
+            repo.fuzzy_query_score(&self.query).is_some()
+        } else {
+            repo.matches_query(&self.query)
         }
     }
 
-    /// API parameter value
-    pub fn api_value(&self) -> &'static str {
-        match self {
-            SortField::Starred => "created",
-            SortField::Pushed => "updated",
+    /// Whether any filter is narrowing the list. Fuzzy mode alone isn't.
+    pub fn is_active(&self) -> bool {
+        *self
+            != FilterSpec {
+                fuzzy: self.fuzzy,
+                ..Default::default()
+            }
+    }
+
+    /// Describe `count` matching repos, e.g. "42 archived repositories"
+    pub fn describe(&self, count: usize) -> String {
+        let mut words = vec![count.to_string()];
+        if self.duplicates_only {
+            words.push("likely duplicate".to_string());
+        }
+        if self.archived_only {
+            words.push("archived".to_string());
+        }
+        if self.fork_filter == ForkFilter::Only {
+            words.push("forked".to_string());
+        }
+        words.push(if count == 1 { "repository" } else { "repositories" }.to_string());
+
+        if self.fork_filter == ForkFilter::Exclude {
+            words.push("(excluding forks)".to_string());
+        }
+        if self.no_description_only {
+            words.push("without a description".to_string());
         }
+        if let Some(days) = self.stale_threshold_days {
+            words.push(format!("not pushed to in {} days", days));
+        }
+        if let Some(language) = &self.language {
+            words.push(format!("in {}", language));
+        }
+        if let Some(topic) = &self.topic {
+            words.push(format!("tagged #{}", topic));
+        }
+        if let Some(owner) = &self.owner {
+            words.push(format!("owned by {}", owner));
+        }
+        match self.license.as_deref() {
+            Some(NO_LICENSE) => words.push("without a license".to_string()),
+            Some(license) => words.push(format!("licensed under {}", license)),
+            None => {}
+        }
+        if !self.query.is_empty() {
+            let verb = if self.fuzzy { "fuzzy-matching" } else { "matching" };
+            words.push(format!("{} \"{}\"", verb, self.query));
+        }
+        words.join(" ")
     }
+}
 
-    pub fn all() -> &'static [SortField] {
-        &[
-            SortField::Starred,
-            SortField::Pushed,
-        ]
+/// A preset's filters, with no search query
+impl From<&FilterPreset> for FilterSpec {
+    fn from(preset: &FilterPreset) -> Self {
+        FilterSpec {
+            language: preset.language.clone(),
+            topic: preset.topic.clone(),
+            stale_threshold_days: preset.stale_threshold_days,
+            archived_only: preset.archived_only,
+            no_description_only: preset.no_description_only,
+            fork_filter: preset.fork_filter,
+            ..Default::default()
+        }
     }
 }
 
-/// Sort direction
+/// What a row's copy button puts on the clipboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CopyTarget {
+    Url,
+    FullName,
+}
+
+/// Which repositories the select-all checkbox covers
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
-pub enum SortDirection {
+pub enum SelectScope {
+    /// Only repositories matching the current filters and search
     #[default]
-    Asc,
-    Desc,
+    Visible,
+    /// Every fetched repository, filtered out or not
+    AllLoaded,
 }
 
-impl SortDirection {
-    pub fn toggle(&self) -> Self {
+/// How long a copy button reads "Copied!"
+pub const COPIED_FEEDBACK: Duration = Duration::from_millis(1500);
+
+/// Stale filter presets shown in the toolbar: (label, days since last push)
+pub const STALE_PRESETS: [(&str, u32); 3] = [("6mo", 182), ("1yr", 365), ("2yr", 730)];
+
+/// How long the undo button stays available after an unstar
+pub const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+/// Opening more selected repos than this in the browser asks first
+pub const OPEN_SELECTED_CONFIRM_THRESHOLD: usize = 10;
+
+/// License filter option for repositories without a license
+pub const NO_LICENSE: &str = "No license";
+
+/// Word accepted in place of the count when typing to confirm a large unstar
+pub const TYPE_TO_CONFIRM_WORD: &str = "UNSTAR";
+
+/// Most repositories named in a batch unstar confirmation
+pub const CONFIRM_LIST_LIMIT: usize = 100;
+
+impl PendingAction {
+    /// Whether this action is a bulk unstar large enough to require typing to confirm
+    pub fn requires_typed_confirmation(&self, threshold: usize) -> bool {
+        self.bulk_count().is_some_and(|count| count >= threshold)
+    }
+
+    /// Number of repos a bulk unstar affects, None for other actions
+    pub fn bulk_count(&self) -> Option<usize> {
         match self {
-            SortDirection::Asc => SortDirection::Desc,
-            SortDirection::Desc => SortDirection::Asc,
+            PendingAction::UnstarSelected(count) => Some(*count),
+            PendingAction::UnstarByFilter(_, ids) => Some(ids.len()),
+            _ => None,
         }
     }
 
-    pub fn label(&self) -> &'static str {
-        match self {
-            SortDirection::Asc => "↑",
-            SortDirection::Desc => "↓",
+    /// Whether the typed text confirms this action (the count or "UNSTAR")
+    pub fn is_confirmation_text(&self, text: &str) -> bool {
+        let text = text.trim();
+        match self.bulk_count() {
+            Some(count) => text == count.to_string() || text == TYPE_TO_CONFIRM_WORD,
+            None => true,
         }
     }
+}
 
-    /// API parameter value
-    pub fn api_value(&self) -> &'static str {
-        match self {
-            SortDirection::Asc => "asc",
-            SortDirection::Desc => "desc",
+/// README preview for a repository, cached for the session
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadmePreview {
+    Loading,
+    Loaded(String),
+    /// The repository has no README
+    Missing,
+    Failed(String),
+}
+
+/// Number of recent operations averaged for the time-remaining estimate
+const ETA_WINDOW: usize = 10;
+
+/// Progress of a bulk unstar, with a rolling estimate of time remaining
+#[derive(Debug, Clone, Default)]
+pub struct UnstarProgress {
+    pub total: usize,
+    pub completed: usize,
+    elapsed: Duration,
+    recent: VecDeque<Duration>,
+    /// Repos per chunk when the batch is sent in chunks, 0 otherwise
+    chunk_size: usize,
+}
+
+impl UnstarProgress {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            ..Default::default()
+        }
+    }
+
+    /// Progress for a batch sent `chunk_size` repos at a time
+    pub fn chunked(total: usize, chunk_size: usize) -> Self {
+        Self {
+            chunk_size,
+            ..Self::new(total)
+        }
+    }
+
+    /// The chunk in progress and how many there are, e.g. (2, 3), for
+    /// batches spanning more than one chunk
+    pub fn chunk(&self) -> Option<(usize, usize)> {
+        if self.chunk_size == 0 || self.total <= self.chunk_size {
+            return None;
+        }
+        let chunks = self.total.div_ceil(self.chunk_size);
+        Some(((self.completed / self.chunk_size + 1).min(chunks), chunks))
+    }
+
+    /// Record one finished operation and how long it took (including any backoff)
+    pub fn record(&mut self, took: Duration) {
+        self.completed += 1;
+        self.elapsed += took;
+        self.recent.push_back(took);
+        if self.recent.len() > ETA_WINDOW {
+            self.recent.pop_front();
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.total.saturating_sub(self.completed)
+    }
+
+    /// Share of the batch finished, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        (self.completed.min(self.total) as f32) / self.total as f32
+    }
+
+    /// Progress with the chunk and estimate when known, e.g.
+    /// "Unstarred 142 / 300 · batch 6 of 12 · ~16s left"
+    pub fn label(&self) -> String {
+        let mut done = format!(
+            "Unstarred {} / {}",
+            format_count(self.completed),
+            format_count(self.total)
+        );
+        if let Some((chunk, chunks)) = self.chunk() {
+            done = format!("{} · batch {} of {}", done, chunk, chunks);
+        }
+        match self.eta_label() {
+            Some(eta) => format!("{} · {}", done, eta),
+            None => done,
+        }
+    }
+
+    /// Estimated time remaining, None until the first operation completes
+    pub fn eta(&self) -> Option<Duration> {
+        if self.completed == 0 {
+            return None;
+        }
+
+        // Use the slower of the recent and overall averages so the estimate
+        // stays conservative when requests start getting throttled
+        let overall = self.elapsed / self.completed as u32;
+        let recent = self.recent.iter().sum::<Duration>() / self.recent.len() as u32;
+        Some(overall.max(recent) * self.remaining() as u32)
+    }
+
+    /// Human-readable estimate, e.g. "~1m 30s left"
+    pub fn eta_label(&self) -> Option<String> {
+        let secs = self.eta()?.as_secs();
+        Some(if secs < 60 {
+            format!("~{}s left", secs.max(1))
+        } else {
+            format!("~{}m {}s left", secs / 60, secs % 60)
+        })
+    }
+}
+
+/// Format a count with thousands separators, e.g. 1423 -> "1,423"
+fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(ch);
+    }
+    formatted
+}
+
+/// Number of languages listed in the stats panel
+pub const STATS_TOP_LANGUAGES: usize = 5;
+
+/// API requests activity badges leave unused, so unstarring and loading
+/// pages still work after scrolling through a large list
+pub const ACTIVITY_RATE_LIMIT_RESERVE: u32 = 500;
+
+/// Requests it takes to fetch one repository's activity
+const ACTIVITY_REQUESTS_PER_REPO: u32 = 2;
+
+/// At-a-glance breakdown of the loaded star list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepoStats {
+    pub total: usize,
+    /// Stargazers summed across every repository
+    pub total_stars: usize,
+    /// Most common languages and their counts, most common first
+    pub top_languages: Vec<(String, usize)>,
+    /// Median days since the last push, None if no repository has been pushed to
+    pub median_pushed_age_days: Option<i64>,
+    pub archived: usize,
+    pub forks: usize,
+}
+
+impl RepoStats {
+    pub fn compute(repos: &[Repository], now: DateTime<Utc>, top_languages: usize) -> Self {
+        let mut by_language: HashMap<&str, usize> = HashMap::new();
+        for language in repos.iter().filter_map(|r| r.language.as_deref()) {
+            *by_language.entry(language).or_default() += 1;
+        }
+        let mut languages: Vec<(String, usize)> = by_language
+            .into_iter()
+            .map(|(language, count)| (language.to_string(), count))
+            .collect();
+        // Break ties by name so the order doesn't change between renders
+        languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        languages.truncate(top_languages);
+
+        let mut ages: Vec<i64> = repos
+            .iter()
+            .filter_map(|r| r.pushed_at)
+            .map(|pushed_at| (now - pushed_at).num_days())
+            .collect();
+        ages.sort_unstable();
+        let median_pushed_age_days = match ages.len() {
+            0 => None,
+            len if len.is_multiple_of(2) => Some((ages[len / 2 - 1] + ages[len / 2]) / 2),
+            len => Some(ages[len / 2]),
+        };
+
+        Self {
+            total: repos.len(),
+            total_stars: repos.iter().map(|r| r.stargazers_count as usize).sum(),
+            top_languages: languages,
+            median_pushed_age_days,
+            archived: repos.iter().filter(|r| r.archived).count(),
+            forks: repos.iter().filter(|r| r.fork).count(),
+        }
+    }
+
+    /// Total stars with thousands separators
+    pub fn total_stars_label(&self) -> String {
+        format_count(self.total_stars)
+    }
+}
+
+/// What the diagnostics view has found out, so users can check their setup
+/// before filing an issue. Checks still running are None; failures hold
+/// the error message.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    pub username: Option<Result<String, String>>,
+    pub scopes: Option<Result<Vec<String>, String>>,
+    pub rate_limit: Option<Result<RateLimit, String>>,
+    pub api_url: String,
+    /// Proxy requests go through, without credentials
+    pub proxy: Option<String>,
+    pub config_path: PathBuf,
+    pub cache_dir: PathBuf,
+}
+
+impl Diagnostics {
+    /// (label, value) rows in display order, with failed checks as Err
+    pub fn rows(&self) -> Vec<(&'static str, Result<String, String>)> {
+        fn checked<T>(result: &Option<Result<T, String>>, show: impl Fn(&T) -> String) -> Result<String, String> {
+            match result {
+                None => Ok("Checking...".to_string()),
+                Some(Ok(value)) => Ok(show(value)),
+                Some(Err(e)) => Err(e.clone()),
+            }
         }
+
+        vec![
+            ("Signed in as", checked(&self.username, |username| format!("@{}", username))),
+            (
+                "Token scopes",
+                checked(&self.scopes, |scopes| match scopes.as_slice() {
+                    // Fine-grained tokens don't report scopes
+                    [] => "None reported (fine-grained token)".to_string(),
+                    scopes if has_starring_scope(scopes) => scopes.join(", "),
+                    scopes => format!("{} (missing repo or public_repo, needed to unstar)", scopes.join(", ")),
+                }),
+            ),
+            (
+                "Rate limit",
+                checked(&self.rate_limit, |rate_limit| {
+                    format!(
+                        "{} / {} remaining · {}",
+                        rate_limit.remaining,
+                        rate_limit.limit,
+                        rate_limit.reset_label()
+                    )
+                }),
+            ),
+            ("API base URL", Ok(self.api_url.clone())),
+            ("Proxy", Ok(self.proxy.clone().unwrap_or_else(|| "None".to_string()))),
+            ("Config file", Ok(self.config_path.display().to_string())),
+            ("Cache directory", Ok(self.cache_dir.display().to_string())),
+        ]
+    }
+
+    /// Plain-text report for pasting into an issue, one row per line
+    pub fn report(&self) -> String {
+        self.rows()
+            .into_iter()
+            .map(|(label, value)| match value {
+                Ok(value) => format!("{}: {}", label, value),
+                Err(e) => format!("{}: failed: {}", label, e),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -99,16 +490,105 @@ pub struct AppState {
     pub loading: bool,
     pub loading_more: bool,
     pub error: Option<String>,
+    /// Non-fatal problem shown under the error banner, e.g. settings that
+    /// couldn't be saved but still apply for this session
+    pub warning: Option<String>,
     pub username: Option<String>,
     pub current_page: u32,
     pub has_more: bool,
+    /// GraphQL cursor after the last loaded page, None when pages come from REST
+    pub starred_cursor: Option<String>,
     pub pending_action: Option<PendingAction>,
     pub sort_field: SortField,
     pub sort_direction: SortDirection,
+    pub unstar_progress: Option<UnstarProgress>,
+    pub readme_cache: HashMap<u64, ReadmePreview>,
+    /// Repository whose README preview is expanded
+    pub expanded_readme: Option<u64>,
+    /// Last commit and release per repository, fetched as rows scroll into
+    /// view. None while fetching or if the fetch failed; either way it isn't
+    /// asked for again this session, to spare the rate limit.
+    pub repo_activity: HashMap<u64, Option<RepoActivity>>,
+    /// Client-side filter over loaded repositories
+    pub search_query: String,
+    /// Only show repositories tagged with this topic
+    pub topic_filter: Option<String>,
+    /// Only show repositories not pushed to in this many days
+    pub stale_threshold_days: Option<u32>,
+    /// Only show archived repositories
+    pub archived_only: bool,
+    /// Only show repositories without a description
+    pub no_description_only: bool,
+    pub fork_filter: ForkFilter,
+    /// Only show repositories owned by the signed-in user
+    pub own_only: bool,
+    /// Only show likely duplicate stars, grouped together
+    pub duplicates_only: bool,
+    /// Only show repositories under this license, or without one for `NO_LICENSE`
+    pub license_filter: Option<String>,
+    /// Total number of starred repositories reported by GitHub
+    pub total_starred: Option<u32>,
+    /// When the list was last fetched from GitHub
+    pub last_refreshed: Option<DateTime<Utc>>,
+    /// The list changed since it was last written to the on-disk cache
+    pub cache_dirty: bool,
+    /// The list was restored from the on-disk cache and hasn't been refreshed yet
+    pub showing_cached: bool,
+    /// A list was just restored from the cache and its background refresh
+    /// hasn't been started yet
+    pub refresh_pending: bool,
+    /// GitHub couldn't be reached at startup, so the cached list is shown
+    /// read-only until a retry gets through
+    pub offline: bool,
+    /// Set when a background page load fails, so `load_all` stops retrying
+    pub load_all_paused: bool,
+    /// Set by "Stop loading" to end `load_all` after the page in flight.
+    /// Shared with the auto-load task, which checks it between pages.
+    pub load_all_cancel: Arc<AtomicBool>,
+    /// Comfortable or compact rows, saved as `config.row_density`
+    pub row_density: RowDensity,
+    /// Cards or table, saved as `config.view_mode`
+    pub view_mode: ViewMode,
+    /// Row and button most recently copied from, while its feedback shows
+    pub copied: Option<(u64, CopyTarget)>,
+    /// The user's star lists, empty if lists aren't available
+    pub lists: Vec<StarList>,
+    /// Repository whose "Move to list" menu is open
+    pub list_menu_open: Option<u64>,
+    /// Repositories a dry-run unstar would have removed
+    pub would_unstar: HashSet<u64>,
+    /// Repositories hidden while their unstar request is in flight, and
+    /// whether each was selected, restored if it fails
+    pub pending_removal: HashMap<u64, (Repository, bool)>,
+    /// Bumped on logout and on every reload from page 1. Spawned tasks
+    /// capture it and drop their results if it has moved on.
+    pub epoch: u64,
+    /// Complete star list from the last session's cache, compared with the
+    /// fresh list once that's complete too
+    previous_session: Option<Vec<Repository>>,
+    /// Repositories starred or unstarred from the app this session, which
+    /// aren't news in the session diff
+    changed_in_app: HashSet<u64>,
+    /// Stars added and removed elsewhere since the last session, until dismissed
+    pub session_diff: Option<RepoDiff>,
+    /// Most recently fetched API rate limit
+    pub rate_limit: Option<RateLimit>,
+    /// Repositories removed by the most recent unstar, for undo
+    pub last_unstarred: Vec<Repository>,
+    /// When `last_unstarred` was last added to
+    pub last_unstarred_at: Option<Instant>,
+    /// Repositories unstarred since signing in, less any undone
+    pub session_unstar_count: usize,
+    /// Rapid cleanup mode: `u` unstars the highlighted row without asking,
+    /// and everything unstarred stays undoable until the mode is left
+    pub focus_mode: bool,
+    /// Shown over the current screen while open
+    pub diagnostics: Option<Diagnostics>,
 }
 
 impl AppState {
-    /// Initialize state from saved config
+    /// Initialize state from saved config (whose token `ConfigService::load`
+    /// has already resolved from the keychain)
     pub fn from_config(config: AppConfig) -> Self {
         let screen = if config.has_token() {
             AppScreen::Loading
@@ -118,6 +598,10 @@ impl AppState {
 
         Self {
             screen,
+            sort_field: config.sort_field,
+            sort_direction: config.sort_direction,
+            row_density: config.row_density,
+            view_mode: config.view_mode,
             config,
             current_page: 1,
             has_more: true,
@@ -125,255 +609,2638 @@ impl AppState {
         }
     }
 
-    /// Set PAT and create GitHub service
-    pub fn set_token(&mut self, token: String) -> anyhow::Result<()> {
-        self.config.github.personal_access_token = Some(token.clone());
-        self.github_service = Some(GitHubService::new(&token)?);
-        ConfigService::save(&self.config)?;
-        Ok(())
+    /// Show a cached star list right away instead of the loading screen,
+    /// unless it's older than the configured TTL
+    pub fn restore_cache(&mut self, cache: StarCache) {
+        if self.screen == AppScreen::Loading && cache.complete {
+            self.previous_session = Some(cache.repositories.clone());
+        }
+
+        let usable = self.screen == AppScreen::Loading
+            && !cache.repositories.is_empty()
+            && !cache.is_expired(self.config.cache.ttl(), Utc::now());
+        if !usable {
+            return;
+        }
+
+        self.repositories = cache.repositories;
+        self.last_refreshed = Some(cache.fetched_at);
+        self.sort_loaded_repositories();
+        self.screen = AppScreen::RepositoryList;
+        self.showing_cached = true;
+        self.refresh_pending = true;
     }
 
-    /// Get selected repositories for unstar (owner, repo) pairs
-    pub fn get_selected_repos(&self) -> Vec<(String, String)> {
-        self.repositories
-            .iter()
-            .filter(|r| self.selection.is_selected(r.id))
-            .map(|r| (r.owner.clone(), r.name.clone()))
-            .collect()
+    /// Fall back to browsing the cached star list read-only after the startup
+    /// load couldn't reach GitHub. A list already restored from the cache is
+    /// kept, otherwise `cache` is used even if it has expired. Returns false
+    /// if there's nothing cached to show.
+    pub fn enter_offline(&mut self, cache: Option<StarCache>) -> bool {
+        if !self.showing_cached {
+            let Some(cache) = cache.filter(|cache| !cache.repositories.is_empty()) else {
+                return false;
+            };
+            self.repositories = cache.repositories;
+            self.last_refreshed = Some(cache.fetched_at);
+            self.sort_loaded_repositories();
+        }
+        self.showing_cached = false;
+        self.offline = true;
+        self.loading = false;
+        self.screen = AppScreen::RepositoryList;
+        true
     }
 
-    /// Get selected repository IDs
-    pub fn get_selected_ids(&self) -> Vec<u64> {
-        self.repositories
+    /// Replace the list with freshly fetched pages, keeping the selection
+    /// for repositories that are still starred
+    pub fn reconcile_refreshed(&mut self, mut repos: Vec<Repository>, pages_loaded: u32, has_more: bool) {
+        // Repos being unstarred stay hidden until their request finishes
+        repos.retain(|r| !self.pending_removal.contains_key(&r.id));
+        let ids: HashSet<u64> = repos.iter().map(|r| r.id).collect();
+        self.selection.selected_ids.retain(|id| ids.contains(id));
+        self.repositories = repos;
+        self.current_page = pages_loaded;
+        self.has_more = has_more;
+        self.showing_cached = false;
+        self.offline = false;
+        self.last_refreshed = Some(Utc::now());
+        self.sort_loaded_repositories();
+        self.check_session_diff();
+    }
+
+    /// Once every star is loaded, compare with the last session's list and
+    /// keep any changes made outside the app for the summary banner
+    pub fn check_session_diff(&mut self) {
+        if self.has_more {
+            return;
+        }
+        let Some(previous) = self.previous_session.take() else {
+            return;
+        };
+
+        let mut diff = diff_repos(&previous, &self.repositories);
+        // Unstars still in flight and ones made here aren't news
+        diff.removed
+            .retain(|id| !self.changed_in_app.contains(id) && !self.pending_removal.contains_key(id));
+        diff.added.retain(|id| !self.changed_in_app.contains(id));
+        if !diff.is_empty() {
+            self.session_diff = Some(diff);
+        }
+    }
+
+    /// Append a newly fetched page, skipping repos already loaded. Stars added
+    /// between page fetches shift later pages and would otherwise repeat rows.
+    pub fn merge_new_repos(&mut self, incoming: Vec<Repository>) {
+        let mut seen: HashSet<u64> = self
+            .repositories
             .iter()
-            .filter(|r| self.selection.is_selected(r.id))
             .map(|r| r.id)
-            .collect()
+            .chain(self.pending_removal.keys().copied())
+            .collect();
+        self.repositories
+            .extend(incoming.into_iter().filter(|r| seen.insert(r.id)));
     }
 
-    /// Remove repositories by IDs (after unstar)
-    pub fn remove_repos(&mut self, ids: &[u64]) {
-        self.repositories.retain(|r| !ids.contains(&r.id));
-        self.selection.remove_ids(ids);
+    /// Clear the list for a fresh first-page fetch, superseding any load in
+    /// flight. Returns the epoch its response has to match.
+    pub fn begin_reload(&mut self) -> u64 {
+        self.epoch += 1;
+        // Unstars still in flight keep their rows out of the new list and
+        // are recorded when they finish
+        self.loading = true;
+        self.loading_more = false;
+        self.load_all_paused = false;
+        self.load_all_cancel.store(false, Ordering::Relaxed);
+        self.repositories.clear();
+        self.selection.clear();
+        self.current_page = 1;
+        self.has_more = true;
+        self.starred_cursor = None;
+        self.epoch
     }
 
-    /// Clear error message
-    pub fn clear_error(&mut self) {
-        self.error = None;
+    /// Whether a task started at `epoch` hasn't been superseded by a
+    /// reload or logout
+    pub fn is_current_epoch(&self, epoch: u64) -> bool {
+        self.epoch == epoch
     }
 
-    /// Set error message
-    pub fn set_error(&mut self, error: String) {
-        self.error = Some(error);
+    /// Have the loaded list written to the on-disk cache. The list view
+    /// picks this up with `take_cache_write`, so a burst of pages or unstars
+    /// costs one write.
+    pub fn queue_cache_save(&mut self) {
+        self.cache_dirty = true;
     }
 
-    /// Logout and clear token
-    pub fn logout(&mut self) -> anyhow::Result<()> {
-        self.github_service = None;
-        self.username = None;
-        self.repositories.clear();
-        self.selection.clear();
-        self.screen = AppScreen::Setup;
-        ConfigService::clear_token()?;
-        self.config.github.personal_access_token = None;
+    /// A write of the list as it is now, to run off the UI thread, if it
+    /// changed since the last one
+    pub fn take_cache_write(&mut self) -> Option<impl FnOnce() + Send + use<>> {
+        if !std::mem::take(&mut self.cache_dirty) {
+            return None;
+        }
+        let profile = self.config.active_profile_name().to_string();
+        let repos = self.repositories.clone();
+        let complete = !self.has_more;
+        let config = self.config.cache.clone();
+        Some(move || {
+            if let Err(e) = CacheService::save_repos(&profile, &repos, complete, &config) {
+                tracing::warn!("Failed to save star cache: {}", e);
+            }
+        })
+    }
+
+    /// Set PAT and create GitHub service
+    pub fn set_token(&mut self, token: String) -> anyhow::Result<()> {
+        self.github_service = Some(GitHubService::new(&token, self.config.github.proxy.as_deref())?);
+        let (saved, warning) = ConfigService::save_token(&token);
+        self.config.github = saved.github;
+        self.warning = warning;
         Ok(())
     }
 
-    /// Handle API errors, with special handling for token expiration
-    pub fn handle_api_error(&mut self, err: anyhow::Error, context: &str) {
-        if is_token_expired_error(&err) {
-            let _ = self.logout();
-            self.error = Some("Token expired. Please login again.".to_string());
-        } else {
-            self.error = Some(format!("{}: {}", context, err));
-        }
+    /// Loaded repositories passing every filter in `spec`, in list order
+    pub fn apply_filters(&self, spec: &FilterSpec) -> Vec<&Repository> {
+        let now = Utc::now();
+        let duplicates = spec.duplicates_only.then(|| self.duplicate_positions());
+        self.repositories
+            .iter()
+            .filter(|r| spec.matches(r, now))
+            .filter(|r| duplicates.as_ref().is_none_or(|positions| positions.contains_key(&r.id)))
+            .collect()
     }
-}
 
-impl Global for AppState {}
+    /// What "Unstar All Filtered" unstars for `spec`: its matches minus
+    /// pinned repositories, which are kept like with select all
+    pub fn unstar_filter_targets(&self, spec: &FilterSpec) -> Vec<&Repository> {
+        let mut repos = self.apply_filters(spec);
+        repos.retain(|r| !self.is_pinned(r.id));
+        repos
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::GitHubConfig;
-    use chrono::Utc;
+    /// Position of each likely duplicate when the groups are listed one
+    /// after another
+    fn duplicate_positions(&self) -> HashMap<u64, usize> {
+        find_duplicate_groups(&self.repositories)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(position, r)| (r.id, position))
+            .collect()
+    }
 
-    fn create_test_repo(id: u64, name: &str, owner: &str) -> Repository {
-        Repository {
-            id,
-            name: name.to_string(),
-            full_name: format!("{}/{}", owner, name),
-            owner: owner.to_string(),
-            description: None,
-            language: None,
-            stargazers_count: 0,
-            forks_count: 0,
-            open_issues_count: 0,
-            license: None,
-            topics: vec![],
-            updated_at: Utc::now(),
-            pushed_at: None,
-            html_url: format!("https://github.com/{}/{}", owner, name),
-            starred_order: 0,
+    /// Loaded repositories matching the active filters and search, which is
+    /// what the list shows and what select-all covers. Pinned repositories
+    /// come first, then the best fuzzy matches if fuzzy search is on,
+    /// otherwise list order. While finding duplicates, only likely
+    /// duplicates are shown, one group after another.
+    pub fn visible_repositories(&self) -> Vec<&Repository> {
+        let spec = self.filter_spec();
+        let mut repos = self.apply_filters(&spec);
+        if spec.duplicates_only {
+            let positions = self.duplicate_positions();
+            repos.sort_by_key(|r| positions[&r.id]);
+            return repos;
         }
+        if spec.fuzzy && !spec.query.is_empty() {
+            repos.sort_by_key(|r| std::cmp::Reverse(r.fuzzy_query_score(&spec.query)));
+        }
+        repos.sort_by_key(|r| !self.is_pinned(r.id));
+        repos
     }
 
-    #[test]
-    fn test_app_screen_default() {
-        let screen = AppScreen::default();
-        assert_eq!(screen, AppScreen::Setup);
+    /// Visible repositories that bulk selection applies to, i.e. not pinned
+    fn selectable_repositories(&self) -> Vec<&Repository> {
+        let mut repos = self.visible_repositories();
+        repos.retain(|r| !self.is_pinned(r.id));
+        repos
     }
 
-    #[test]
-    fn test_sort_field_label() {
-        assert_eq!(SortField::Starred.label(), "Starred");
-        assert_eq!(SortField::Pushed.label(), "Pushed");
+    /// Ask to confirm "Unstar All Filtered", fixing its targets to what the
+    /// current filters match so pages loaded meanwhile aren't swept in
+    pub fn confirm_unstar_filtered(&mut self) {
+        let spec = self.filter_spec();
+        let ids = self.unstar_filter_targets(&spec).iter().map(|r| r.id).collect();
+        self.pending_action = Some(PendingAction::UnstarByFilter(spec, ids));
     }
 
-    #[test]
-    fn test_sort_field_api_value() {
-        assert_eq!(SortField::Starred.api_value(), "created");
-        assert_eq!(SortField::Pushed.api_value(), "updated");
+    pub fn is_pinned(&self, id: u64) -> bool {
+        self.config.pinned_ids.contains(&id)
     }
 
-    #[test]
-    fn test_sort_field_all() {
-        let all = SortField::all();
-        assert_eq!(all.len(), 2);
-        assert!(all.contains(&SortField::Starred));
-        assert!(all.contains(&SortField::Pushed));
+    /// Display index of the first visible unpinned repository at or after
+    /// `from`, where focus mode can unstar without asking
+    pub fn next_unpinned_index(&self, from: usize) -> Option<usize> {
+        self.visible_repositories()
+            .iter()
+            .enumerate()
+            .skip(from)
+            .find(|(_, r)| !self.is_pinned(r.id))
+            .map(|(index, _)| index)
     }
 
-    #[test]
-    fn test_sort_direction_toggle() {
-        assert_eq!(SortDirection::Asc.toggle(), SortDirection::Desc);
-        assert_eq!(SortDirection::Desc.toggle(), SortDirection::Asc);
+    /// Pin a repository as a keeper, or unpin it. Pinning also deselects it.
+    pub fn toggle_pin(&mut self, id: u64) {
+        if self.config.toggle_pin(id) {
+            self.selection.selected_ids.remove(&id);
+        }
+        self.save_config("pinned repositories");
     }
 
-    #[test]
-    fn test_sort_direction_label() {
-        assert_eq!(SortDirection::Asc.label(), "↑");
-        assert_eq!(SortDirection::Desc.label(), "↓");
+    /// The currently active filters
+    pub fn filter_spec(&self) -> FilterSpec {
+        let (query, language) = split_language_filter(&self.search_query);
+        FilterSpec {
+            query: query.to_lowercase(),
+            language,
+            topic: self.topic_filter.clone(),
+            stale_threshold_days: self.stale_threshold_days,
+            archived_only: self.archived_only,
+            no_description_only: self.no_description_only,
+            fork_filter: self.fork_filter,
+            owner: self.own_only.then(|| self.username.clone()).flatten(),
+            license: self.license_filter.clone(),
+            fuzzy: self.config.fuzzy_search,
+            duplicates_only: self.duplicates_only,
+        }
     }
 
-    #[test]
-    fn test_sort_direction_api_value() {
-        assert_eq!(SortDirection::Asc.api_value(), "asc");
-        assert_eq!(SortDirection::Desc.api_value(), "desc");
+    /// Distinct licenses of the loaded repositories for the license filter,
+    /// sorted, with `NO_LICENSE` last if any repository has none
+    pub fn license_options(&self) -> Vec<String> {
+        let licenses: BTreeSet<&str> = self.repositories.iter().filter_map(|r| r.license.as_deref()).collect();
+        let mut options: Vec<String> = licenses.into_iter().map(str::to_string).collect();
+        if self.repositories.iter().any(|r| r.license.is_none()) {
+            options.push(NO_LICENSE.to_string());
+        }
+        options
     }
 
-    #[test]
-    fn test_app_state_default() {
-        let state = AppState::default();
-        assert_eq!(state.screen, AppScreen::Setup);
-        assert!(state.repositories.is_empty());
-        assert_eq!(state.selection.count(), 0);
-        assert!(!state.loading);
-        assert!(state.error.is_none());
-        assert!(state.username.is_none());
-        assert!(state.pending_action.is_none());
-        assert_eq!(state.sort_field, SortField::Pushed);
-        assert_eq!(state.sort_direction, SortDirection::Asc);
+    /// Whether the signed-in user owns `repo`, i.e. starred their own repository
+    pub fn is_own_repo(&self, repo: &Repository) -> bool {
+        self.username
+            .as_deref()
+            .is_some_and(|username| repo.owner.eq_ignore_ascii_case(username))
     }
 
-    #[test]
-    fn test_from_config_with_token() {
-        let config = AppConfig {
-            github: GitHubConfig {
-                personal_access_token: Some("valid_token".to_string()),
-            },
-        };
+    /// The current filters as a preset named `name`, leaving out the free-text search
+    pub fn preset_from_filters(&self, name: &str) -> FilterPreset {
+        let spec = self.filter_spec();
+        FilterPreset {
+            name: name.trim().to_string(),
+            language: spec.language,
+            topic: spec.topic,
+            stale_threshold_days: spec.stale_threshold_days,
+            archived_only: spec.archived_only,
+            no_description_only: spec.no_description_only,
+            fork_filter: spec.fork_filter,
+        }
+    }
 
-        let state = AppState::from_config(config);
-        assert_eq!(state.screen, AppScreen::Loading);
-        assert_eq!(state.current_page, 1);
-        assert!(state.has_more);
+    /// Save the current filters as a preset, replacing any preset with the
+    /// same name, and write the config
+    pub fn save_preset(&mut self, name: &str) {
+        let preset = self.preset_from_filters(name);
+        match self
+            .config
+            .presets
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(&preset.name))
+        {
+            Some(existing) => *existing = preset,
+            None => self.config.presets.push(preset),
+        }
+        self.save_config("filter preset");
     }
 
-    #[test]
-    fn test_from_config_without_token() {
-        let config = AppConfig {
-            github: GitHubConfig {
-                personal_access_token: None,
-            },
+    /// Replace the filters with a preset's, keeping the free-text search
+    pub fn apply_preset(&mut self, preset: &FilterPreset) {
+        let (query, _) = split_language_filter(&self.search_query);
+        self.search_query = match &preset.language {
+            Some(language) if query.is_empty() => format!("language:{}", language),
+            Some(language) => format!("{} language:{}", query, language),
+            None => query,
         };
+        self.topic_filter = preset.topic.clone();
+        self.stale_threshold_days = preset.stale_threshold_days;
+        self.archived_only = preset.archived_only;
+        self.no_description_only = preset.no_description_only;
+        self.fork_filter = preset.fork_filter;
+    }
 
-        let state = AppState::from_config(config);
-        assert_eq!(state.screen, AppScreen::Setup);
+    /// Remove a saved preset and write the config
+    pub fn delete_preset(&mut self, name: &str) {
+        self.config.presets.retain(|p| p.name != name);
+        self.save_config("filter presets");
     }
 
-    #[test]
-    fn test_from_config_with_empty_token() {
-        let config = AppConfig {
-            github: GitHubConfig {
-                personal_access_token: Some("".to_string()),
-            },
-        };
+    /// Summary stats over every loaded repository, ignoring filters
+    pub fn compute_stats(&self) -> RepoStats {
+        RepoStats::compute(&self.repositories, Utc::now(), STATS_TOP_LANGUAGES)
+    }
 
-        let state = AppState::from_config(config);
-        assert_eq!(state.screen, AppScreen::Setup);
+    /// Whether any filter is narrowing the visible repositories
+    pub fn is_filtered(&self) -> bool {
+        self.filter_spec().is_active()
     }
 
-    #[test]
-    fn test_get_selected_repos() {
-        let mut state = AppState::default();
-        state.repositories = vec![
-            create_test_repo(1, "repo1", "owner1"),
-            create_test_repo(2, "repo2", "owner2"),
-            create_test_repo(3, "repo3", "owner3"),
-        ];
+    /// Whether every visible repository is selected
+    pub fn all_filtered_selected(&self) -> bool {
+        let filtered = self.selectable_repositories();
+        !filtered.is_empty() && filtered.iter().all(|r| self.selection.is_selected(r.id))
+    }
 
-        state.selection.toggle(1);
-        state.selection.toggle(3);
+    /// Select all visible unpinned repositories, or clear the selection if
+    /// they already are
+    pub fn toggle_select_all_filtered(&mut self) {
+        if self.all_filtered_selected() {
+            self.selection.clear();
+        } else {
+            self.selection.selected_ids = self.selectable_repositories().iter().map(|r| r.id).collect();
+        }
+    }
 
-        let selected = state.get_selected_repos();
-        assert_eq!(selected.len(), 2);
-        assert!(selected.contains(&("owner1".to_string(), "repo1".to_string())));
-        assert!(selected.contains(&("owner3".to_string(), "repo3".to_string())));
+    /// Whether every fetched unpinned repository is selected, including
+    /// filtered-out ones
+    pub fn all_loaded_selected(&self) -> bool {
+        let mut unpinned = self.repositories.iter().filter(|r| !self.is_pinned(r.id)).peekable();
+        unpinned.peek().is_some() && unpinned.all(|r| self.selection.is_selected(r.id))
     }
 
-    #[test]
-    fn test_get_selected_ids() {
-        let mut state = AppState::default();
-        state.repositories = vec![
-            create_test_repo(1, "repo1", "owner1"),
-            create_test_repo(2, "repo2", "owner2"),
-        ];
+    /// Select every fetched unpinned repository regardless of filters, or
+    /// clear the selection if they already are
+    pub fn toggle_select_all_loaded(&mut self) {
+        if self.all_loaded_selected() {
+            self.selection.clear();
+        } else {
+            let unpinned: Vec<Repository> =
+                self.repositories.iter().filter(|r| !self.is_pinned(r.id)).cloned().collect();
+            self.selection.select_all(&unpinned);
+        }
+    }
 
-        state.selection.toggle(1);
+    /// Whether everything `scope` covers is selected
+    pub fn all_selected_in(&self, scope: SelectScope) -> bool {
+        match scope {
+            SelectScope::Visible => self.all_filtered_selected(),
+            SelectScope::AllLoaded => self.all_loaded_selected(),
+        }
+    }
+
+    /// Select every visible unpinned repository between two display
+    /// indices, inclusive
+    pub fn select_filtered_range(&mut self, from: usize, to: usize) {
+        let (start, end) = (from.min(to), from.max(to));
+        let ids: Vec<u64> = self
+            .visible_repositories()
+            .iter()
+            .skip(start)
+            .take(end - start + 1)
+            .filter(|r| !self.is_pinned(r.id))
+            .map(|r| r.id)
+            .collect();
+        self.selection.selected_ids.extend(ids);
+    }
+
+    /// Flip the selection of every visible unpinned repository
+    pub fn invert_selection_filtered(&mut self) {
+        let visible: Vec<Repository> = self.selectable_repositories().into_iter().cloned().collect();
+        self.selection.invert(&visible);
+    }
+
+    /// Ids of loaded, unpinned repositories whose full name or name matches
+    /// `pattern`, so `^awesome-` finds repositories named awesome-*
+    fn regex_matches(&self, pattern: &str) -> anyhow::Result<Vec<u64>> {
+        let regex = Regex::new(pattern).map_err(|e| match e {
+            // Syntax errors span several lines with a caret; the last says what's wrong
+            regex::Error::Syntax(message) => anyhow::anyhow!(
+                "Invalid regex: {}",
+                message.lines().last().unwrap_or_default().trim_start_matches("error: ")
+            ),
+            e => anyhow::anyhow!("Invalid regex: {}", e),
+        })?;
+        Ok(self
+            .repositories
+            .iter()
+            .filter(|r| !self.is_pinned(r.id) && (regex.is_match(&r.full_name) || regex.is_match(&r.name)))
+            .map(|r| r.id)
+            .collect())
+    }
+
+    /// How many repositories `select_by_regex` would select
+    pub fn count_regex_matches(&self, pattern: &str) -> anyhow::Result<usize> {
+        self.regex_matches(pattern).map(|ids| ids.len())
+    }
+
+    /// Replace the selection with every loaded repository matching
+    /// `pattern`, regardless of filters. Returns how many were selected.
+    pub fn select_by_regex(&mut self, pattern: &str) -> anyhow::Result<usize> {
+        let ids = self.regex_matches(pattern)?;
+        self.selection.selected_ids = ids.iter().copied().collect();
+        Ok(ids.len())
+    }
+
+    /// (sort, direction) API parameters for fetching pages.
+    /// Client-side fields always page in newest-starred-first order so that
+    /// changing their direction never invalidates already-loaded pages.
+    pub fn api_sort_params(&self) -> (&'static str, &'static str) {
+        if self.sort_field.is_client_side() {
+            (self.sort_field.api_value(), SortDirection::Desc.api_value())
+        } else {
+            (self.sort_field.api_value(), self.sort_direction.api_value())
+        }
+    }
+
+    /// Reorder loaded repositories by the active client-side sort field.
+    /// API-backed fields are already in server order and are left untouched.
+    pub fn sort_loaded_repositories(&mut self) {
+        let field = self.sort_field;
+        if !field.is_client_side() {
+            return;
+        }
+
+        self.repositories.sort_by(|a, b| {
+            let ordering = match field {
+                SortField::Stars => a.stargazers_count.cmp(&b.stargazers_count),
+                SortField::Forks => a.forks_count.cmp(&b.forks_count),
+                SortField::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortField::Issues => a.open_issues_count.cmp(&b.open_issues_count),
+                SortField::Language => {
+                    let key = |r: &Repository| (r.language.is_none(), r.language.as_deref().map(str::to_lowercase));
+                    key(a).cmp(&key(b))
+                }
+                SortField::Starred | SortField::Pushed => std::cmp::Ordering::Equal,
+            };
+            // Fall back to starred order so ties stay stable across pages
+            let ordering = ordering.then(a.starred_order.cmp(&b.starred_order));
+            match self.sort_direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            }
+        });
+    }
+
+    /// Save the current sort field and direction to config
+    pub fn persist_sort(&mut self) {
+        self.config.sort_field = self.sort_field;
+        self.config.sort_direction = self.sort_direction;
+        self.save_config("sort preference");
+    }
+
+    /// Switch between comfortable and compact rows and save the choice
+    pub fn toggle_row_density(&mut self) {
+        self.row_density = self.row_density.toggle();
+        self.config.row_density = self.row_density;
+        self.save_config("row density");
+    }
+
+    /// Switch between cards and the table and save the choice
+    pub fn toggle_view_mode(&mut self) {
+        self.view_mode = self.view_mode.toggle();
+        self.config.view_mode = self.view_mode;
+        self.save_config("view mode");
+    }
+
+    /// Remember the main window's bounds for the next launch and save them
+    pub fn save_window_state(&mut self, window: WindowState) {
+        if self.config.window == Some(window) {
+            return;
+        }
+        self.config.window = Some(window);
+        self.save_config("window position");
+    }
+
+    /// Switch between the dark and light themes and save the choice
+    pub fn toggle_theme(&mut self) {
+        self.config.theme = self.config.theme.toggle();
+        self.save_config("theme preference");
+    }
+
+    /// Turn the single-unstar confirmation on or off and save the choice
+    pub fn set_confirm_single_unstar(&mut self, confirm: bool) {
+        self.config.unstar.confirm_single_unstar = confirm;
+        self.save_config("confirmation preference");
+    }
+
+    /// Let bulk unstars smaller than `threshold` skip the confirmation dialog
+    /// and save the choice
+    pub fn set_batch_confirm_threshold(&mut self, threshold: usize) {
+        self.config.unstar.batch_confirm_threshold = threshold.max(1);
+        self.save_config("confirmation preference");
+    }
+
+    /// Turn dry-run mode on or off and save the choice. Leaving dry-run
+    /// mode clears the "would unstar" marks.
+    pub fn set_fuzzy_search(&mut self, fuzzy: bool) {
+        self.config.fuzzy_search = fuzzy;
+        self.save_config("search mode");
+    }
+
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.config.unstar.dry_run = dry_run;
+        if !dry_run {
+            self.would_unstar.clear();
+        }
+        self.save_config("dry-run preference");
+    }
+
+    /// Log the unstars a dry run skipped and mark the repositories, leaving
+    /// them starred and in the list
+    pub fn record_dry_run(&mut self, ids: &[u64]) {
+        let ids: HashSet<u64> = ids.iter().copied().collect();
+        for repo in self.repositories.iter().filter(|r| ids.contains(&r.id)) {
+            tracing::info!("Dry run: would unstar {}", repo.full_name);
+            self.would_unstar.insert(repo.id);
+        }
+    }
+
+    /// Get selected repositories for unstar (owner, repo) pairs
+    pub fn get_selected_repos(&self) -> Vec<(String, String)> {
+        self.repositories
+            .iter()
+            .filter(|r| self.selection.is_selected(r.id))
+            .map(|r| (r.owner.clone(), r.name.clone()))
+            .collect()
+    }
+
+    /// Get the browser URLs of the selected repositories, in list order
+    pub fn get_selected_urls(&self) -> Vec<String> {
+        self.repositories
+            .iter()
+            .filter(|r| self.selection.is_selected(r.id))
+            .map(|r| r.html_url.clone())
+            .collect()
+    }
+
+    /// The selected repositories as a markdown list in list order, one
+    /// `- [owner/repo](url) — description` line each, leaving out the
+    /// description when there isn't one
+    pub fn selected_as_markdown(&self) -> String {
+        self.repositories
+            .iter()
+            .filter(|r| self.selection.is_selected(r.id))
+            .map(|r| {
+                let link = format!("- [{}]({})", r.full_name, r.html_url);
+                match r.description.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+                    Some(description) => format!("{} — {}", link, description),
+                    None => link,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Full names of up to `limit` selected repositories, in list order,
+    /// and how many more are selected
+    pub fn selected_names_preview(&self, limit: usize) -> (Vec<String>, usize) {
+        let mut selected = self
+            .repositories
+            .iter()
+            .filter(|r| self.selection.is_selected(r.id));
+        let names = selected.by_ref().take(limit).map(|r| r.full_name.clone()).collect();
+        (names, selected.count())
+    }
+
+    /// Get selected repository IDs
+    pub fn get_selected_ids(&self) -> Vec<u64> {
+        self.repositories
+            .iter()
+            .filter(|r| self.selection.is_selected(r.id))
+            .map(|r| r.id)
+            .collect()
+    }
+
+    /// Remove repositories by IDs (after unstar), remembering them for undo
+    pub fn remove_repos(&mut self, ids: &[u64]) {
+        self.begin_removal(ids);
+        for &id in ids {
+            self.confirm_removal(id);
+        }
+    }
+
+    /// Hide repositories as soon as their unstar starts, holding them in
+    /// `pending_removal` until the request finishes
+    pub fn begin_removal(&mut self, ids: &[u64]) {
+        let id_set: HashSet<u64> = ids.iter().copied().collect();
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.repositories)
+            .into_iter()
+            .partition(|r| id_set.contains(&r.id));
+        self.repositories = kept;
+        if let Some(total) = self.total_starred.as_mut() {
+            *total = total.saturating_sub(removed.len() as u32);
+        }
+        for repo in removed {
+            let was_selected = self.selection.is_selected(repo.id);
+            self.pending_removal.insert(repo.id, (repo, was_selected));
+        }
+        self.selection.remove_ids(ids);
+    }
+
+    /// The unstar went through, so the repository becomes undoable. Returns
+    /// false if it was no longer pending, e.g. after a logout.
+    pub fn confirm_removal(&mut self, id: u64) -> bool {
+        let Some((repo, _)) = self.pending_removal.remove(&id) else {
+            return false;
+        };
+        self.changed_in_app.insert(id);
+        self.session_unstar_count += 1;
+        // Removals within the undo window belong to the same operation,
+        // so a bulk unstar is undone as a whole
+        if !self.can_undo() {
+            self.last_unstarred.clear();
+        }
+        self.last_unstarred.push(repo);
+        self.last_unstarred_at = Some(Instant::now());
+        true
+    }
+
+    /// The unstar failed, so put the repository back where it was, selected
+    /// again if it was before. Returns false if it was no longer pending.
+    pub fn rollback_removal(&mut self, id: u64) -> bool {
+        let Some((repo, was_selected)) = self.pending_removal.remove(&id) else {
+            return false;
+        };
+        if was_selected {
+            self.selection.selected_ids.insert(id);
+        }
+        self.insert_by_starred_order(repo);
+        if let Some(total) = self.total_starred.as_mut() {
+            *total += 1;
+        }
+        self.sort_loaded_repositories();
+        true
+    }
+
+    fn insert_by_starred_order(&mut self, repo: Repository) {
+        let index = self
+            .repositories
+            .iter()
+            .position(|r| r.starred_order > repo.starred_order)
+            .unwrap_or(self.repositories.len());
+        self.repositories.insert(index, repo);
+    }
+
+    /// Prepend a newly starred repository, ignoring ones already listed
+    pub fn add_starred_repo(&mut self, mut repo: Repository) {
+        if self.repositories.iter().any(|r| r.id == repo.id) {
+            return;
+        }
+        repo.starred_order = self.next_star_order();
+        self.changed_in_app.insert(repo.id);
+        self.repositories.insert(0, repo);
+        if let Some(total) = self.total_starred.as_mut() {
+            *total += 1;
+        }
+        self.sort_loaded_repositories();
+    }
+
+    /// Starred order for a star made now, which is GitHub's newest: after
+    /// every loaded repo when the list is oldest first, otherwise before them
+    /// (`min - 1`, moving the others down one when the first is already 0)
+    fn next_star_order(&mut self) -> u32 {
+        let orders = self.repositories.iter().map(|r| r.starred_order);
+        if self.sort_field == SortField::Starred && self.sort_direction == SortDirection::Asc {
+            return orders.max().map_or(0, |max| max + 1);
+        }
+        match orders.min() {
+            Some(0) => {
+                let held = self.pending_removal.values_mut().map(|(repo, _)| repo);
+                for repo in self.repositories.iter_mut().chain(held).chain(self.last_unstarred.iter_mut()) {
+                    repo.starred_order += 1;
+                }
+                0
+            }
+            Some(min) => min - 1,
+            None => 0,
+        }
+    }
+
+    /// Whether the last unstar can still be undone
+    pub fn can_undo(&self) -> bool {
+        !self.last_unstarred.is_empty()
+            && (self.focus_mode
+                || self
+                    .last_unstarred_at
+                    .is_some_and(|at| at.elapsed() < UNDO_WINDOW))
+    }
+
+    /// Enter or leave focus mode. Unstars made in focus mode form one undo
+    /// group, and leaving restarts the undo window so it can still be undone.
+    pub fn set_focus_mode(&mut self, on: bool) {
+        if on == self.focus_mode {
+            return;
+        }
+        if on {
+            // Start a fresh undo group rather than extending an older one
+            self.last_unstarred.clear();
+            self.last_unstarred_at = None;
+        } else if !self.last_unstarred.is_empty() {
+            self.last_unstarred_at = Some(Instant::now());
+        }
+        self.focus_mode = on;
+    }
+
+    /// Drop the undo entry once its window has passed
+    pub fn expire_undo(&mut self) {
+        if !self.can_undo() {
+            self.last_unstarred.clear();
+            self.last_unstarred_at = None;
+        }
+    }
+
+    /// Put the last unstarred repositories back at their prior starred order,
+    /// returning them so the caller can re-star them on GitHub
+    pub fn undo_last_unstar(&mut self) -> Vec<Repository> {
+        if !self.can_undo() {
+            return Vec::new();
+        }
+        self.last_unstarred_at = None;
+        let restored = std::mem::take(&mut self.last_unstarred);
+        self.session_unstar_count = self.session_unstar_count.saturating_sub(restored.len());
+
+        for repo in &restored {
+            self.insert_by_starred_order(repo.clone());
+        }
+        if let Some(total) = self.total_starred.as_mut() {
+            *total += restored.len() as u32;
+        }
+        self.sort_loaded_repositories();
+
+        restored
+    }
+
+    /// Re-starring an undone repository failed, so it's still unstarred on
+    /// GitHub. Takes it back out of the list without offering another undo.
+    pub fn undo_failed(&mut self, id: u64) {
+        let before = self.repositories.len();
+        self.repositories.retain(|r| r.id != id);
+        if self.repositories.len() == before {
+            return;
+        }
+        self.selection.remove_ids(&[id]);
+        self.session_unstar_count += 1;
+        if let Some(total) = self.total_starred.as_mut() {
+            *total = total.saturating_sub(1);
+        }
+    }
+
+    /// Clear error message
+    pub fn clear_error(&mut self) {
+        self.error = None;
+    }
+
+    pub fn clear_warning(&mut self) {
+        self.warning = None;
+    }
+
+    /// Write the config, warning if it can't be saved. The settings stay in
+    /// effect in memory either way.
+    fn save_config(&mut self, what: &str) {
+        if let Err(e) = ConfigService::save(&self.config) {
+            tracing::warn!("Failed to save {}: {}", what, e);
+            self.warning = Some(config_save_warning(&e));
+        }
+    }
+
+    /// Show the load failure screen, keeping the token for a retry
+    pub fn fail_load(&mut self, error: String) {
+        self.showing_cached = false;
+        self.offline = false;
+        self.loading = false;
+        self.error = Some(error);
+        self.screen = AppScreen::LoadFailed;
+    }
+
+    /// Go back to the loading screen, which starts a fresh load
+    pub fn retry_load(&mut self) {
+        self.error = None;
+        self.offline = false;
+        self.loading = false;
+        self.screen = AppScreen::Loading;
+    }
+
+    /// Set error message
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Logout and clear token. The session always ends; if the saved token
+    /// can't be removed, `warning` says so.
+    pub fn logout(&mut self) {
+        self.clear_session();
+        self.config.github.personal_access_token = None;
+        self.config.github.token_in_keychain = false;
+        if let Err(e) = CacheService::clear(self.config.active_profile_name()) {
+            tracing::warn!("Failed to clear star cache: {}", e);
+        }
+        if let Err(e) = ConfigService::clear_token() {
+            tracing::error!("Failed to clear saved token: {:#}", e);
+            self.warning = Some(format!("Logged out, but the saved token couldn't be removed: {:#}", e));
+        }
+    }
+
+    /// Switch to another saved GitHub account, loading its stars if it's
+    /// signed in and showing the setup screen otherwise
+    pub fn switch_profile(&mut self, index: usize) -> anyhow::Result<()> {
+        let (saved, warning) = ConfigService::switch_profile(index)?;
+        self.enter_profile(saved);
+        self.warning = warning;
+        Ok(())
+    }
+
+    /// Add a GitHub account and switch to it
+    pub fn add_profile(&mut self, name: &str) -> anyhow::Result<()> {
+        let (saved, warning) = ConfigService::add_profile(name)?;
+        self.enter_profile(saved);
+        self.warning = warning;
+        Ok(())
+    }
+
+    /// Start over as the profile that's active in `saved`, showing its
+    /// cached stars while they refresh
+    fn enter_profile(&mut self, saved: AppConfig) {
+        self.clear_session();
+        self.config.github = saved.github;
+        self.config.profiles = saved.profiles;
+        self.config.active_profile = saved.active_profile;
+        if self.config.has_token() {
+            self.screen = AppScreen::Loading;
+            match CacheService::load_repos(self.config.active_profile_name()) {
+                Ok(Some(cache)) => self.restore_cache(cache),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to load star cache: {}", e),
+            }
+        }
+    }
+
+    /// Reset everything tied to the signed-in account, in memory only
+    fn clear_session(&mut self) {
+        self.github_service = None;
+        self.username = None;
+        // A load in flight is dropped by the epoch bump below
+        self.loading = false;
+        self.loading_more = false;
+        self.repositories.clear();
+        self.selection.clear();
+        self.unstar_progress = None;
+        self.readme_cache.clear();
+        self.expanded_readme = None;
+        self.repo_activity.clear();
+        self.search_query.clear();
+        self.topic_filter = None;
+        self.stale_threshold_days = None;
+        self.archived_only = false;
+        self.no_description_only = false;
+        self.fork_filter = ForkFilter::All;
+        self.own_only = false;
+        self.duplicates_only = false;
+        self.license_filter = None;
+        self.total_starred = None;
+        self.load_all_paused = false;
+        self.load_all_cancel.store(false, Ordering::Relaxed);
+        self.showing_cached = false;
+        self.refresh_pending = false;
+        self.offline = false;
+        self.last_refreshed = None;
+        // Nothing of the old account left to write
+        self.cache_dirty = false;
+        self.lists.clear();
+        self.list_menu_open = None;
+        self.would_unstar.clear();
+        self.pending_removal.clear();
+        self.previous_session = None;
+        self.changed_in_app.clear();
+        self.session_diff = None;
+        // Drop results of tasks started before logging out
+        self.epoch += 1;
+        self.rate_limit = None;
+        self.last_unstarred.clear();
+        self.last_unstarred_at = None;
+        self.session_unstar_count = 0;
+        self.focus_mode = false;
+        self.diagnostics = None;
+        self.screen = AppScreen::Setup;
+    }
+
+    /// Running total for the header, e.g. "Unstarred 87 this session"
+    pub fn session_unstar_label(&self) -> Option<String> {
+        (self.session_unstar_count > 0)
+            .then(|| format!("Unstarred {} this session", format_count(self.session_unstar_count)))
+    }
+
+    /// When a repository was starred relative to the others, e.g. "#1,234 of
+    /// 1,500" where #1 is the oldest star. Only known while sorted by starred
+    /// time, since `starred_order` is the position in that fetch, and only
+    /// when the total is known.
+    pub fn star_age_label(&self, repo: &Repository) -> Option<String> {
+        if self.sort_field != SortField::Starred {
+            return None;
+        }
+        let total = self.total_starred?;
+        let number = match self.sort_direction {
+            SortDirection::Asc => repo.starred_order.checked_add(1)?,
+            SortDirection::Desc => total.checked_sub(repo.starred_order)?,
+        };
+        (1..=total).contains(&number).then(|| {
+            format!(
+                "#{} of {}",
+                format_count(number as usize),
+                format_count(total as usize)
+            )
+        })
+    }
+
+    /// Loading progress, e.g. "Loaded 100 / 1,423", or just the loaded
+    /// count when the total is unknown
+    pub fn load_progress_label(&self) -> String {
+        let loaded = format_count(self.repositories.len());
+        match self.total_starred {
+            Some(total) => format!("Loaded {} / {}", loaded, format_count(total as usize)),
+            None => format!("Loaded {}", loaded),
+        }
+    }
+
+    /// Whether the next page should be fetched automatically (`load_all`)
+    pub fn should_auto_load(&self) -> bool {
+        self.config.load_all
+            && self.screen == AppScreen::RepositoryList
+            && self.has_more
+            && !self.loading
+            && !self.loading_more
+            && !self.load_all_paused
+            && !self.is_load_all_cancelled()
+            && !self.is_read_only()
+            // New pages would change what an open confirmation is about
+            && self.pending_action.is_none()
+    }
+
+    /// Whether `load_all` is still working through the remaining pages,
+    /// i.e. whether "Stop loading" has anything to stop
+    pub fn is_auto_loading(&self) -> bool {
+        self.config.load_all
+            && self.has_more
+            && !self.showing_cached
+            && !self.offline
+            && !self.load_all_paused
+            && !self.is_load_all_cancelled()
+    }
+
+    /// Stop `load_all` after the page in flight, keeping what's loaded.
+    /// `has_more` stays set so "Load More" still fetches the rest by hand.
+    pub fn stop_loading_all(&mut self) {
+        self.load_all_cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_load_all_cancelled(&self) -> bool {
+        self.load_all_cancel.load(Ordering::Relaxed)
+    }
+
+    /// Whether regaining focus should reload the list (`reload_on_focus`).
+    /// Holds off while anything is in progress or selected, since a reload
+    /// starts the list over.
+    pub fn should_reload_on_focus(&self, now: DateTime<Utc>) -> bool {
+        let interval = chrono::Duration::minutes(self.config.reload_on_focus_after_mins.max(1).into());
+        self.config.reload_on_focus
+            && self.screen == AppScreen::RepositoryList
+            && self.github_service.is_some()
+            && !self.loading
+            && !self.loading_more
+            && self.unstar_progress.is_none()
+            && self.pending_action.is_none()
+            && self.selection.count() == 0
+            && !self.is_rate_limited()
+            && self.last_refreshed.is_some_and(|refreshed| now - refreshed >= interval)
+    }
+
+    /// Visible repositories at `rows` (indices into `visible_repositories`)
+    /// whose activity hasn't been asked for yet, as (id, owner, name). Marks
+    /// them as fetching, so each is only requested once. Nothing is fetched
+    /// while read-only, and no more than the rate limit allows while keeping
+    /// `ACTIVITY_RATE_LIMIT_RESERVE` requests back.
+    pub fn claim_activity_fetches(&mut self, rows: Range<usize>) -> Vec<(u64, String, String)> {
+        let claimed: Vec<_> = self
+            .activity_to_fetch(rows)
+            .into_iter()
+            .map(|r| (r.id, r.owner.clone(), r.name.clone()))
+            .collect();
+        for (id, _, _) in &claimed {
+            self.repo_activity.insert(*id, None);
+        }
+        claimed
+    }
+
+    /// Repositories at `rows` that `claim_activity_fetches` would claim
+    pub fn activity_to_fetch(&self, rows: Range<usize>) -> Vec<&Repository> {
+        if self.github_service.is_none() || self.is_read_only() {
+            return Vec::new();
+        }
+        let budget = self.rate_limit.as_ref().map_or(usize::MAX, |rate_limit| {
+            (rate_limit.remaining.saturating_sub(ACTIVITY_RATE_LIMIT_RESERVE) / ACTIVITY_REQUESTS_PER_REPO) as usize
+        });
+        self.visible_repositories()
+            .into_iter()
+            .skip(rows.start)
+            .take(rows.len())
+            .filter(|r| !self.repo_activity.contains_key(&r.id))
+            .take(budget)
+            .collect()
+    }
+
+    /// Give back a claim that wasn't fetched, so the row is asked for again
+    /// when it next comes into view
+    pub fn release_activity_fetch(&mut self, id: u64) {
+        if self.repo_activity.get(&id) == Some(&None) {
+            self.repo_activity.remove(&id);
+        }
+    }
+
+    /// Whether repository `id` is at one of `rows` of the visible list
+    pub fn is_in_rows(&self, id: u64, rows: Range<usize>) -> bool {
+        self.visible_repositories()
+            .get(rows)
+            .is_some_and(|repos| repos.iter().any(|r| r.id == id))
+    }
+
+    /// Whether a signed-in session has gone `auto_logout_minutes` without
+    /// a click or key press since `last_interaction`
+    pub fn should_auto_logout(&self, last_interaction: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.config.auto_logout_minutes.is_some_and(|minutes| {
+            self.config.has_token() && now - last_interaction >= chrono::Duration::minutes(minutes.max(1).into())
+        })
+    }
+
+    /// Log out an idle session, back to the setup screen with a note saying why
+    pub fn auto_logout(&mut self) {
+        self.logout();
+        let minutes = self.config.auto_logout_minutes.unwrap_or_default();
+        let note = format!("Logged out after {} minutes without activity", minutes);
+        self.warning = Some(match self.warning.take() {
+            Some(failure) => format!("{}. {}", note, failure),
+            None => note,
+        });
+    }
+
+    /// Whether the API rate limit is used up, blocking further requests
+    pub fn is_rate_limited(&self) -> bool {
+        self.rate_limit.as_ref().is_some_and(RateLimit::is_exhausted)
+    }
+
+    /// Whether unstarring, starring and loading more pages are off, either
+    /// because the list is offline or the rate limit is used up
+    pub fn is_read_only(&self) -> bool {
+        self.offline || self.is_rate_limited()
+    }
+
+    /// Handle API errors. Only a token GitHub rejected (`TokenExpiredError`)
+    /// logs out; network errors and the like just show the error.
+    pub fn handle_api_error(&mut self, err: anyhow::Error, context: &str) {
+        if is_token_expired_error(&err) {
+            self.logout();
+            self.error = Some("Token expired. Please login again.".to_string());
+        } else if let Some(message) = proxy_error_message(&err) {
+            self.error = Some(format!("{}: {}", context, message));
+        } else {
+            self.error = Some(format!("{}: {}", context, err));
+        }
+    }
+}
+
+impl Global for AppState {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{create_test_repo, GitHubConfig};
+    use chrono::Utc;
+
+    #[test]
+    fn test_app_screen_default() {
+        let screen = AppScreen::default();
+        assert_eq!(screen, AppScreen::Setup);
+    }
+
+    #[test]
+    fn test_api_sort_params() {
+        let mut state = AppState {
+            sort_field: SortField::Pushed,
+            sort_direction: SortDirection::Asc,
+            ..Default::default()
+        };
+        assert_eq!(state.api_sort_params(), ("updated", "asc"));
+
+        state.sort_field = SortField::Stars;
+        assert_eq!(state.api_sort_params(), ("created", "desc"));
+        state.sort_direction = SortDirection::Desc;
+        assert_eq!(state.api_sort_params(), ("created", "desc"));
+    }
+
+    #[test]
+    fn test_sort_loaded_repositories_by_stars() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "a", "o"),
+                create_test_repo(2, "b", "o"),
+                create_test_repo(3, "c", "o"),
+            ],
+            sort_field: SortField::Stars,
+            sort_direction: SortDirection::Desc,
+            ..Default::default()
+        };
+        state.repositories[0].stargazers_count = 10;
+        state.repositories[1].stargazers_count = 300;
+        state.repositories[2].stargazers_count = 42;
+
+        state.sort_loaded_repositories();
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_sort_loaded_repositories_by_issues() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "a", "o"),
+                create_test_repo(2, "b", "o"),
+                create_test_repo(3, "c", "o"),
+            ],
+            sort_field: SortField::Issues,
+            sort_direction: SortDirection::Desc,
+            ..Default::default()
+        };
+        state.repositories[0].open_issues_count = 7;
+        state.repositories[1].open_issues_count = 0;
+        state.repositories[2].open_issues_count = 120;
+
+        state.sort_loaded_repositories();
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+
+        state.sort_direction = SortDirection::Asc;
+        state.sort_loaded_repositories();
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_sort_loaded_repositories_by_name_ignores_case() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "zed", "o"),
+                create_test_repo(2, "Alacritty", "o"),
+                create_test_repo(3, "bat", "o"),
+            ],
+            sort_field: SortField::Name,
+            sort_direction: SortDirection::Asc,
+            ..Default::default()
+        };
+
+        state.sort_loaded_repositories();
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn test_sort_loaded_repositories_by_language_puts_none_last() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "a", "o"),
+                create_test_repo(2, "b", "o"),
+                create_test_repo(3, "c", "o"),
+            ],
+            sort_field: SortField::Language,
+            sort_direction: SortDirection::Asc,
+            ..Default::default()
+        };
+        state.repositories[1].language = Some("Rust".to_string());
+        state.repositories[2].language = Some("go".to_string());
+
+        state.sort_loaded_repositories();
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_sort_loaded_repositories_skips_api_fields() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(2, "b", "o"), create_test_repo(1, "a", "o")],
+            sort_field: SortField::Pushed,
+            ..Default::default()
+        };
+
+        state.sort_loaded_repositories();
+        assert_eq!(state.repositories[0].id, 2);
+    }
+
+    #[test]
+    fn test_unstar_progress_eta_none_before_first_completion() {
+        let progress = UnstarProgress::new(5);
+        assert_eq!(progress.remaining(), 5);
+        assert!(progress.eta().is_none());
+        assert!(progress.eta_label().is_none());
+    }
+
+    #[test]
+    fn test_unstar_progress_eta_from_average() {
+        let mut progress = UnstarProgress::new(10);
+        progress.record(Duration::from_secs(2));
+        progress.record(Duration::from_secs(2));
+
+        assert_eq!(progress.completed, 2);
+        assert_eq!(progress.eta(), Some(Duration::from_secs(16)));
+        assert_eq!(progress.eta_label(), Some("~16s left".to_string()));
+    }
+
+    #[test]
+    fn test_unstar_progress_fraction_and_label() {
+        let mut progress = UnstarProgress::new(300);
+        assert_eq!(progress.fraction(), 0.0);
+        assert_eq!(progress.label(), "Unstarred 0 / 300");
+
+        for _ in 0..150 {
+            progress.record(Duration::from_millis(100));
+        }
+        assert_eq!(progress.fraction(), 0.5);
+        assert_eq!(progress.label(), "Unstarred 150 / 300 · ~15s left");
+
+        assert_eq!(UnstarProgress::new(0).fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_unstar_progress_reports_chunks() {
+        let mut progress = UnstarProgress::chunked(60, 25);
+        assert_eq!(progress.chunk(), Some((1, 3)));
+        assert_eq!(progress.label(), "Unstarred 0 / 60 · batch 1 of 3");
+
+        for _ in 0..50 {
+            progress.record(Duration::from_millis(100));
+        }
+        assert_eq!(progress.chunk(), Some((3, 3)));
+        for _ in 0..10 {
+            progress.record(Duration::from_millis(100));
+        }
+        assert_eq!(progress.chunk(), Some((3, 3)));
+
+        // A single chunk isn't worth mentioning
+        assert_eq!(UnstarProgress::chunked(25, 25).chunk(), None);
+        assert_eq!(UnstarProgress::new(60).chunk(), None);
+    }
+
+    #[test]
+    fn test_unstar_progress_eta_is_conservative_on_slowdown() {
+        let mut progress = UnstarProgress::new(100);
+        for _ in 0..20 {
+            progress.record(Duration::from_millis(100));
+        }
+        // A run of slow (backed-off) requests should push the estimate up
+        for _ in 0..ETA_WINDOW {
+            progress.record(Duration::from_secs(1));
+        }
+
+        assert_eq!(progress.remaining(), 70);
+        assert_eq!(progress.eta(), Some(Duration::from_secs(70)));
+        assert_eq!(progress.eta_label(), Some("~1m 10s left".to_string()));
+    }
+
+    #[test]
+    fn test_app_state_default() {
+        let state = AppState::default();
+        assert_eq!(state.screen, AppScreen::Setup);
+        assert!(state.repositories.is_empty());
+        assert_eq!(state.selection.count(), 0);
+        assert!(!state.loading);
+        assert!(state.error.is_none());
+        assert!(state.username.is_none());
+        assert!(state.pending_action.is_none());
+        assert!(state.unstar_progress.is_none());
+        assert_eq!(state.sort_field, SortField::Pushed);
+        assert_eq!(state.sort_direction, SortDirection::Asc);
+    }
+
+    #[test]
+    fn test_from_config_with_token() {
+        let config = AppConfig {
+            github: GitHubConfig {
+                personal_access_token: Some("valid_token".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let state = AppState::from_config(config);
+        assert_eq!(state.screen, AppScreen::Loading);
+        assert_eq!(state.current_page, 1);
+        assert!(state.has_more);
+    }
+
+    #[test]
+    fn test_from_config_restores_sort() {
+        let config = AppConfig {
+            sort_field: SortField::Stars,
+            sort_direction: SortDirection::Desc,
+            ..Default::default()
+        };
+
+        let state = AppState::from_config(config);
+        assert_eq!(state.sort_field, SortField::Stars);
+        assert_eq!(state.sort_direction, SortDirection::Desc);
+    }
+
+    #[test]
+    fn test_from_config_restores_theme() {
+        let config = AppConfig {
+            theme: crate::models::ThemeMode::Light,
+            ..Default::default()
+        };
+
+        let state = AppState::from_config(config);
+        assert_eq!(state.config.theme, crate::models::ThemeMode::Light);
+        assert_eq!(AppState::default().config.theme, crate::models::ThemeMode::Dark);
+    }
+
+    #[test]
+    fn test_from_config_without_token() {
+        let config = AppConfig {
+            github: GitHubConfig {
+                personal_access_token: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let state = AppState::from_config(config);
+        assert_eq!(state.screen, AppScreen::Setup);
+    }
+
+    #[test]
+    fn test_from_config_with_empty_token() {
+        let config = AppConfig {
+            github: GitHubConfig {
+                personal_access_token: Some("".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let state = AppState::from_config(config);
+        assert_eq!(state.screen, AppScreen::Setup);
+    }
+
+    #[test]
+    fn test_get_selected_repos() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "repo1", "owner1"),
+                create_test_repo(2, "repo2", "owner2"),
+                create_test_repo(3, "repo3", "owner3"),
+            ],
+            ..Default::default()
+        };
+
+        state.selection.toggle(1);
+        state.selection.toggle(3);
+
+        let selected = state.get_selected_repos();
+        assert_eq!(selected.len(), 2);
+        assert!(selected.contains(&("owner1".to_string(), "repo1".to_string())));
+        assert!(selected.contains(&("owner3".to_string(), "repo3".to_string())));
+    }
+
+    #[test]
+    fn test_get_selected_ids() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "repo1", "owner1"),
+                create_test_repo(2, "repo2", "owner2"),
+            ],
+            ..Default::default()
+        };
+
+        state.selection.toggle(1);
+        state.selection.toggle(2);
+
+        let ids = state.get_selected_ids();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&1));
+        assert!(ids.contains(&2));
+    }
+
+    #[test]
+    fn test_remove_repos() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "repo1", "owner1"),
+                create_test_repo(2, "repo2", "owner2"),
+                create_test_repo(3, "repo3", "owner3"),
+            ],
+            ..Default::default()
+        };
+
+        state.selection.toggle(1);
+        state.selection.toggle(2);
+        state.selection.toggle(3);
+
+        state.remove_repos(&[1, 3]);
+
+        assert_eq!(state.repositories.len(), 1);
+        assert_eq!(state.repositories[0].id, 2);
+        assert_eq!(state.selection.count(), 1);
+        assert!(state.selection.is_selected(2));
+    }
+
+    #[test]
+    fn test_record_dry_run_keeps_repos() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "repo1", "owner1"),
+                create_test_repo(2, "repo2", "owner2"),
+            ],
+            ..Default::default()
+        };
+        state.selection.toggle(1);
+
+        state.record_dry_run(&[1, 99]);
+
+        assert_eq!(state.repositories.len(), 2);
+        assert!(state.selection.is_selected(1));
+        assert_eq!(state.would_unstar, HashSet::from([1]));
+        assert!(!state.can_undo());
+    }
+
+    fn create_ordered_repos(count: u64) -> Vec<Repository> {
+        (1..=count)
+            .map(|id| Repository {
+                archived: false,
+                fork: false,
+                starred_order: id as u32,
+                starred_at: None,
+                ..create_test_repo(id, &format!("repo{}", id), "owner")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_failed_undo_is_not_undoable_again() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            total_starred: Some(3),
+            ..Default::default()
+        };
+        state.remove_repos(&[2]);
+        let restored = state.undo_last_unstar();
+        assert_eq!(restored.len(), 1);
+
+        state.undo_failed(2);
+
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(state.total_starred, Some(2));
+        assert_eq!(state.session_unstar_count, 1);
+        assert!(!state.can_undo());
+        assert!(state.last_unstarred.is_empty());
+    }
+
+    #[test]
+    fn test_undo_last_unstar_restores_starred_order() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(5),
+            ..Default::default()
+        };
+
+        // A bulk unstar removes repos one at a time
+        state.remove_repos(&[2]);
+        state.remove_repos(&[4]);
+        assert!(state.can_undo());
+        assert_eq!(state.last_unstarred.len(), 2);
+
+        let restored = state.undo_last_unstar();
+        assert_eq!(restored.len(), 2);
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+        assert!(!state.can_undo());
+        assert!(state.undo_last_unstar().is_empty());
+    }
+
+    #[test]
+    fn test_failed_removal_rolls_back_to_starred_order() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(5),
+            total_starred: Some(5),
+            ..Default::default()
+        };
+        state.selection.toggle(2);
+        state.selection.toggle(4);
+
+        state.begin_removal(&[2, 4]);
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3, 5]);
+        assert_eq!(state.pending_removal.len(), 2);
+        assert_eq!(state.selection.count(), 0);
+        assert_eq!(state.total_starred, Some(3));
+
+        state.confirm_removal(2);
+        state.rollback_removal(4);
+
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3, 4, 5]);
+        assert!(state.pending_removal.is_empty());
+        assert_eq!(state.total_starred, Some(4));
+        // The failed one is selected again, ready to retry
+        assert_eq!(state.get_selected_ids(), vec![4]);
+        // Only the confirmed unstar can be undone
+        assert_eq!(state.last_unstarred.len(), 1);
+        assert_eq!(state.last_unstarred[0].id, 2);
+    }
+
+    #[test]
+    fn test_pending_removals_stay_hidden_on_refresh() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            ..Default::default()
+        };
+        state.begin_removal(&[2]);
+
+        state.merge_new_repos(create_ordered_repos(3));
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        state.reconcile_refreshed(create_ordered_repos(3), 1, false);
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        state.rollback_removal(2);
+        assert_eq!(state.repositories.len(), 3);
+    }
+
+    #[test]
+    fn test_unstar_finishing_after_reload_is_recorded() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            ..Default::default()
+        };
+        state.begin_removal(&[2, 3]);
+
+        state.begin_reload();
+        state.merge_new_repos(create_ordered_repos(3));
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1]);
+
+        assert!(state.confirm_removal(2));
+        assert_eq!(state.session_unstar_count, 1);
+        assert_eq!(state.last_unstarred[0].id, 2);
+        assert!(state.rollback_removal(3));
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        // Nothing is left to record once logged out
+        state.begin_removal(&[1]);
+        state.clear_session();
+        assert!(!state.confirm_removal(1));
+        assert_eq!(state.session_unstar_count, 0);
+    }
+
+    #[test]
+    fn test_add_starred_repo_prepends_once() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(2),
+            ..Default::default()
+        };
+
+        state.add_starred_repo(create_test_repo(9, "new", "owner"));
+        state.add_starred_repo(create_test_repo(9, "new", "owner"));
+
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![9, 1, 2]);
+        assert_eq!(state.repositories[0].starred_order, 0);
+    }
+
+    #[test]
+    fn test_new_star_ordered_before_loaded_stars() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            ..Default::default()
+        };
+        state.repositories[0].starred_order = 0;
+        state.begin_removal(&[3]);
+
+        state.add_starred_repo(create_test_repo(9, "new", "owner"));
+        let orders: Vec<(u64, u32)> = state.repositories.iter().map(|r| (r.id, r.starred_order)).collect();
+        assert_eq!(orders, vec![(9, 0), (1, 1), (2, 3)]);
+        // A rollback still lands after the others
+        state.rollback_removal(3);
+        assert_eq!(state.repositories.last().map(|r| (r.id, r.starred_order)), Some((3, 4)));
+
+        state.sort_field = SortField::Starred;
+        state.sort_direction = SortDirection::Asc;
+        state.add_starred_repo(create_test_repo(10, "newer", "owner"));
+        assert_eq!(state.repositories[0].starred_order, 5);
+    }
+
+    #[test]
+    fn test_focus_mode_collects_unstars_into_one_undo() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(4),
+            ..Default::default()
+        };
+        state.remove_repos(&[1]);
+
+        state.set_focus_mode(true);
+        assert!(state.last_unstarred.is_empty());
+        state.remove_repos(&[2]);
+        // Slower than the undo window between keypresses
+        state.last_unstarred_at = Instant::now().checked_sub(UNDO_WINDOW);
+        state.remove_repos(&[3]);
+        state.last_unstarred_at = Instant::now().checked_sub(UNDO_WINDOW);
+        state.expire_undo();
+        assert!(state.can_undo());
+        assert_eq!(state.last_unstarred.len(), 2);
+
+        // Leaving restarts the window rather than expiring the group
+        state.set_focus_mode(false);
+        assert!(state.can_undo());
+        let restored: Vec<u64> = state.undo_last_unstar().iter().map(|r| r.id).collect();
+        assert_eq!(restored, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_undo_expires_after_window() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            ..Default::default()
+        };
+
+        state.remove_repos(&[1]);
+        state.last_unstarred_at = Instant::now().checked_sub(UNDO_WINDOW);
+        assert!(!state.can_undo());
+        assert!(state.undo_last_unstar().is_empty());
+
+        // A later unstar starts a fresh undo entry
+        state.remove_repos(&[2]);
+        assert_eq!(state.last_unstarred.len(), 1);
+        assert_eq!(state.last_unstarred[0].id, 2);
+
+        state.last_unstarred_at = Instant::now().checked_sub(UNDO_WINDOW);
+        state.expire_undo();
+        assert!(state.last_unstarred.is_empty());
+    }
+
+    #[test]
+    fn test_visible_repositories() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(state.visible_repositories().len(), 3);
+
+        state.search_query = "SHARKDP".to_string();
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        state.search_query = "  ".to_string();
+        assert_eq!(state.visible_repositories().len(), 3);
+    }
+
+    #[test]
+    fn test_network_error_keeps_session() {
+        let mut state = AppState {
+            username: Some("octocat".to_string()),
+            repositories: vec![create_test_repo(1, "repo", "owner")],
+            screen: AppScreen::RepositoryList,
+            ..Default::default()
+        };
+
+        state.handle_api_error(anyhow::anyhow!("error sending request: connection reset"), "Failed to reload");
+
+        assert_eq!(state.screen, AppScreen::RepositoryList);
+        assert_eq!(state.username.as_deref(), Some("octocat"));
+        assert_eq!(state.repositories.len(), 1);
+        assert_eq!(
+            state.error.as_deref(),
+            Some("Failed to reload: error sending request: connection reset")
+        );
+    }
+
+    fn composed_filter_state() -> AppState {
+        let mut rust_fork = create_test_repo(1, "ripgrep", "fork-owner");
+        rust_fork.language = Some("Rust".to_string());
+        rust_fork.fork = true;
+        rust_fork.topics = vec!["cli".to_string()];
+
+        let mut rust_archived = create_test_repo(2, "old-cli", "owner");
+        rust_archived.language = Some("Rust".to_string());
+        rust_archived.archived = true;
+        rust_archived.topics = vec!["cli".to_string()];
+
+        let mut go_archived = create_test_repo(3, "gocli", "owner");
+        go_archived.language = Some("Go".to_string());
+        go_archived.archived = true;
+        go_archived.topics = vec!["cli".to_string()];
+
+        let mut rust_plain = create_test_repo(4, "serde", "owner");
+        rust_plain.language = Some("Rust".to_string());
+        rust_plain.description = Some("Serialization".to_string());
+
+        AppState {
+            repositories: vec![rust_fork, rust_archived, go_archived, rust_plain],
+            ..Default::default()
+        }
+    }
+
+    fn visible_ids(state: &AppState) -> Vec<u64> {
+        state.visible_repositories().iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn test_visible_repositories_composes_language_and_archived() {
+        let mut state = composed_filter_state();
+        state.search_query = "language:rust".to_string();
+        assert_eq!(visible_ids(&state), vec![1, 2, 4]);
+
+        state.archived_only = true;
+        assert_eq!(visible_ids(&state), vec![2]);
+    }
+
+    #[test]
+    fn test_visible_repositories_composes_topic_fork_and_search() {
+        let mut state = composed_filter_state();
+        state.topic_filter = Some("cli".to_string());
+        state.fork_filter = ForkFilter::Exclude;
+        assert_eq!(visible_ids(&state), vec![2, 3]);
+
+        state.search_query = "go".to_string();
+        assert_eq!(visible_ids(&state), vec![3]);
+
+        state.fork_filter = ForkFilter::Only;
+        assert!(visible_ids(&state).is_empty());
+    }
+
+    #[test]
+    fn test_license_filter() {
+        let mut state = composed_filter_state();
+        state.repositories[0].license = Some("GNU General Public License v3.0".to_string());
+        state.repositories[1].license = Some("MIT License".to_string());
+        state.repositories[2].license = Some("GNU General Public License v3.0".to_string());
+        state.repositories[3].license = None;
+
+        assert_eq!(
+            state.license_options(),
+            vec![
+                "GNU General Public License v3.0".to_string(),
+                "MIT License".to_string(),
+                NO_LICENSE.to_string(),
+            ]
+        );
+
+        state.license_filter = Some("GNU General Public License v3.0".to_string());
+        assert_eq!(visible_ids(&state), vec![1, 3]);
+        // Composes with the other filters
+        state.archived_only = true;
+        assert_eq!(visible_ids(&state), vec![3]);
+        assert_eq!(
+            state.filter_spec().describe(1),
+            "1 archived repository licensed under GNU General Public License v3.0"
+        );
+
+        state.archived_only = false;
+        state.license_filter = Some(NO_LICENSE.to_string());
+        assert_eq!(visible_ids(&state), vec![4]);
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_by_score() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ready-quick-write", "owner"),
+                create_test_repo(2, "ripgrep", "BurntSushi"),
+                create_test_repo(3, "reqwest", "seanmonstar"),
+            ],
+            search_query: "reqw".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(visible_ids(&state), vec![3]);
+
+        state.config.fuzzy_search = true;
+        assert_eq!(visible_ids(&state), vec![3, 1]);
+        assert!(state.filter_spec().describe(2).ends_with("fuzzy-matching \"reqw\""));
+
+        state.search_query.clear();
+        assert_eq!(visible_ids(&state), vec![1, 2, 3]);
+        assert!(!state.is_filtered());
+    }
+
+    #[test]
+    fn test_own_repos() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "dotfiles", "Octocat"),
+                create_test_repo(2, "ripgrep", "BurntSushi"),
+                create_test_repo(3, "hello-world", "octocat"),
+            ],
+            ..Default::default()
+        };
+        // Nobody signed in owns nothing
+        assert!(!state.is_own_repo(&state.repositories[0]));
+
+        state.username = Some("octocat".to_string());
+        assert!(state.is_own_repo(&state.repositories[0]));
+        assert!(!state.is_own_repo(&state.repositories[1]));
+
+        state.own_only = true;
+        assert_eq!(visible_ids(&state), vec![1, 3]);
+        assert!(state.is_filtered());
+        assert_eq!(state.filter_spec().describe(2), "2 repositories owned by octocat");
+
+        state.search_query = "hello".to_string();
+        assert_eq!(visible_ids(&state), vec![3]);
+    }
+
+    #[test]
+    fn test_duplicates_only() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "tool", "old-org"),
+                create_test_repo(2, "ripgrep", "BurntSushi"),
+                create_test_repo(3, "fd", "sharkdp"),
+                create_test_repo(4, "tool", "new-org"),
+                create_test_repo(5, "fd", "someone"),
+            ],
+            ..Default::default()
+        };
+        state.config.pinned_ids.insert(5);
+        state.duplicates_only = true;
+        // One group after another, pinned repositories included in place
+        assert_eq!(visible_ids(&state), vec![1, 4, 3, 5]);
+        assert!(state.is_filtered());
+
+        state.search_query = "tool".to_string();
+        assert_eq!(visible_ids(&state), vec![1, 4]);
+    }
+
+    #[test]
+    fn test_unstar_filter_targets_skip_pinned() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "old-tool", "owner"),
+                create_test_repo(2, "keeper", "owner"),
+                create_test_repo(3, "ripgrep", "BurntSushi"),
+            ],
+            own_only: true,
+            username: Some("owner".to_string()),
+            ..Default::default()
+        };
+        state.config.pinned_ids.insert(2);
+        let spec = state.filter_spec();
+        let ids: Vec<u64> = state.unstar_filter_targets(&spec).iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    #[test]
+    fn test_unstar_filtered_with_duplicates_only() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "tool", "old-org"),
+                create_test_repo(2, "ripgrep", "BurntSushi"),
+                create_test_repo(3, "tool", "new-org"),
+            ],
+            duplicates_only: true,
+            ..Default::default()
+        };
+        assert!(state.is_filtered());
+        state.confirm_unstar_filtered();
+        let Some(PendingAction::UnstarByFilter(spec, ids)) = state.pending_action.clone() else {
+            panic!("expected an UnstarByFilter confirmation");
+        };
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(spec.describe(ids.len()), "2 likely duplicate repositories");
+    }
+
+    #[test]
+    fn test_unstar_filtered_targets_fixed_when_confirming() {
+        let mut state = AppState {
+            screen: AppScreen::RepositoryList,
+            repositories: vec![create_test_repo(1, "a", "owner")],
+            has_more: true,
+            ..Default::default()
+        };
+        state.config.load_all = true;
+        state.repositories[0].archived = true;
+        state.archived_only = true;
+        assert!(state.should_auto_load());
+
+        state.confirm_unstar_filtered();
+        // No pages load while the dialog is open
+        assert!(!state.should_auto_load());
+        // and one that arrived anyway isn't added to the targets
+        state.merge_new_repos(vec![Repository { archived: true, ..create_test_repo(2, "b", "owner") }]);
+        assert_eq!(state.pending_action.as_ref().and_then(PendingAction::bulk_count), Some(1));
+    }
+
+    #[test]
+    fn test_select_all_follows_composed_filters() {
+        let mut state = composed_filter_state();
+        state.archived_only = true;
+        state.no_description_only = true;
+        state.search_query = "language:go".to_string();
+
+        state.toggle_select_all_filtered();
+        assert_eq!(state.get_selected_ids(), vec![3]);
+        assert_eq!(state.apply_filters(&FilterSpec::default()).len(), 4);
+    }
+
+    #[test]
+    fn test_cache_write_taken_once_per_change() {
+        let mut state = AppState::default();
+        assert!(state.take_cache_write().is_none());
+
+        state.queue_cache_save();
+        state.queue_cache_save();
+        assert!(state.take_cache_write().is_some());
+        assert!(state.take_cache_write().is_none());
+
+        // Logging out drops a write that hasn't happened yet
+        state.queue_cache_save();
+        state.clear_session();
+        assert!(state.take_cache_write().is_none());
+    }
+
+    #[test]
+    fn test_begin_reload_supersedes_older_loads() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "repo1", "owner")],
+            loading_more: true,
+            current_page: 3,
+            has_more: false,
+            ..Default::default()
+        };
+        state.selection.toggle(1);
+        let older = state.epoch;
+
+        let epoch = state.begin_reload();
+
+        assert!(state.is_current_epoch(epoch));
+        assert!(!state.is_current_epoch(older));
+        assert!(state.loading);
+        assert!(!state.loading_more);
+        assert!(state.repositories.is_empty());
+        assert_eq!(state.selection.count(), 0);
+        assert_eq!(state.current_page, 1);
+        assert!(state.has_more);
+
+        let newer = state.begin_reload();
+        assert!(!state.is_current_epoch(epoch));
+        assert!(state.is_current_epoch(newer));
+    }
+
+    #[test]
+    fn test_logout_advances_epoch() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "repo1", "owner")],
+            screen: AppScreen::RepositoryList,
+            loading: true,
+            ..Default::default()
+        };
+        let epoch = state.epoch;
+
+        state.clear_session();
+
+        assert!(!state.is_current_epoch(epoch));
+        assert!(state.repositories.is_empty());
+        assert_eq!(state.screen, AppScreen::Setup);
+        assert!(!state.loading);
+
+        // A reload after logging back in is a newer epoch still
+        let reloaded = state.begin_reload();
+        assert!(reloaded > epoch + 1);
+    }
+
+    #[test]
+    fn test_language_term_in_search() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            ..Default::default()
+        };
+        state.repositories[0].language = Some("Rust".to_string());
+        state.repositories[2].language = Some("Rust".to_string());
+
+        state.search_query = "language:rust".to_string();
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        state.search_query = "sharkdp language:rust".to_string();
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3]);
+        assert_eq!(
+            state.filter_spec().describe(1),
+            "1 repository in rust matching \"sharkdp\""
+        );
+    }
+
+    #[test]
+    fn test_preset_from_filters_roundtrips() {
+        let mut state = AppState {
+            search_query: "cli language:rust".to_string(),
+            archived_only: true,
+            fork_filter: ForkFilter::Only,
+            stale_threshold_days: Some(730),
+            ..Default::default()
+        };
+        let preset = state.preset_from_filters("  Old forks ");
+        assert_eq!(preset.name, "Old forks");
+        assert_eq!(preset.language.as_deref(), Some("rust"));
+
+        state.clear_session();
+        state.search_query = "bat language:go".to_string();
+        state.apply_preset(&preset);
+
+        assert_eq!(state.search_query, "bat language:rust");
+        assert!(state.archived_only);
+        assert_eq!(state.fork_filter, ForkFilter::Only);
+        assert_eq!(state.stale_threshold_days, Some(730));
+    }
+
+    #[test]
+    fn test_empty_preset_matches_everything() {
+        let spec = FilterSpec::from(&FilterPreset::default());
+        assert!(!spec.is_active());
+        assert!(spec.matches(&create_test_repo(1, "repo", "owner"), Utc::now()));
+    }
+
+    #[test]
+    fn test_preset_combines_criteria() {
+        let spec = FilterSpec::from(&FilterPreset {
+            name: "Old archived forks".to_string(),
+            stale_threshold_days: Some(730),
+            archived_only: true,
+            fork_filter: ForkFilter::Only,
+            ..Default::default()
+        });
+        let now = Utc::now();
+        let repo = |archived: bool, fork: bool, pushed_days_ago: i64| Repository {
+            archived,
+            fork,
+            pushed_at: Some(now - chrono::Duration::days(pushed_days_ago)),
+            ..create_test_repo(1, "repo", "owner")
+        };
+
+        assert!(spec.matches(&repo(true, true, 800), now));
+        assert!(!spec.matches(&repo(true, true, 100), now));
+        assert!(!spec.matches(&repo(false, true, 800), now));
+        assert!(!spec.matches(&repo(true, false, 800), now));
+    }
+
+    #[test]
+    fn test_preset_language_and_topic() {
+        let now = Utc::now();
+        let repo = Repository {
+            language: Some("Rust".to_string()),
+            topics: vec!["cli".to_string()],
+            ..create_test_repo(1, "repo", "owner")
+        };
+        let mut preset = FilterPreset {
+            language: Some("rust".to_string()),
+            topic: Some("cli".to_string()),
+            ..Default::default()
+        };
+        assert!(FilterSpec::from(&preset).matches(&repo, now));
+
+        preset.topic = Some("web".to_string());
+        assert!(!FilterSpec::from(&preset).matches(&repo, now));
+    }
+
+    #[test]
+    fn test_apply_preset_matches_like_the_preset() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            search_query: "language:go".to_string(),
+            ..Default::default()
+        };
+        state.repositories[1].fork = true;
+        state.repositories[2].fork = true;
+        state.repositories[2].archived = true;
+        let preset = FilterPreset {
+            name: "Archived forks".to_string(),
+            archived_only: true,
+            fork_filter: ForkFilter::Only,
+            ..Default::default()
+        };
+
+        state.apply_preset(&preset);
+
+        let now = Utc::now();
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        let expected: Vec<u64> = state
+            .repositories
+            .iter()
+            .filter(|r| FilterSpec::from(&preset).matches(r, now))
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, vec![3]);
+        assert_eq!(ids, expected);
+        assert!(state.search_query.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_select_all_filtered_only_selects_visible() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            search_query: "sharkdp".to_string(),
+            ..Default::default()
+        };
+
+        state.toggle_select_all_filtered();
+        assert_eq!(state.selection.count(), 2);
+        assert!(!state.selection.is_selected(1));
+        assert!(state.all_filtered_selected());
+
+        state.toggle_select_all_filtered();
+        assert_eq!(state.selection.count(), 0);
+    }
+
+    #[test]
+    fn test_pinned_repos_listed_first() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            ..Default::default()
+        };
+        state.config.pinned_ids = HashSet::from([3]);
+        assert_eq!(visible_ids(&state), vec![3, 1, 2]);
+
+        state.search_query = "fd".to_string();
+        assert_eq!(visible_ids(&state), vec![2]);
+    }
+
+    #[test]
+    fn test_next_unpinned_index_skips_pinned_rows() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "a", "owner"),
+                create_test_repo(2, "b", "owner"),
+                create_test_repo(3, "c", "owner"),
+            ],
+            ..Default::default()
+        };
+        state.config.pinned_ids = HashSet::from([2, 3]);
+
+        // Pinned rows are listed first, so focus mode starts after them
+        assert_eq!(state.next_unpinned_index(0), Some(2));
+        assert_eq!(state.visible_repositories()[2].id, 1);
+        assert_eq!(state.next_unpinned_index(3), None);
+    }
+
+    #[test]
+    fn test_bulk_selection_skips_pinned() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            ..Default::default()
+        };
+        state.config.pinned_ids = HashSet::from([2]);
+
+        state.toggle_select_all_filtered();
+        assert_eq!(state.get_selected_ids(), vec![1, 3]);
+        assert!(state.all_filtered_selected());
+        state.toggle_select_all_filtered();
+        assert_eq!(state.selection.count(), 0);
+
+        state.selection.toggle(1);
+        state.invert_selection_filtered();
+        assert_eq!(state.get_selected_ids(), vec![3]);
+
+        state.selection.clear();
+        state.toggle_select_all_loaded();
+        assert_eq!(state.get_selected_ids(), vec![1, 3]);
+        assert!(state.all_loaded_selected());
+
+        // Pinned fd is listed first, so rows 0..=1 are fd and ripgrep
+        state.selection.clear();
+        state.select_filtered_range(0, 1);
+        assert_eq!(state.get_selected_ids(), vec![1]);
+
+        // Pinned repos can still be picked one at a time
         state.selection.toggle(2);
+        assert!(state.selection.is_selected(2));
+    }
+
+    #[test]
+    fn test_select_by_regex() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "awesome-rust", "rust-unofficial"),
+                create_test_repo(2, "awesome-go", "avelino"),
+                create_test_repo(3, "not-awesome", "someone"),
+                create_test_repo(4, "awesome-python", "vinta"),
+            ],
+            search_query: "go".to_string(),
+            ..Default::default()
+        };
+        state.config.pinned_ids = HashSet::from([4]);
+
+        assert_eq!(state.count_regex_matches("^awesome-").unwrap(), 2);
+        assert_eq!(state.selection.count(), 0);
+
+        // Filters don't narrow it, and the previous selection is replaced
+        state.selection.toggle(3);
+        assert_eq!(state.select_by_regex("^awesome-").unwrap(), 2);
+        assert_eq!(state.get_selected_ids(), vec![1, 2]);
+
+        assert_eq!(state.select_by_regex("^avelino/").unwrap(), 1);
+        assert_eq!(state.get_selected_ids(), vec![2]);
+    }
+
+    #[test]
+    fn test_select_by_regex_invalid_pattern() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "awesome-rust", "rust-unofficial")],
+            ..Default::default()
+        };
+        state.selection.toggle(1);
+
+        let err = state.select_by_regex("awesome-(").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid regex: unclosed group");
+        assert!(state.selection.is_selected(1));
+    }
+
+    #[test]
+    fn test_toggle_select_all_loaded_ignores_filters() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            search_query: "sharkdp".to_string(),
+            ..Default::default()
+        };
+
+        state.toggle_select_all_loaded();
+        assert_eq!(state.selection.count(), 3);
+        assert!(state.selection.is_selected(1));
+        assert!(state.all_loaded_selected());
+
+        state.toggle_select_all_loaded();
+        assert_eq!(state.selection.count(), 0);
+    }
+
+    #[test]
+    fn test_all_selected_in_scope() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+            ],
+            search_query: "sharkdp".to_string(),
+            ..Default::default()
+        };
+        assert!(!state.all_selected_in(SelectScope::Visible));
+
+        state.toggle_select_all_filtered();
+        assert!(state.all_selected_in(SelectScope::Visible));
+        assert!(!state.all_selected_in(SelectScope::AllLoaded));
+
+        state.toggle_select_all_loaded();
+        assert!(state.all_selected_in(SelectScope::Visible));
+        assert!(state.all_selected_in(SelectScope::AllLoaded));
+    }
+
+    fn create_topic_repo(id: u64, topics: &[&str]) -> Repository {
+        Repository {
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+            ..create_test_repo(id, &format!("repo{}", id), "owner")
+        }
+    }
+
+    #[test]
+    fn test_topic_filter() {
+        let mut state = AppState {
+            repositories: vec![
+                create_topic_repo(1, &["rust", "cli"]),
+                create_topic_repo(2, &["python"]),
+                create_topic_repo(3, &["rust"]),
+            ],
+            ..Default::default()
+        };
+        assert!(!state.is_filtered());
+
+        state.topic_filter = Some("rust".to_string());
+        assert!(state.is_filtered());
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        // Topics must match exactly, not as a substring
+        state.topic_filter = Some("rus".to_string());
+        assert!(state.visible_repositories().is_empty());
+    }
+
+    #[test]
+    fn test_stale_filter() {
+        let now = Utc::now();
+        let mut fresh = create_test_repo(1, "fresh", "owner");
+        fresh.pushed_at = Some(now - chrono::Duration::days(30));
+        let mut old = create_test_repo(2, "old", "owner");
+        old.pushed_at = Some(now - chrono::Duration::days(500));
+        let never_pushed = create_test_repo(3, "never", "owner");
+
+        let mut state = AppState {
+            repositories: vec![fresh, old, never_pushed],
+            stale_threshold_days: Some(365),
+            ..Default::default()
+        };
+        assert!(state.is_filtered());
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        state.stale_threshold_days = Some(730);
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![3]);
+    }
+
+    #[test]
+    fn test_archived_filter() {
+        let mut archived = create_test_repo(2, "old", "owner");
+        archived.archived = true;
+
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "active", "owner"), archived],
+            ..Default::default()
+        };
+        assert_eq!(state.visible_repositories().len(), 2);
+
+        state.archived_only = true;
+        assert!(state.is_filtered());
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_no_description_filter_respected_by_select_all() {
+        let mut described = create_test_repo(1, "described", "owner");
+        described.description = Some("A useful tool".to_string());
+
+        let mut state = AppState {
+            repositories: vec![described, create_test_repo(2, "blank", "owner")],
+            no_description_only: true,
+            ..Default::default()
+        };
+        assert!(state.is_filtered());
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2]);
+
+        state.toggle_select_all_filtered();
+        assert_eq!(state.selection.count(), 1);
+        assert!(state.selection.is_selected(2));
+    }
+
+    #[test]
+    fn test_fork_filter() {
+        let mut fork = create_test_repo(2, "fork", "owner");
+        fork.fork = true;
+
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "original", "owner"), fork],
+            ..Default::default()
+        };
+        assert!(!state.is_filtered());
 
-        let ids = state.get_selected_ids();
-        assert_eq!(ids.len(), 2);
-        assert!(ids.contains(&1));
-        assert!(ids.contains(&2));
+        state.fork_filter = ForkFilter::Only;
+        assert!(state.is_filtered());
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![2]);
+
+        state.fork_filter = ForkFilter::Exclude;
+        let ids: Vec<u64> = state.visible_repositories().iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    fn star_cache(repos: Vec<Repository>, age_hours: i64) -> StarCache {
+        StarCache {
+            fetched_at: Utc::now() - chrono::Duration::hours(age_hours),
+            repositories: repos,
+            complete: true,
+        }
     }
 
     #[test]
-    fn test_remove_repos() {
-        let mut state = AppState::default();
-        state.repositories = vec![
-            create_test_repo(1, "repo1", "owner1"),
-            create_test_repo(2, "repo2", "owner2"),
-            create_test_repo(3, "repo3", "owner3"),
-        ];
+    fn test_session_diff_after_complete_refresh() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            ..Default::default()
+        };
+        // Expired, so only used for the comparison
+        state.restore_cache(star_cache(
+            vec![
+                create_test_repo(1, "kept", "owner"),
+                create_test_repo(2, "gone", "owner"),
+                create_test_repo(3, "unstarred-here", "owner"),
+            ],
+            48,
+        ));
+        state.changed_in_app.insert(3);
+
+        let fresh = vec![create_test_repo(1, "kept", "owner"), create_test_repo(4, "new", "owner")];
+        state.reconcile_refreshed(fresh.clone(), 1, true);
+        assert!(state.session_diff.is_none());
+
+        state.reconcile_refreshed(fresh, 1, false);
+        let diff = state.session_diff.clone().unwrap();
+        assert_eq!(diff.added, HashSet::from([4]));
+        assert_eq!(diff.removed, HashSet::from([2]));
+
+        // Only compared once per session
+        state.session_diff = None;
+        state.check_session_diff();
+        assert!(state.session_diff.is_none());
+    }
+
+    #[test]
+    fn test_incomplete_cache_is_not_diffed() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            ..Default::default()
+        };
+        let mut cache = star_cache(vec![create_test_repo(1, "first-page", "owner")], 1);
+        cache.complete = false;
+        state.restore_cache(cache);
+
+        state.reconcile_refreshed(vec![create_test_repo(2, "other", "owner")], 1, false);
+        assert!(state.session_diff.is_none());
+    }
+
+    #[test]
+    fn test_restore_fresh_cache_skips_loading_screen() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            ..Default::default()
+        };
+        state.restore_cache(star_cache(vec![create_test_repo(1, "cached", "owner")], 1));
+
+        assert_eq!(state.screen, AppScreen::RepositoryList);
+        assert!(state.showing_cached);
+        assert!(state.refresh_pending);
+        assert!(state.last_refreshed.is_some());
+        assert_eq!(state.repositories.len(), 1);
+    }
+
+    #[test]
+    fn test_restore_expired_cache_is_ignored() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            ..Default::default()
+        };
+        state.restore_cache(star_cache(vec![create_test_repo(1, "cached", "owner")], 48));
+
+        assert_eq!(state.screen, AppScreen::Loading);
+        assert!(!state.showing_cached);
+        assert!(state.repositories.is_empty());
+    }
+
+    #[test]
+    fn test_enter_offline_uses_expired_cache() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            loading: true,
+            ..Default::default()
+        };
+        assert!(state.enter_offline(Some(star_cache(vec![create_test_repo(1, "cached", "owner")], 48))));
+
+        assert_eq!(state.screen, AppScreen::RepositoryList);
+        assert!(state.offline);
+        assert!(state.is_read_only());
+        assert!(!state.loading);
+        assert_eq!(state.repositories.len(), 1);
+
+        // Getting through again ends it
+        state.reconcile_refreshed(vec![create_test_repo(1, "cached", "owner")], 1, false);
+        assert!(!state.offline);
+        assert!(!state.is_read_only());
+    }
+
+    #[test]
+    fn test_enter_offline_keeps_restored_list() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            ..Default::default()
+        };
+        state.restore_cache(star_cache(vec![create_test_repo(1, "cached", "owner")], 1));
+
+        assert!(state.enter_offline(None));
+        assert!(state.offline);
+        assert!(!state.showing_cached);
+        assert_eq!(state.repositories.len(), 1);
+    }
+
+    #[test]
+    fn test_enter_offline_without_cache() {
+        let mut state = AppState {
+            screen: AppScreen::Loading,
+            ..Default::default()
+        };
+        assert!(!state.enter_offline(None));
+        assert!(!state.enter_offline(Some(star_cache(vec![], 1))));
+        assert!(!state.offline);
+        assert_eq!(state.screen, AppScreen::Loading);
+    }
 
+    #[test]
+    fn test_reconcile_refreshed_drops_unstarred_selection() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "kept", "owner"), create_test_repo(2, "gone", "owner")],
+            showing_cached: true,
+            ..Default::default()
+        };
         state.selection.toggle(1);
         state.selection.toggle(2);
-        state.selection.toggle(3);
 
-        state.remove_repos(&[1, 3]);
+        state.reconcile_refreshed(vec![create_test_repo(1, "kept", "owner")], 1, false);
 
+        assert!(!state.showing_cached);
+        assert!(state.last_refreshed.is_some());
         assert_eq!(state.repositories.len(), 1);
-        assert_eq!(state.repositories[0].id, 2);
+        assert!(state.selection.is_selected(1));
+        assert!(!state.selection.is_selected(2));
+        assert!(!state.has_more);
+    }
+
+    #[test]
+    fn test_select_filtered_range() {
+        let mut state = AppState {
+            repositories: (1..=6).map(|id| create_test_repo(id, &format!("repo-{}", id), "owner")).collect(),
+            ..Default::default()
+        };
+        state.selection.toggle(6);
+
+        // Works in either direction and keeps existing selections
+        state.select_filtered_range(3, 1);
+        let mut ids: Vec<u64> = state.selection.selected_ids.iter().copied().collect();
+        ids.sort();
+        assert_eq!(ids, vec![2, 3, 4, 6]);
+
+        // Out-of-range ends are clamped to the list
+        state.select_filtered_range(4, 10);
+        assert_eq!(state.selection.count(), 5);
+        assert!(!state.selection.is_selected(1));
+    }
+
+    #[test]
+    fn test_merge_new_repos_skips_duplicates() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "a", "o"), create_test_repo(2, "b", "o")],
+            ..Default::default()
+        };
+
+        state.merge_new_repos(vec![
+            create_test_repo(2, "b", "o"),
+            create_test_repo(3, "c", "o"),
+            create_test_repo(3, "c", "o"),
+        ]);
+
+        let ids: Vec<u64> = state.repositories.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_select_all_with_topic_filter_only_selects_matching() {
+        let mut state = AppState {
+            repositories: vec![
+                create_topic_repo(1, &["rust"]),
+                create_topic_repo(2, &["python"]),
+            ],
+            topic_filter: Some("python".to_string()),
+            ..Default::default()
+        };
+
+        state.toggle_select_all_filtered();
         assert_eq!(state.selection.count(), 1);
         assert!(state.selection.is_selected(2));
     }
 
     #[test]
     fn test_clear_error() {
-        let mut state = AppState::default();
-        state.error = Some("Test error".to_string());
+        let mut state = AppState {
+            error: Some("Test error".to_string()),
+            ..Default::default()
+        };
 
         state.clear_error();
         assert!(state.error.is_none());
@@ -387,6 +3254,141 @@ mod tests {
         assert_eq!(state.error, Some("Something went wrong".to_string()));
     }
 
+    #[test]
+    fn test_requires_typed_confirmation() {
+        assert!(PendingAction::UnstarSelected(500).requires_typed_confirmation(500));
+        assert!(!PendingAction::UnstarSelected(499).requires_typed_confirmation(500));
+        assert!(!PendingAction::Logout.requires_typed_confirmation(0));
+
+        let by_filter = PendingAction::UnstarByFilter(FilterSpec::default(), (0..600).collect());
+        assert!(by_filter.requires_typed_confirmation(500));
+        assert!(by_filter.is_confirmation_text("600"));
+    }
+
+    #[test]
+    fn test_filter_spec_describe() {
+        let archived = FilterSpec {
+            archived_only: true,
+            ..Default::default()
+        };
+        assert_eq!(archived.describe(42), "42 archived repositories");
+
+        let stale_topic = FilterSpec {
+            stale_threshold_days: Some(365),
+            topic: Some("rust".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            stale_topic.describe(1),
+            "1 repository not pushed to in 365 days tagged #rust"
+        );
+    }
+
+    #[test]
+    fn test_is_confirmation_text() {
+        let action = PendingAction::UnstarSelected(523);
+        assert!(action.is_confirmation_text("523"));
+        assert!(action.is_confirmation_text(" UNSTAR "));
+        assert!(!action.is_confirmation_text("52"));
+        assert!(!action.is_confirmation_text("unstar"));
+        assert!(!action.is_confirmation_text(""));
+    }
+
+    #[test]
+    fn test_selected_names_preview() {
+        let mut state = AppState {
+            repositories: (1..=5).map(|id| create_test_repo(id, &format!("repo{}", id), "owner")).collect(),
+            ..Default::default()
+        };
+        for id in [5, 1, 3, 4] {
+            state.selection.toggle(id);
+        }
+
+        let (names, more) = state.selected_names_preview(2);
+        assert_eq!(names, vec!["owner/repo1".to_string(), "owner/repo3".to_string()]);
+        assert_eq!(more, 2);
+
+        let (names, more) = state.selected_names_preview(CONFIRM_LIST_LIMIT);
+        assert_eq!(names.len(), 4);
+        assert_eq!(more, 0);
+    }
+
+    #[test]
+    fn test_get_selected_urls() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            ..Default::default()
+        };
+        assert!(state.get_selected_urls().is_empty());
+
+        state.selection.toggle(3);
+        state.selection.toggle(1);
+        assert_eq!(
+            state.get_selected_urls(),
+            vec![
+                "https://github.com/BurntSushi/ripgrep".to_string(),
+                "https://github.com/sharkdp/bat".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selected_as_markdown() {
+        let mut state = AppState {
+            repositories: vec![
+                create_test_repo(1, "ripgrep", "BurntSushi"),
+                create_test_repo(2, "fd", "sharkdp"),
+                create_test_repo(3, "bat", "sharkdp"),
+            ],
+            ..Default::default()
+        };
+        state.repositories[0].description = Some("Recursively search directories for a regex".to_string());
+        state.repositories[2].description = Some("  ".to_string());
+        assert_eq!(state.selected_as_markdown(), "");
+
+        state.selection.toggle(3);
+        state.selection.toggle(1);
+        assert_eq!(
+            state.selected_as_markdown(),
+            "- [BurntSushi/ripgrep](https://github.com/BurntSushi/ripgrep) — Recursively search directories for a regex\n\
+             - [sharkdp/bat](https://github.com/sharkdp/bat)"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_report() {
+        let mut diagnostics = Diagnostics {
+            api_url: "https://api.github.com".to_string(),
+            config_path: PathBuf::from("/home/me/.config/github-starcleaner/config.toml"),
+            cache_dir: PathBuf::from("/home/me/.cache/github-starcleaner"),
+            ..Default::default()
+        };
+        assert_eq!(diagnostics.rows()[0], ("Signed in as", Ok("Checking...".to_string())));
+
+        diagnostics.username = Some(Ok("octocat".to_string()));
+        diagnostics.scopes = Some(Ok(vec!["read:user".to_string()]));
+        diagnostics.rate_limit = Some(Err("Request timed out".to_string()));
+        assert_eq!(
+            diagnostics.report(),
+            "Signed in as: @octocat\n\
+             Token scopes: read:user (missing repo or public_repo, needed to unstar)\n\
+             Rate limit: failed: Request timed out\n\
+             API base URL: https://api.github.com\n\
+             Proxy: None\n\
+             Config file: /home/me/.config/github-starcleaner/config.toml\n\
+             Cache directory: /home/me/.cache/github-starcleaner"
+        );
+
+        diagnostics.scopes = Some(Ok(Vec::new()));
+        assert_eq!(diagnostics.rows()[1].1, Ok("None reported (fine-grained token)".to_string()));
+        diagnostics.scopes = Some(Ok(vec!["public_repo".to_string(), "user".to_string()]));
+        assert_eq!(diagnostics.rows()[1].1, Ok("public_repo, user".to_string()));
+    }
+
     #[test]
     fn test_pending_action_variants() {
         let single = PendingAction::UnstarSingle(1, "owner".to_string(), "repo".to_string(), "owner/repo".to_string());
@@ -414,4 +3416,349 @@ mod tests {
             _ => panic!("Expected Logout"),
         }
     }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1423), "1,423");
+        assert_eq!(format_count(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_repo_stats_empty() {
+        let stats = RepoStats::compute(&[], Utc::now(), STATS_TOP_LANGUAGES);
+        assert_eq!(stats, RepoStats::default());
+    }
+
+    #[test]
+    fn test_repo_stats_counts() {
+        let mut repos: Vec<Repository> = (0..4).map(|i| create_test_repo(i, "r", "o")).collect();
+        repos[0].stargazers_count = 1000;
+        repos[1].stargazers_count = 500;
+        repos[2].stargazers_count = 25;
+        repos[0].archived = true;
+        repos[1].fork = true;
+        repos[2].fork = true;
+
+        let stats = RepoStats::compute(&repos, Utc::now(), STATS_TOP_LANGUAGES);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.total_stars, 1525);
+        assert_eq!(stats.total_stars_label(), "1,525");
+        assert_eq!(stats.archived, 1);
+        assert_eq!(stats.forks, 2);
+    }
+
+    #[test]
+    fn test_repo_stats_top_languages() {
+        let languages = [Some("Rust"), Some("Go"), Some("Rust"), None, Some("C"), Some("Go"), Some("Rust")];
+        let repos: Vec<Repository> = languages
+            .iter()
+            .enumerate()
+            .map(|(i, language)| {
+                let mut repo = create_test_repo(i as u64, "r", "o");
+                repo.language = language.map(str::to_string);
+                repo
+            })
+            .collect();
+
+        let stats = RepoStats::compute(&repos, Utc::now(), 2);
+        assert_eq!(
+            stats.top_languages,
+            vec![("Rust".to_string(), 3), ("Go".to_string(), 2)]
+        );
+
+        // Ties are ordered by name
+        let stats = RepoStats::compute(&repos[3..5], Utc::now(), 5);
+        assert_eq!(stats.top_languages, vec![("C".to_string(), 1)]);
+        let stats = RepoStats::compute(&repos[4..6], Utc::now(), 5);
+        assert_eq!(
+            stats.top_languages,
+            vec![("C".to_string(), 1), ("Go".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_repo_stats_median_pushed_age() {
+        let now = Utc::now();
+        let pushed = |i: u64, days: Option<i64>| {
+            let mut repo = create_test_repo(i, "r", "o");
+            repo.pushed_at = days.map(|d| now - chrono::Duration::days(d));
+            repo
+        };
+
+        let odd = vec![pushed(0, Some(30)), pushed(1, Some(1)), pushed(2, Some(400)), pushed(3, None)];
+        assert_eq!(RepoStats::compute(&odd, now, 5).median_pushed_age_days, Some(30));
+
+        let even = vec![pushed(0, Some(10)), pushed(1, Some(20)), pushed(2, Some(90)), pushed(3, Some(5))];
+        assert_eq!(RepoStats::compute(&even, now, 5).median_pushed_age_days, Some(15));
+
+        let never = vec![pushed(0, None)];
+        assert_eq!(RepoStats::compute(&never, now, 5).median_pushed_age_days, None);
+    }
+
+    #[test]
+    fn test_compute_stats_ignores_filters() {
+        let mut state = AppState {
+            repositories: vec![create_test_repo(1, "alpha", "o"), create_test_repo(2, "beta", "o")],
+            ..Default::default()
+        };
+        state.search_query = "alpha".to_string();
+
+        assert_eq!(state.visible_repositories().len(), 1);
+        assert_eq!(state.compute_stats().total, 2);
+    }
+
+    #[test]
+    fn test_load_progress_label() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            ..Default::default()
+        };
+        assert_eq!(state.load_progress_label(), "Loaded 3");
+
+        state.total_starred = Some(1423);
+        assert_eq!(state.load_progress_label(), "Loaded 3 / 1,423");
+    }
+
+    #[test]
+    fn test_star_age_label() {
+        let mut repo = create_test_repo(1, "repo1", "owner");
+        repo.starred_order = 0;
+        let mut state = AppState {
+            sort_field: SortField::Starred,
+            sort_direction: SortDirection::Desc,
+            ..Default::default()
+        };
+        // Unknown total hides the badge
+        assert_eq!(state.star_age_label(&repo), None);
+
+        state.total_starred = Some(1500);
+        assert_eq!(state.star_age_label(&repo).as_deref(), Some("#1,500 of 1,500"));
+        repo.starred_order = 266;
+        assert_eq!(state.star_age_label(&repo).as_deref(), Some("#1,234 of 1,500"));
+
+        state.sort_direction = SortDirection::Asc;
+        assert_eq!(state.star_age_label(&repo).as_deref(), Some("#267 of 1,500"));
+
+        // Positions past a stale total are hidden rather than shown as nonsense
+        repo.starred_order = 1500;
+        assert_eq!(state.star_age_label(&repo), None);
+
+        state.sort_field = SortField::Pushed;
+        repo.starred_order = 0;
+        assert_eq!(state.star_age_label(&repo), None);
+    }
+
+    #[test]
+    fn test_total_starred_tracks_unstar_and_undo() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            total_starred: Some(10),
+            ..Default::default()
+        };
+
+        state.remove_repos(&[1, 2]);
+        assert_eq!(state.total_starred, Some(8));
+
+        state.undo_last_unstar();
+        assert_eq!(state.total_starred, Some(10));
+    }
+
+    #[test]
+    fn test_session_unstar_count() {
+        let mut state = AppState {
+            repositories: create_ordered_repos(3),
+            ..Default::default()
+        };
+        assert_eq!(state.session_unstar_label(), None);
+
+        state.remove_repos(&[1, 2]);
+        state.begin_removal(&[3]);
+        assert_eq!(state.session_unstar_count, 2);
+        // Only unstars that went through count
+        state.rollback_removal(3);
+        assert_eq!(state.session_unstar_label().as_deref(), Some("Unstarred 2 this session"));
+
+        state.undo_last_unstar();
+        assert_eq!(state.session_unstar_count, 0);
+
+        state.remove_repos(&[3]);
+        state.clear_session();
+        assert_eq!(state.session_unstar_count, 0);
+    }
+
+    #[test]
+    fn test_claim_activity_fetches() {
+        let mut state = AppState {
+            repositories: (1..=5).map(|id| create_test_repo(id, &format!("repo{}", id), "owner")).collect(),
+            ..Default::default()
+        };
+        // Signed out
+        assert!(state.claim_activity_fetches(0..3).is_empty());
+
+        state.github_service = GitHubService::new("token", None).ok();
+        let claimed = state.claim_activity_fetches(1..3);
+        assert_eq!(
+            claimed,
+            vec![(2, "owner".to_string(), "repo2".to_string()), (3, "owner".to_string(), "repo3".to_string())]
+        );
+        // Already asked for, whether or not the answer is in
+        state.repo_activity.insert(2, Some(RepoActivity::default()));
+        let ids: Vec<u64> = state.claim_activity_fetches(0..4).into_iter().map(|(id, _, _)| id).collect();
+        assert_eq!(ids, vec![1, 4]);
+
+        state.offline = true;
+        assert!(state.claim_activity_fetches(0..5).is_empty());
+        state.offline = false;
+
+        // Scrolled away before its turn came
+        state.release_activity_fetch(4);
+        assert!(!state.repo_activity.contains_key(&4));
+        assert!(state.is_in_rows(5, 3..5));
+        assert!(!state.is_in_rows(5, 0..3));
+    }
+
+    #[test]
+    fn test_claim_activity_fetches_keeps_a_rate_limit_reserve() {
+        let mut state = AppState {
+            repositories: (1..=5).map(|id| create_test_repo(id, &format!("repo{}", id), "owner")).collect(),
+            github_service: GitHubService::new("token", None).ok(),
+            ..Default::default()
+        };
+        let reset_at = Utc::now() + chrono::Duration::minutes(30);
+        // Two requests each for two repos above the reserve
+        state.rate_limit = Some(RateLimit { remaining: ACTIVITY_RATE_LIMIT_RESERVE + 5, limit: 5000, reset_at });
+        assert_eq!(state.claim_activity_fetches(0..5).len(), 2);
+
+        state.rate_limit = Some(RateLimit { remaining: ACTIVITY_RATE_LIMIT_RESERVE, limit: 5000, reset_at });
+        assert!(state.claim_activity_fetches(0..5).is_empty());
+    }
+
+    #[test]
+    fn test_should_auto_logout() {
+        let now = Utc::now();
+        let mut state = AppState::default();
+        state.config.github.personal_access_token = Some("token".to_string());
+        let idle = now - chrono::Duration::minutes(30);
+        // Off unless configured
+        assert!(!state.should_auto_logout(idle, now));
+
+        state.config.auto_logout_minutes = Some(30);
+        assert!(state.should_auto_logout(idle, now));
+        assert!(!state.should_auto_logout(now - chrono::Duration::minutes(29), now));
+
+        // Nothing to log out of
+        state.config.github.personal_access_token = None;
+        assert!(!state.should_auto_logout(idle, now));
+    }
+
+    #[test]
+    fn test_should_reload_on_focus() {
+        let now = Utc::now();
+        let mut state = AppState {
+            screen: AppScreen::RepositoryList,
+            github_service: GitHubService::new("token", None).ok(),
+            last_refreshed: Some(now - chrono::Duration::minutes(11)),
+            ..Default::default()
+        };
+        assert!(!state.should_reload_on_focus(now));
+
+        state.config.reload_on_focus = true;
+        assert!(state.should_reload_on_focus(now));
+
+        // Refreshed too recently
+        state.last_refreshed = Some(now - chrono::Duration::minutes(9));
+        assert!(!state.should_reload_on_focus(now));
+        state.config.reload_on_focus_after_mins = 5;
+        assert!(state.should_reload_on_focus(now));
+
+        // A reload would throw away the selection
+        state.selection.toggle(1);
+        assert!(!state.should_reload_on_focus(now));
+        state.selection.clear();
+
+        state.unstar_progress = Some(UnstarProgress::new(3));
+        assert!(!state.should_reload_on_focus(now));
+        state.unstar_progress = None;
+
+        state.loading_more = true;
+        assert!(!state.should_reload_on_focus(now));
+    }
+
+    #[test]
+    fn test_load_failure_keeps_token_for_retry() {
+        let mut state = AppState::from_config(AppConfig {
+            github: GitHubConfig {
+                personal_access_token: Some("ghp_test".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        state.loading = true;
+
+        state.fail_load("Failed to load: connection reset".to_string());
+        assert_eq!(state.screen, AppScreen::LoadFailed);
+        assert!(!state.loading);
+        assert!(state.config.has_token());
+
+        state.retry_load();
+        assert_eq!(state.screen, AppScreen::Loading);
+        assert!(state.error.is_none());
+    }
+
+    #[test]
+    fn test_should_auto_load() {
+        let mut state = AppState {
+            screen: AppScreen::RepositoryList,
+            has_more: true,
+            ..Default::default()
+        };
+        assert!(!state.should_auto_load());
+
+        state.config.load_all = true;
+        assert!(state.should_auto_load());
+
+        state.loading_more = true;
+        assert!(!state.should_auto_load());
+        state.loading_more = false;
+
+        state.load_all_paused = true;
+        assert!(!state.should_auto_load());
+        state.load_all_paused = false;
+
+        state.stop_loading_all();
+        assert!(!state.should_auto_load());
+        assert!(!state.is_auto_loading());
+        assert!(state.has_more);
+        state.begin_reload();
+        assert!(!state.is_load_all_cancelled());
+        state.loading = false;
+        assert!(state.should_auto_load());
+
+        let reset_at = Utc::now() + chrono::Duration::minutes(30);
+        state.rate_limit = Some(RateLimit { remaining: 0, limit: 5000, reset_at });
+        assert!(!state.should_auto_load());
+        state.rate_limit = None;
+
+        state.offline = true;
+        assert!(!state.should_auto_load());
+        state.offline = false;
+
+        state.has_more = false;
+        assert!(!state.should_auto_load());
+    }
+
+    #[test]
+    fn test_is_rate_limited() {
+        let mut state = AppState::default();
+        assert!(!state.is_rate_limited());
+
+        let reset_at = chrono::Utc::now() + chrono::Duration::minutes(30);
+        state.rate_limit = Some(RateLimit { remaining: 10, limit: 5000, reset_at });
+        assert!(!state.is_rate_limited());
+
+        state.rate_limit = Some(RateLimit { remaining: 0, limit: 5000, reset_at });
+        assert!(state.is_rate_limited());
+    }
 }
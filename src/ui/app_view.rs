@@ -1,43 +1,194 @@
-use crate::services::GitHubService;
+use crate::models::{WindowState, MAX_PER_PAGE};
+use crate::services::{is_token_expired_error, is_transient_error, proxy_error_message, CacheService, GitHubService};
 use crate::state::{AppScreen, AppState, SortDirection, SortField};
-use crate::ui::{RepositoryListView, SetupView};
+use crate::ui::{
+    open_diagnostics, render_diagnostics, render_error_banner, render_warning_banner, spinner, RepositoryListView,
+    SetupView, Theme,
+};
+use chrono::{DateTime, Utc};
+use gpui::prelude::FluentBuilder;
 use gpui::*;
+use std::time::Duration;
+
+/// Re-check the token when the window comes back after being away this long
+const RESUME_REVALIDATE_AFTER: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Save the window bounds once moving or resizing has paused this long
+const WINDOW_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// How often to check whether an idle session should be logged out
+const AUTO_LOGOUT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The window's restore bounds, i.e. its windowed size even while maximized
+fn window_state(window: &Window) -> WindowState {
+    let bounds = window.window_bounds().get_bounds();
+    WindowState {
+        x: bounds.origin.x.into(),
+        y: bounds.origin.y.into(),
+        width: bounds.size.width.into(),
+        height: bounds.size.height.into(),
+    }
+}
 
 pub struct AppView {
     setup_view: Entity<SetupView>,
     repo_list_view: Entity<RepositoryListView>,
+    /// Wall-clock time the window lost focus; unlike `Instant` this keeps
+    /// counting while the machine sleeps
+    inactive_since: Option<DateTime<Utc>>,
+    /// Debounced write of the window bounds; replacing it cancels the last one
+    pending_window_save: Option<Task<()>>,
+    /// Wall-clock time of the last click or key press, for `auto_logout_minutes`
+    last_interaction: DateTime<Utc>,
 }
 
 impl AppView {
-    pub fn new(cx: &mut Context<Self>) -> Self {
-        let setup_view = cx.new(|cx| SetupView::new(cx));
-        let repo_list_view = cx.new(|cx| RepositoryListView::new(cx));
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let setup_view = cx.new(SetupView::new);
+        let repo_list_view = cx.new(RepositoryListView::new);
 
-        // If we have a token, trigger loading
-        let state = cx.global::<AppState>();
-        if state.screen == AppScreen::Loading {
+        // Re-render on state changes so the loading screen shows progress
+        cx.observe_global::<AppState>(|_this, cx| cx.notify()).detach();
+
+        cx.observe_window_activation(window, |this, window, cx| {
+            if !window.is_window_active() {
+                this.inactive_since = Some(Utc::now());
+                return;
+            }
+            let resumed = this
+                .inactive_since
+                .take()
+                .is_some_and(|since| Utc::now() - since >= RESUME_REVALIDATE_AFTER);
+            if resumed {
+                Self::revalidate_on_resume(cx);
+            }
+            if cx.global::<AppState>().should_reload_on_focus(Utc::now()) {
+                this.repo_list_view.update(cx, |view, cx| view.reload_repos(cx));
+            }
+        })
+        .detach();
+
+        cx.observe_window_bounds(window, |this, window, cx| {
+            this.schedule_window_save(window_state(window), cx);
+        })
+        .detach();
+        // Closing can beat the debounced saves, so save right away too
+        window.on_window_should_close(cx, |window, cx| {
+            let cache_write = cx.update_global::<AppState, _>(|state, _cx| {
+                state.save_window_state(window_state(window));
+                state.take_cache_write()
+            });
+            if let Some(write) = cache_write {
+                write();
+            }
+            true
+        });
+
+        // If we have a token, trigger loading. A cached list is refreshed
+        // from `render`.
+        if cx.global::<AppState>().screen == AppScreen::Loading {
             Self::trigger_load_repos(cx);
         }
+        if cx.global::<AppState>().config.auto_logout_minutes.is_some() {
+            Self::watch_for_inactivity(cx);
+        }
 
         Self {
             setup_view,
             repo_list_view,
+            inactive_since: None,
+            pending_window_save: None,
+            last_interaction: Utc::now(),
+        }
+    }
+
+    /// Log out once the session has been idle for `auto_logout_minutes`,
+    /// checking periodically for as long as the view is open
+    fn watch_for_inactivity(cx: &mut Context<Self>) {
+        cx.spawn(async |view, cx| loop {
+            cx.background_executor().timer(AUTO_LOGOUT_CHECK_INTERVAL).await;
+            let updated = view.update(cx, |this, cx| {
+                if cx.global::<AppState>().should_auto_logout(this.last_interaction, Utc::now()) {
+                    tracing::info!("Logging out after inactivity");
+                    cx.update_global::<AppState, _>(|state, _cx| state.auto_logout());
+                }
+            });
+            if updated.is_err() {
+                break;
+            }
+        })
+        .detach();
+    }
+
+    /// Save the window bounds after a drag or resize settles
+    fn schedule_window_save(&mut self, window: WindowState, cx: &mut Context<Self>) {
+        self.pending_window_save = Some(cx.spawn(async move |_view, cx| {
+            cx.background_executor().timer(WINDOW_SAVE_DELAY).await;
+            cx.update(|cx| {
+                cx.update_global::<AppState, _>(|state, _cx| state.save_window_state(window));
+            })
+            .ok();
+        }));
+    }
+
+    /// Check the token is still good after a long absence, e.g. waking from
+    /// sleep. Only a rejected token logs out; network errors are just logged.
+    fn revalidate_on_resume(cx: &mut Context<Self>) {
+        let state = cx.global::<AppState>();
+        if state.screen != AppScreen::RepositoryList {
+            return;
         }
+        let Some(service) = state.github_service.clone() else {
+            return;
+        };
+        let epoch = state.epoch;
+
+        cx.spawn(async move |_view, cx| {
+            let result = service.revalidate().await;
+            cx.update(|cx| {
+                let state = cx.global_mut::<AppState>();
+                if !state.is_current_epoch(epoch) {
+                    return;
+                }
+                match result {
+                    Ok(_) => {}
+                    Err(e) if is_token_expired_error(&e) => state.handle_api_error(e, "Session check failed"),
+                    Err(e) => tracing::warn!("Token check after resume failed: {}", e),
+                }
+            })
+            .ok();
+        })
+        .detach();
     }
 
     fn trigger_load_repos(cx: &mut Context<Self>) {
         cx.spawn(async |_view, cx| {
             // Get token and sort options
-            let (token, sort_field, sort_direction): (Option<String>, SortField, SortDirection) = cx
+            let (epoch, token, proxy, per_page, cached_count, sort, graphql) = cx
                 .update(|cx| {
                     let state = cx.global::<AppState>();
                     (
+                        state.epoch,
                         state.config.github.personal_access_token.clone(),
-                        state.sort_field,
-                        state.sort_direction,
+                        state.config.github.proxy.clone(),
+                        state.config.per_page,
+                        state.showing_cached.then_some(state.repositories.len()),
+                        state.api_sort_params(),
+                        state.config.graphql_stars,
                     )
                 })
-                .unwrap_or((None, SortField::default(), SortDirection::default()));
+                .unwrap_or((
+                    0,
+                    None,
+                    None,
+                    MAX_PER_PAGE,
+                    None,
+                    (SortField::default().api_value(), SortDirection::default().api_value()),
+                    false,
+                ));
+            // Refresh as many pages as the cached list covered, so it doesn't shrink
+            let pages_to_load = cached_count
+                .map_or(1, |count| count.div_ceil(per_page.into()).max(1)) as u32;
 
             let Some(token) = token else {
                 cx.update(|cx| {
@@ -52,30 +203,104 @@ impl AppView {
 
             // Create service and validate, then load first page
             let result = async {
-                let service = GitHubService::new(&token)?;
-                let (username, _) = service.validate_token().await?;
-                let (repos, has_more) = service
-                    .fetch_starred_repos_page(1, 100, sort_field.api_value(), sort_direction.api_value())
-                    .await?;
-                Ok::<_, anyhow::Error>((service, username, repos, has_more))
+                let service = GitHubService::new(&token, proxy.as_deref())?;
+                // Retries transient failures, e.g. right after waking from sleep
+                let username = service.revalidate().await?;
+
+                // Show the total on the loading screen while the first page loads
+                let total_starred = match service.get_starred_count().await {
+                    Ok(count) => Some(count),
+                    Err(e) => {
+                        tracing::warn!("Failed to fetch starred count: {}", e);
+                        None
+                    }
+                };
+                cx.update(|cx| {
+                    let state = cx.global_mut::<AppState>();
+                    if state.is_current_epoch(epoch) {
+                        state.total_starred = total_starred;
+                    }
+                })
+                .ok();
+
+                let mut repos = Vec::new();
+                let mut page = 0;
+                let mut has_more = true;
+                let mut cursor = None;
+                while has_more && page < pages_to_load {
+                    page += 1;
+                    let starred = service
+                        .fetch_starred_page(page, per_page, sort, cursor.as_deref(), graphql)
+                        .await?;
+                    repos.extend(starred.repos);
+                    has_more = starred.has_more;
+                    cursor = starred.cursor;
+                }
+                let rate_limit = service.get_rate_limit().await.ok();
+                // Lists are optional; without them the "Move to list" menu is hidden
+                let lists = service.fetch_lists().await.unwrap_or_else(|e| {
+                    tracing::warn!("Failed to fetch star lists: {}", e);
+                    Vec::new()
+                });
+                Ok::<_, anyhow::Error>((service, username, repos, page, has_more, cursor, rate_limit, lists))
             }
             .await;
 
             cx.update(|cx| {
-                cx.update_global::<AppState, _>(|state, _cx| match result {
-                    Ok((service, username, repos, has_more)) => {
-                        state.github_service = Some(service);
-                        state.username = Some(username);
-                        state.repositories = repos;
-                        state.loading = false;
-                        state.current_page = 1;
-                        state.has_more = has_more;
-                        state.screen = AppScreen::RepositoryList;
+                cx.update_global::<AppState, _>(|state, _cx| {
+                    // Logged out while loading
+                    if !state.is_current_epoch(epoch) {
+                        return;
                     }
-                    Err(e) => {
-                        state.error = Some(format!("Failed to load: {}", e));
-                        state.screen = AppScreen::Setup;
-                        state.loading = false;
+                    match result {
+                        Ok((service, username, repos, pages_loaded, has_more, cursor, rate_limit, lists)) => {
+                            state.github_service = Some(service);
+                            state.rate_limit = rate_limit;
+                            state.lists = lists;
+                            state.username = Some(username);
+                            state.loading = false;
+                            state.reconcile_refreshed(repos, pages_loaded, has_more);
+                            state.starred_cursor = cursor;
+                            state.queue_cache_save();
+                            // A zero count alongside loaded repos means the count was wrong
+                            if state.total_starred == Some(0) && !state.repositories.is_empty() {
+                                state.total_starred = None;
+                            }
+                            state.screen = AppScreen::RepositoryList;
+                        }
+                        // GitHub couldn't be reached; browse the cache read-only if there is one
+                        Err(e) if is_transient_error(&e) => {
+                            let cache = if state.showing_cached {
+                                None
+                            } else {
+                                CacheService::load_repos(state.config.active_profile_name()).unwrap_or_else(|e| {
+                                    tracing::warn!("Failed to load star cache: {}", e);
+                                    None
+                                })
+                            };
+                            if state.enter_offline(cache) {
+                                tracing::warn!("Offline, showing cached stars: {}", e);
+                            } else {
+                                state.fail_load(
+                                    proxy_error_message(&e).unwrap_or_else(|| format!("Failed to load: {}", e)),
+                                );
+                            }
+                        }
+                        Err(e) if state.showing_cached && !is_token_expired_error(&e) => {
+                            // Keep the session and the cached list; refreshing retries
+                            state.github_service = GitHubService::new(&token, proxy.as_deref()).ok();
+                            state.loading = false;
+                            state.error = Some(
+                                proxy_error_message(&e)
+                                    .unwrap_or_else(|| format!("Couldn't refresh, showing the cached list: {}", e)),
+                            );
+                        }
+                        // GitHub rejected the token, the only case that ends the session
+                        Err(e) if is_token_expired_error(&e) => state.handle_api_error(e, "Failed to load"),
+                        // Likely a network blip; keep the token and offer a retry
+                        Err(e) => state.fail_load(
+                            proxy_error_message(&e).unwrap_or_else(|| format!("Failed to load: {}", e)),
+                        ),
                     }
                 });
             })
@@ -84,30 +309,110 @@ impl AppView {
         .detach();
     }
 
-    fn render_loading(&self) -> impl IntoElement {
+    fn render_loading(&self, progress: String, theme: Theme) -> impl IntoElement {
         div()
             .size_full()
             .flex()
             .items_center()
             .justify_center()
-            .bg(rgb(0x1e1e2e))
+            .bg(rgb(theme.base))
             .child(
                 div()
                     .flex()
                     .flex_col()
                     .gap_4()
                     .items_center()
+                    .child(spinner("loading-screen-spinner", theme.blue))
                     .child(
                         div()
                             .text_lg()
-                            .text_color(rgb(0xcdd6f4))
+                            .text_color(rgb(theme.text))
                             .child("Loading your starred repositories..."),
                     )
                     .child(
                         div()
                             .text_sm()
-                            .text_color(rgb(0x6c7086))
+                            .text_color(rgb(theme.overlay0))
                             .child("This may take a moment if you have many stars."),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .child(progress),
+                    ),
+            )
+    }
+
+    fn render_load_failed(&self, theme: Theme, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(rgb(theme.base))
+            .child(
+                div()
+                    .w(px(320.))
+                    .flex()
+                    .flex_col()
+                    .gap_4()
+                    .items_center()
+                    .child(
+                        div()
+                            .text_lg()
+                            .text_color(rgb(theme.text))
+                            .child("Couldn't load your starred repositories"),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(theme.overlay0))
+                            .child("Your token is still saved. Check your connection and try again."),
+                    )
+                    .child(
+                        div()
+                            .id("retry-load-btn")
+                            .w_full()
+                            .h(px(40.))
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .bg(rgb(theme.blue))
+                            .text_color(rgb(theme.base))
+                            .font_weight(FontWeight::MEDIUM)
+                            .hover(|style| style.bg(rgb(theme.sapphire)))
+                            .child("Retry")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| state.retry_load());
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("use-different-token-link")
+                            .text_sm()
+                            .text_color(rgb(theme.blue))
+                            .cursor_pointer()
+                            .hover(|style| style.underline())
+                            .child("Use a different token")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.error = None;
+                                    state.screen = AppScreen::Setup;
+                                });
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("load-failed-diagnostics-link")
+                            .text_sm()
+                            .text_color(rgb(theme.blue))
+                            .cursor_pointer()
+                            .hover(|style| style.underline())
+                            .child("Diagnostics")
+                            .on_click(cx.listener(|_this, _event, _window, cx| open_diagnostics(cx))),
                     ),
             )
     }
@@ -125,11 +430,44 @@ impl Render for AppView {
             });
             Self::trigger_load_repos(cx);
         }
-
-        match screen {
-            AppScreen::Setup => div().size_full().child(self.setup_view.clone()).into_any_element(),
-            AppScreen::Loading => self.render_loading().into_any_element(),
-            AppScreen::RepositoryList => div().size_full().child(self.repo_list_view.clone()).into_any_element(),
+        // Refresh a list restored from the cache, at startup or on a profile switch
+        if cx.global::<AppState>().refresh_pending {
+            cx.update_global::<AppState, _>(|state, _cx| {
+                state.refresh_pending = false;
+            });
+            Self::trigger_load_repos(cx);
         }
+
+        let state = cx.global::<AppState>();
+        let (error, warning, theme) = (state.error.clone(), state.warning.clone(), Theme::for_mode(state.config.theme));
+        let content = match &state.diagnostics {
+            // Diagnostics cover whichever screen they were opened from
+            Some(diagnostics) => render_diagnostics(diagnostics, theme).into_any_element(),
+            None => match screen {
+                AppScreen::Setup => div().size_full().child(self.setup_view.clone()).into_any_element(),
+                AppScreen::Loading => {
+                    let progress = state.load_progress_label();
+                    self.render_loading(progress, theme).into_any_element()
+                }
+                AppScreen::LoadFailed => self.render_load_failed(theme, cx).into_any_element(),
+                AppScreen::RepositoryList => div().size_full().child(self.repo_list_view.clone()).into_any_element(),
+            },
+        };
+
+        // Errors and warnings from any screen show above it until dismissed
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            // Seen before any child handles them, so every click and key counts
+            .capture_any_mouse_down(cx.listener(|this, _event, _window, _cx| {
+                this.last_interaction = Utc::now();
+            }))
+            .capture_key_down(cx.listener(|this, _event, _window, _cx| {
+                this.last_interaction = Utc::now();
+            }))
+            .when_some(error, |this, error| this.child(render_error_banner(error, theme)))
+            .when_some(warning, |this, warning| this.child(render_warning_banner(warning, theme)))
+            .child(div().flex_1().min_h_0().child(content))
     }
 }
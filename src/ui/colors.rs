@@ -1,17 +1,119 @@
-/// Catppuccin Mocha color palette constants
+use crate::models::ThemeMode;
+use crate::state::AppState;
+use gpui::App;
+
+/// Catppuccin Mocha color palette constants, each documented with the hex
+/// literal it replaces. Views draw through `Theme` rather than raw hex.
 /// See: https://github.com/catppuccin/catppuccin
 pub mod catppuccin {
+    /// `0x1e1e2e` Base: window and input backgrounds
     pub const BASE: u32 = 0x1e1e2e;
+    /// `0x181825` Mantle: header, toolbar and footer bars
     pub const MANTLE: u32 = 0x181825;
+    /// `0x313244` Surface 0: rows, cards and borders
     pub const SURFACE0: u32 = 0x313244;
+    /// `0x45475a` Surface 1: secondary buttons and hovered rows
     pub const SURFACE1: u32 = 0x45475a;
+    /// `0x585b70` Surface 2: hovered secondary buttons
     pub const SURFACE2: u32 = 0x585b70;
+    /// `0x6c7086` Overlay 0: hints, placeholders and muted metadata
     pub const OVERLAY0: u32 = 0x6c7086;
+    /// `0xa6adc8` Subtext 0: secondary text and labels
     pub const SUBTEXT0: u32 = 0xa6adc8;
+    /// `0xcdd6f4` Text: primary text
     pub const TEXT: u32 = 0xcdd6f4;
+    /// `0x89b4fa` Blue: primary buttons, links and selection
     pub const BLUE: u32 = 0x89b4fa;
+    /// `0x74c7ec` Sapphire: hovered primary buttons and accents
     pub const SAPPHIRE: u32 = 0x74c7ec;
+    /// `0xf38ba8` Red: errors and unstar actions
     pub const RED: u32 = 0xf38ba8;
+    /// `0xf9e2af` Yellow: stars and warnings
+    pub const YELLOW: u32 = 0xf9e2af;
 }
 
 pub use catppuccin::*;
+
+/// Named colors the views draw with, so the palette can be swapped at runtime
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub base: u32,
+    pub mantle: u32,
+    pub surface0: u32,
+    pub surface1: u32,
+    pub surface2: u32,
+    pub overlay0: u32,
+    pub subtext0: u32,
+    pub text: u32,
+    pub blue: u32,
+    pub sapphire: u32,
+    pub red: u32,
+    pub yellow: u32,
+}
+
+impl Theme {
+    /// Catppuccin Mocha (dark)
+    pub fn mocha() -> Self {
+        Self {
+            base: catppuccin::BASE,
+            mantle: catppuccin::MANTLE,
+            surface0: catppuccin::SURFACE0,
+            surface1: catppuccin::SURFACE1,
+            surface2: catppuccin::SURFACE2,
+            overlay0: catppuccin::OVERLAY0,
+            subtext0: catppuccin::SUBTEXT0,
+            text: catppuccin::TEXT,
+            blue: catppuccin::BLUE,
+            sapphire: catppuccin::SAPPHIRE,
+            red: catppuccin::RED,
+            yellow: catppuccin::YELLOW,
+        }
+    }
+
+    /// Catppuccin Latte (light)
+    pub fn latte() -> Self {
+        Self {
+            base: 0xeff1f5,
+            mantle: 0xe6e9ef,
+            surface0: 0xccd0da,
+            surface1: 0xbcc0cc,
+            surface2: 0xacb0be,
+            overlay0: 0x9ca0b0,
+            subtext0: 0x6c6f85,
+            text: 0x4c4f69,
+            blue: 0x1e66f5,
+            sapphire: 0x209fb5,
+            red: 0xd20f39,
+            yellow: 0xdf8e1d,
+        }
+    }
+
+    pub fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Dark => Self::mocha(),
+            ThemeMode::Light => Self::latte(),
+        }
+    }
+
+    /// Palette for the theme picked in the settings
+    pub fn current(cx: &App) -> Self {
+        Self::for_mode(cx.global::<AppState>().config.theme)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::mocha()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_mode_picks_palette() {
+        assert_eq!(Theme::for_mode(ThemeMode::Dark), Theme::mocha());
+        assert_eq!(Theme::for_mode(ThemeMode::Light), Theme::latte());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::models::SortField;
+use crate::ui::{TextInput, Theme};
+use crate::util::fuzzy_score;
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+
+/// Most matches listed at once; the rest are reachable by typing more
+const MAX_VISIBLE_COMMANDS: usize = 12;
+
+/// An action the command palette can run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteCommand {
+    UnstarSelected,
+    OpenSelected,
+    CopyMarkdown,
+    SelectAll,
+    DeselectAll,
+    InvertSelection,
+    Reload,
+    Export,
+    ToggleFocusMode,
+    ToggleStats,
+    ToggleDryRun,
+    ToggleDensity,
+    ToggleViewMode,
+    ToggleTheme,
+    FindDuplicates,
+    SortBy(SortField),
+    Diagnostics,
+    Logout,
+}
+
+impl PaletteCommand {
+    /// Every command, in the order listed before anything is typed
+    pub const ALL: [PaletteCommand; 24] = [
+        PaletteCommand::UnstarSelected,
+        PaletteCommand::OpenSelected,
+        PaletteCommand::CopyMarkdown,
+        PaletteCommand::SelectAll,
+        PaletteCommand::DeselectAll,
+        PaletteCommand::InvertSelection,
+        PaletteCommand::Reload,
+        PaletteCommand::Export,
+        PaletteCommand::ToggleFocusMode,
+        PaletteCommand::ToggleStats,
+        PaletteCommand::ToggleDryRun,
+        PaletteCommand::ToggleDensity,
+        PaletteCommand::ToggleViewMode,
+        PaletteCommand::ToggleTheme,
+        PaletteCommand::FindDuplicates,
+        PaletteCommand::SortBy(SortField::Starred),
+        PaletteCommand::SortBy(SortField::Pushed),
+        PaletteCommand::SortBy(SortField::Stars),
+        PaletteCommand::SortBy(SortField::Forks),
+        PaletteCommand::SortBy(SortField::Name),
+        PaletteCommand::SortBy(SortField::Issues),
+        PaletteCommand::SortBy(SortField::Language),
+        PaletteCommand::Diagnostics,
+        PaletteCommand::Logout,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            PaletteCommand::UnstarSelected => "Unstar Selected".to_string(),
+            PaletteCommand::OpenSelected => "Open Selected in Browser".to_string(),
+            PaletteCommand::CopyMarkdown => "Copy Selected as Markdown".to_string(),
+            PaletteCommand::SelectAll => "Select All".to_string(),
+            PaletteCommand::DeselectAll => "Deselect All".to_string(),
+            PaletteCommand::InvertSelection => "Invert Selection".to_string(),
+            PaletteCommand::Reload => "Reload".to_string(),
+            PaletteCommand::Export => "Export JSON".to_string(),
+            PaletteCommand::ToggleFocusMode => "Toggle Focus Mode".to_string(),
+            PaletteCommand::ToggleStats => "Toggle Stats".to_string(),
+            PaletteCommand::ToggleDryRun => "Toggle Dry Run".to_string(),
+            PaletteCommand::ToggleDensity => "Toggle Row Density".to_string(),
+            PaletteCommand::ToggleViewMode => "Toggle Table View".to_string(),
+            PaletteCommand::ToggleTheme => "Toggle Theme".to_string(),
+            PaletteCommand::FindDuplicates => "Find Duplicates".to_string(),
+            PaletteCommand::SortBy(field) => format!("Sort by {}", field.label()),
+            PaletteCommand::Diagnostics => "Show Diagnostics".to_string(),
+            PaletteCommand::Logout => "Logout".to_string(),
+        }
+    }
+}
+
+/// Commands fuzzy-matching `query`, best match first. An empty query lists
+/// them all in registry order.
+pub fn filter_commands(query: &str) -> Vec<PaletteCommand> {
+    let mut scored: Vec<(i64, PaletteCommand)> = PaletteCommand::ALL
+        .iter()
+        .filter_map(|command| fuzzy_score(query, &command.label()).map(|score| (score, *command)))
+        .collect();
+    // Stable, so equally good matches keep registry order
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
+
+/// What the palette asks its owner to do
+pub enum PaletteEvent {
+    Run(PaletteCommand),
+    Dismiss,
+}
+
+/// Overlay listing commands, filtered as you type. Opened with Cmd/Ctrl+K.
+pub struct CommandPaletteView {
+    query: TextInput,
+    /// Index into the filtered commands
+    selected: usize,
+    focus_handle: FocusHandle,
+}
+
+impl EventEmitter<PaletteEvent> for CommandPaletteView {}
+
+impl Focusable for CommandPaletteView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl CommandPaletteView {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            query: TextInput::new(),
+            selected: 0,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let key = event.keystroke.key.as_str();
+        let modifiers = &event.keystroke.modifiers;
+        let matches = filter_commands(self.query.value());
+        let listed = matches.len().min(MAX_VISIBLE_COMMANDS);
+
+        match key {
+            "escape" => cx.emit(PaletteEvent::Dismiss),
+            "k" if modifiers.platform || modifiers.control => cx.emit(PaletteEvent::Dismiss),
+            "enter" => {
+                if let Some(command) = matches.get(self.selected) {
+                    cx.emit(PaletteEvent::Run(*command));
+                }
+            }
+            "down" => self.selected = (self.selected + 1).min(listed.saturating_sub(1)),
+            "up" => self.selected = self.selected.saturating_sub(1),
+            _ if self.query.handle_key(key) => self.selected = 0,
+            _ => match &event.keystroke.key_char {
+                Some(ch) if !modifiers.platform && !modifiers.control => {
+                    let text: String = ch.chars().filter(|c| !c.is_control()).collect();
+                    self.query.insert(&text);
+                    self.selected = 0;
+                }
+                _ => return,
+            },
+        }
+        // Keep the list's own shortcuts from firing underneath
+        cx.stop_propagation();
+        cx.notify();
+    }
+}
+
+impl Render for CommandPaletteView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let matches = filter_commands(self.query.value());
+        let selected = self.selected;
+        let (before_cursor, after_cursor) = self.query.split_at_cursor();
+
+        div()
+            .id("command-palette-overlay")
+            .absolute()
+            .inset_0()
+            .flex()
+            .justify_center()
+            .items_start()
+            .pt(px(80.))
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(|this, event, _window, cx| {
+                this.handle_key_down(event, cx);
+            }))
+            // Semi-transparent backdrop
+            .child(
+                div()
+                    .id("command-palette-backdrop")
+                    .absolute()
+                    .inset_0()
+                    .bg(rgba(0x00000066))
+                    .on_click(cx.listener(|_this, _event, _window, cx| {
+                        cx.emit(PaletteEvent::Dismiss);
+                    })),
+            )
+            .child(
+                div()
+                    .w(px(480.))
+                    .rounded_lg()
+                    .bg(rgb(theme.surface0))
+                    .border_1()
+                    .border_color(rgb(theme.surface2))
+                    .shadow_lg()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
+                    // Query
+                    .child(
+                        div()
+                            .px_4()
+                            .py_3()
+                            .border_b_1()
+                            .border_color(rgb(theme.surface1))
+                            .text_sm()
+                            .child(if self.query.is_empty() {
+                                div()
+                                    .text_color(rgb(theme.overlay0))
+                                    .child("|Type a command...")
+                            } else {
+                                div()
+                                    .text_color(rgb(theme.text))
+                                    .child(format!("{}|{}", before_cursor, after_cursor))
+                            }),
+                    )
+                    // Matching commands
+                    .child(
+                        div()
+                            .py_1()
+                            .flex()
+                            .flex_col()
+                            .when(matches.is_empty(), |this| {
+                                this.child(
+                                    div()
+                                        .px_4()
+                                        .py_2()
+                                        .text_sm()
+                                        .text_color(rgb(theme.overlay0))
+                                        .child("No matching commands"),
+                                )
+                            })
+                            .children(matches.into_iter().take(MAX_VISIBLE_COMMANDS).enumerate().map(
+                                |(index, command)| {
+                                    let is_selected = index == selected;
+                                    div()
+                                        .id(ElementId::Name(format!("command-{}", index).into()))
+                                        .px_4()
+                                        .py_1()
+                                        .text_sm()
+                                        .cursor_pointer()
+                                        .when(is_selected, |this| this.bg(rgb(theme.surface1)))
+                                        .text_color(if is_selected {
+                                            rgb(theme.text)
+                                        } else {
+                                            rgb(theme.subtext0)
+                                        })
+                                        .hover(|style| style.bg(rgb(theme.surface1)))
+                                        .child(command.label())
+                                        .on_click(cx.listener(move |_this, _event, _window, cx| {
+                                            cx.emit(PaletteEvent::Run(command));
+                                        }))
+                                },
+                            )),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_commands, PaletteCommand};
+    use crate::models::SortField;
+
+    #[test]
+    fn test_empty_query_lists_every_command() {
+        assert_eq!(filter_commands(""), PaletteCommand::ALL.to_vec());
+        assert_eq!(filter_commands("  ").len(), PaletteCommand::ALL.len());
+    }
+
+    #[test]
+    fn test_filter_commands_fuzzy_matches_labels() {
+        let matches = filter_commands("sort stars");
+        assert_eq!(matches.first(), Some(&PaletteCommand::SortBy(SortField::Stars)));
+
+        let matches = filter_commands("theme");
+        assert_eq!(matches, vec![PaletteCommand::ToggleTheme]);
+
+        assert!(filter_commands("zzz").is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::models::AppConfig;
+use crate::services::{GitHubService, API_URL};
+use crate::state::{AppState, Diagnostics};
+use crate::ui::Theme;
+use gpui::*;
+
+/// Show the diagnostics view and run its checks against GitHub in the
+/// background, filling in each row as its answer arrives
+pub fn open_diagnostics(cx: &mut App) {
+    let state = cx.global::<AppState>();
+    let epoch = state.epoch;
+    let service = match &state.github_service {
+        Some(service) => Ok(service.clone()),
+        None => match &state.config.github.personal_access_token {
+            Some(token) => GitHubService::new(token, state.config.github.proxy.as_deref()).map_err(|e| e.to_string()),
+            None => Err("No token saved".to_string()),
+        },
+    };
+
+    let mut diagnostics = Diagnostics {
+        api_url: API_URL.to_string(),
+        proxy: service.as_ref().ok().and_then(|service| service.proxy().map(str::to_string)),
+        config_path: AppConfig::config_path(),
+        cache_dir: AppConfig::cache_dir(),
+        ..Default::default()
+    };
+    let service = match service {
+        Ok(service) => service,
+        Err(e) => {
+            diagnostics.username = Some(Err(e.clone()));
+            diagnostics.scopes = Some(Err(e.clone()));
+            diagnostics.rate_limit = Some(Err(e));
+            cx.update_global::<AppState, _>(|state, _cx| state.diagnostics = Some(diagnostics));
+            return;
+        }
+    };
+    cx.update_global::<AppState, _>(|state, _cx| state.diagnostics = Some(diagnostics));
+
+    // Write back only while the view is still open for the same session
+    fn update(epoch: u64, cx: &mut AsyncApp, apply: impl FnOnce(&mut Diagnostics)) {
+        cx.update(|cx| {
+            let state = cx.global_mut::<AppState>();
+            if !state.is_current_epoch(epoch) {
+                return;
+            }
+            if let Some(diagnostics) = state.diagnostics.as_mut() {
+                apply(diagnostics);
+            }
+        })
+        .ok();
+    }
+
+    cx.spawn(async move |cx| {
+        let username = service.validate_token().await.map(|(username, _)| username);
+        update(epoch, cx, |diagnostics| diagnostics.username = Some(username.map_err(|e| e.to_string())));
+
+        let scopes = service.fetch_token_scopes().await;
+        update(epoch, cx, |diagnostics| diagnostics.scopes = Some(scopes.map_err(|e| e.to_string())));
+
+        let rate_limit = service.get_rate_limit().await;
+        update(epoch, cx, |diagnostics| diagnostics.rate_limit = Some(rate_limit.map_err(|e| e.to_string())));
+    })
+    .detach();
+}
+
+/// What the app knows about the token, rate limit and its files, shown in
+/// place of the current screen until closed
+pub fn render_diagnostics(diagnostics: &Diagnostics, theme: Theme) -> impl IntoElement {
+    let report = diagnostics.report();
+
+    div()
+        .size_full()
+        .flex()
+        .items_center()
+        .justify_center()
+        .bg(rgb(theme.base))
+        .child(
+            div()
+                .w(px(560.))
+                .flex()
+                .flex_col()
+                .gap_4()
+                .child(div().text_lg().text_color(rgb(theme.text)).child("Diagnostics"))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(rgb(theme.overlay0))
+                        .child("Include this when reporting a problem. Your token itself is never shown."),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_2()
+                        .p_4()
+                        .rounded_md()
+                        .bg(rgb(theme.surface0))
+                        .children(diagnostics.rows().into_iter().map(|(label, value)| {
+                            let (value, color) = match value {
+                                Ok(value) => (value, theme.text),
+                                Err(e) => (format!("Failed: {}", e), theme.red),
+                            };
+                            div()
+                                .flex()
+                                .gap_3()
+                                .text_sm()
+                                .child(div().w(px(120.)).flex_shrink_0().text_color(rgb(theme.subtext0)).child(label))
+                                .child(div().flex_1().min_w_0().text_color(rgb(color)).child(value))
+                        })),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .gap_2()
+                        .justify_end()
+                        .child(
+                            div()
+                                .id("copy-diagnostics-btn")
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(rgb(theme.surface1))
+                                .text_sm()
+                                .text_color(rgb(theme.subtext0))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.surface2)))
+                                .child("Copy Report")
+                                .on_click(move |_event, _window, cx| {
+                                    cx.write_to_clipboard(ClipboardItem::new_string(report.clone()));
+                                }),
+                        )
+                        .child(
+                            div()
+                                .id("close-diagnostics-btn")
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(rgb(theme.blue))
+                                .text_sm()
+                                .text_color(rgb(theme.base))
+                                .font_weight(FontWeight::MEDIUM)
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.sapphire)))
+                                .child("Close")
+                                .on_click(|_event, _window, cx| {
+                                    cx.update_global::<AppState, _>(|state, _cx| state.diagnostics = None);
+                                }),
+                        ),
+                ),
+        )
+}
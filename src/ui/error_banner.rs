@@ -0,0 +1,57 @@
+use crate::state::AppState;
+use crate::ui::Theme;
+use gpui::*;
+
+/// Banner showing `AppState.error`, with a button that dismisses it
+pub fn render_error_banner(message: String, theme: Theme) -> impl IntoElement {
+    render_banner("dismiss-error-btn", message, theme.red, theme, |state| {
+        state.clear_error();
+    })
+}
+
+/// Banner showing `AppState.warning`, with a button that dismisses it
+pub fn render_warning_banner(message: String, theme: Theme) -> impl IntoElement {
+    render_banner("dismiss-warning-btn", message, theme.yellow, theme, |state| {
+        state.clear_warning();
+    })
+}
+
+fn render_banner(
+    id: &'static str,
+    message: String,
+    color: u32,
+    theme: Theme,
+    dismiss: fn(&mut AppState),
+) -> impl IntoElement {
+    div()
+        .w_full()
+        .flex_shrink_0()
+        .px_4()
+        .py_2()
+        .flex()
+        .items_center()
+        .gap_3()
+        .bg(rgb(color))
+        .child(
+            div()
+                .flex_1()
+                .text_sm()
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(rgb(theme.base))
+                .child(message),
+        )
+        .child(
+            div()
+                .id(id)
+                .px_2()
+                .rounded_sm()
+                .text_sm()
+                .text_color(rgb(theme.base))
+                .cursor_pointer()
+                .hover(|style| style.bg(rgba(0x00000033)))
+                .child("✕")
+                .on_click(move |_event, _window, cx| {
+                    cx.update_global::<AppState, _>(|state, _cx| dismiss(state));
+                }),
+        )
+}
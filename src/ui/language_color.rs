@@ -0,0 +1,82 @@
+/// Dot color for languages GitHub's linguist doesn't color, or that aren't listed below
+pub const FALLBACK_LANGUAGE_COLOR: u32 = 0x8b949e;
+
+/// GitHub's linguist color for a language, as shown next to it on github.com
+pub fn language_color(lang: &str) -> u32 {
+    match lang.to_ascii_lowercase().as_str() {
+        "assembly" => 0x6e4c13,
+        "c" => 0x555555,
+        "c#" => 0x178600,
+        "c++" => 0xf34b7d,
+        "clojure" => 0xdb5855,
+        "crystal" => 0x000100,
+        "css" => 0x563d7c,
+        "dart" => 0x00b4ab,
+        "dockerfile" => 0x384d54,
+        "elixir" => 0x6e4a7e,
+        "elm" => 0x60b5cc,
+        "emacs lisp" => 0xc065db,
+        "erlang" => 0xb83998,
+        "f#" => 0xb845fc,
+        "gleam" => 0xffaff3,
+        "go" => 0x00add8,
+        "groovy" => 0x4298b8,
+        "haskell" => 0x5e5086,
+        "html" => 0xe34c26,
+        "java" => 0xb07219,
+        "javascript" => 0xf1e05a,
+        "julia" => 0xa270ba,
+        "jupyter notebook" => 0xda5b0b,
+        "kotlin" => 0xa97bff,
+        "lua" => 0x000080,
+        "makefile" => 0x427819,
+        "nim" => 0xffc200,
+        "nix" => 0x7e7eff,
+        "objective-c" => 0x438eff,
+        "ocaml" => 0xef7a08,
+        "perl" => 0x0298c3,
+        "php" => 0x4f5d95,
+        "powershell" => 0x012456,
+        "python" => 0x3572a5,
+        "r" => 0x198ce7,
+        "ruby" => 0x701516,
+        "rust" => 0xdea584,
+        "scala" => 0xc22d40,
+        "scss" => 0xc6538c,
+        "shell" => 0x89e051,
+        "solidity" => 0xaa6746,
+        "svelte" => 0xff3e00,
+        "swift" => 0xf05138,
+        "tex" => 0x3d6117,
+        "typescript" => 0x3178c6,
+        "vim script" => 0x199f4b,
+        "vue" => 0x41b883,
+        "zig" => 0xec915c,
+        _ => FALLBACK_LANGUAGE_COLOR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_languages() {
+        assert_eq!(language_color("Rust"), 0xdea584);
+        assert_eq!(language_color("TypeScript"), 0x3178c6);
+        assert_eq!(language_color("C++"), 0xf34b7d);
+        assert_eq!(language_color("Jupyter Notebook"), 0xda5b0b);
+    }
+
+    #[test]
+    fn test_lookup_ignores_case() {
+        assert_eq!(language_color("python"), language_color("Python"));
+        assert_eq!(language_color("GO"), 0x00add8);
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_gray() {
+        assert_eq!(language_color("Brainfuck-Extended"), FALLBACK_LANGUAGE_COLOR);
+        assert_eq!(language_color(""), FALLBACK_LANGUAGE_COLOR);
+    }
+}
@@ -1,11 +1,23 @@
 pub mod app_view;
 pub mod colors;
+pub mod command_palette;
+pub mod diagnostics;
+pub mod error_banner;
+pub mod language_color;
 pub mod repository_list;
 pub mod repository_row;
 pub mod setup_view;
+pub mod spinner;
+pub mod text_input;
 
 pub use app_view::*;
-pub use colors::catppuccin;
+pub use colors::{catppuccin, Theme};
+pub use command_palette::*;
+pub use diagnostics::*;
+pub use error_banner::*;
+pub use language_color::*;
 pub use repository_list::*;
 pub use repository_row::*;
 pub use setup_view::*;
+pub use spinner::*;
+pub use text_input::*;
@@ -1,25 +1,218 @@
-use crate::services::is_token_expired_error;
-use crate::state::{AppState, PendingAction, SortDirection, SortField};
-use crate::ui::{catppuccin, render_repository_row};
+use crate::models::{parse_repo_reference, split_language_filter};
+use crate::services::{is_rate_limit_error, is_repo_moved_error, is_token_expired_error, ExportService};
+use crate::state::{
+    AppState, ForkFilter, PendingAction, ReadmePreview, RepoStats, SelectScope, SortDirection, SortField, UnstarProgress, TYPE_TO_CONFIRM_WORD,
+    CONFIRM_LIST_LIMIT, COPIED_FEEDBACK, OPEN_SELECTED_CONFIRM_THRESHOLD, STALE_PRESETS, UNDO_WINDOW,
+};
+use crate::ui::{open_diagnostics, render_repository_row, render_repository_table_row, render_table_header, spinner, CommandPaletteView, PaletteCommand, PaletteEvent, RowState, TextInput, Theme};
+use crate::util::relative_time;
 use gpui::prelude::FluentBuilder;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
 use gpui::*;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
-pub struct RepositoryListView;
+/// Pause between pages when `load_all` fetches in the background
+const AUTO_LOAD_DELAY: Duration = Duration::from_millis(500);
+
+/// Quiet period after a sort change before the list is refetched
+const SORT_RELOAD_DELAY: Duration = Duration::from_millis(300);
+
+/// Scrolling has to settle this long before activity badges are fetched
+const ACTIVITY_FETCH_DELAY: Duration = Duration::from_millis(400);
+
+/// At most one cache write this often while pages load or unstars finish
+const CACHE_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// How often "Refreshed 5 minutes ago" is redrawn while it's shown
+const REFRESHED_LABEL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The list keeps at least this much height on short windows, below the
+/// header and toolbar that stay pinned above it
+const MIN_LIST_HEIGHT: Pixels = px(160.);
+
+/// Count shown in the toolbar. With `rows` (the 1-based first and last rows
+/// in view) it follows the scroll position, e.g. "21–40 of 1500 repositories".
+fn list_summary(rows: Option<(usize, usize)>, visible_count: usize, total_count: usize, is_filtered: bool) -> String {
+    match (rows, is_filtered) {
+        (None, false) => format!("{} repositories", total_count),
+        (None, true) => format!("{} of {} repositories", visible_count, total_count),
+        (Some((first, last)), false) => format!("{}–{} of {} repositories", first, last, total_count),
+        (Some((first, last)), true) => {
+            format!("{}–{} of {} matching · {} repositories", first, last, visible_count, total_count)
+        }
+    }
+}
+
+pub struct RepositoryListView {
+    /// Focus for list-level keyboard shortcuts
+    list_focus_handle: FocusHandle,
+    list_scroll_handle: ScrollHandle,
+    /// Row moved to with the arrow keys, as an index into the filtered list
+    highlighted_index: Option<usize>,
+    /// Last row whose checkbox was clicked, the anchor for shift-click ranges
+    last_clicked_index: Option<usize>,
+    /// What the last select-all action covered; the checkbox shows its state
+    select_scope: SelectScope,
+    /// Text typed into the confirmation dialog for large bulk unstars
+    confirm_input: String,
+    /// "Don't ask again" checkbox in the single and selected unstar dialogs
+    skip_confirm: bool,
+    confirm_focus_handle: FocusHandle,
+    /// Focus for the confirmation overlay, so Enter/Escape reach it
+    dialog_focus_handle: FocusHandle,
+    search_focus_handle: FocusHandle,
+    /// `owner/repo` or URL typed into the star input
+    star_input: String,
+    star_focus_handle: FocusHandle,
+    starring: bool,
+    star_error: Option<String>,
+    /// Whether a background page load is already queued
+    auto_load_scheduled: bool,
+    /// Reset time of the exhausted rate limit a re-check is queued for
+    rate_limit_recheck: Option<DateTime<Utc>>,
+    /// Rows of the visible list in view, as of the last render
+    activity_rows: Option<Range<usize>>,
+    /// Activity fetch waiting out `ACTIVITY_FETCH_DELAY`; replacing it cancels the old one
+    pending_activity_fetch: Option<Task<()>>,
+    /// Cache write waiting out `CACHE_SAVE_DELAY` or running in the background
+    pending_cache_save: Option<Task<()>>,
+    /// Redraws the "Refreshed" label every `REFRESHED_LABEL_INTERVAL`
+    refreshed_label_ticker: Option<Task<()>>,
+    /// Whether the stats panel below the header is expanded
+    show_stats: bool,
+    /// Set while "Copy as Markdown" reads "Copied!"; the task clears it
+    markdown_copied: Option<Task<()>>,
+    /// Reload waiting out `SORT_RELOAD_DELAY`; replacing it cancels the old one
+    pending_sort_reload: Option<Task<()>>,
+    /// A search over every starred repo on GitHub is running
+    searching_all: bool,
+    /// Query of the last search over all stars and how many repos matched
+    search_all_result: Option<(String, usize)>,
+    /// Whether the filter presets dropdown is open
+    preset_menu_open: bool,
+    /// Whether the license filter dropdown is open
+    license_menu_open: bool,
+    /// Pattern typed into "Select by regex", None while it's closed
+    regex_input: Option<TextInput>,
+    regex_focus_handle: FocusHandle,
+    /// How many repositories the typed pattern matches, or why it doesn't compile
+    regex_preview: Option<Result<usize, String>>,
+    /// Open command palette, dropped (with its subscription) on close
+    command_palette: Option<(Entity<CommandPaletteView>, Subscription)>,
+    /// Name typed for saving the current filters as a preset
+    preset_name: String,
+    preset_name_focus_handle: FocusHandle,
+    /// Whether the account switcher dropdown is open
+    profile_menu_open: bool,
+    /// Name typed for a new account profile
+    profile_name: String,
+    profile_name_focus_handle: FocusHandle,
+}
 
 impl RepositoryListView {
     pub fn new(cx: &mut Context<Self>) -> Self {
         // Observe global state changes to refresh UI
-        cx.observe_global::<AppState>(|_this, cx| {
+        cx.observe_global::<AppState>(|this, cx| {
             tracing::info!("Global state changed, notifying view");
             cx.notify();
+            this.maybe_auto_load(cx);
+            this.maybe_recheck_rate_limit(cx);
+            this.maybe_save_cache(cx);
         }).detach();
 
-        Self
+        Self {
+            list_focus_handle: cx.focus_handle(),
+            list_scroll_handle: ScrollHandle::new(),
+            highlighted_index: None,
+            last_clicked_index: None,
+            select_scope: SelectScope::default(),
+            confirm_input: String::new(),
+            skip_confirm: false,
+            confirm_focus_handle: cx.focus_handle(),
+            dialog_focus_handle: cx.focus_handle(),
+            search_focus_handle: cx.focus_handle(),
+            star_input: String::new(),
+            star_focus_handle: cx.focus_handle(),
+            starring: false,
+            star_error: None,
+            auto_load_scheduled: false,
+            rate_limit_recheck: None,
+            activity_rows: None,
+            pending_activity_fetch: None,
+            pending_cache_save: None,
+            refreshed_label_ticker: None,
+            show_stats: false,
+            markdown_copied: None,
+            pending_sort_reload: None,
+            searching_all: false,
+            search_all_result: None,
+            preset_menu_open: false,
+            license_menu_open: false,
+            regex_input: None,
+            regex_focus_handle: cx.focus_handle(),
+            regex_preview: None,
+            command_palette: None,
+            preset_name: String::new(),
+            preset_name_focus_handle: cx.focus_handle(),
+            profile_menu_open: false,
+            profile_name: String::new(),
+            profile_name_focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Enter confirms and Escape cancels the open dialog; no key reaches the list beneath
+    fn handle_dialog_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        cx.stop_propagation();
+        let state = cx.global::<AppState>();
+        let Some(action) = state.pending_action.clone() else {
+            return;
+        };
+        let requires_typing = action.requires_typed_confirmation(state.config.unstar.type_to_confirm_threshold);
+
+        match event.keystroke.key.as_str() {
+            "enter" if !requires_typing || action.is_confirmation_text(&self.confirm_input) => {
+                self.execute_action(action, cx);
+            }
+            "escape" => {
+                cx.update_global::<AppState, _>(|state, _cx| {
+                    state.pending_action = None;
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_confirm_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let key = &event.keystroke.key;
+
+        if key == "backspace" {
+            self.confirm_input.pop();
+            cx.notify();
+            return;
+        }
+
+        if let Some(ch) = &event.keystroke.key_char {
+            let filtered: String = ch.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+            if !filtered.is_empty() {
+                self.confirm_input.push_str(&filtered);
+                cx.notify();
+            }
+        }
     }
 }
 
 impl Render for RepositoryListView {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let theme_mode = cx.global::<AppState>().config.theme;
+        let dry_run = cx.global::<AppState>().config.unstar.dry_run;
+        let auto_loading = cx.global::<AppState>().is_auto_loading();
+        let view = cx.entity().downgrade();
         // Clone all needed data upfront to avoid borrow issues
         let (
             selection_count,
@@ -32,19 +225,66 @@ impl Render for RepositoryListView {
             loading,
             sort_field,
             sort_direction,
+            unstar_progress,
+            type_to_confirm_threshold,
+            search_query,
+            topic_filter,
+            stale_threshold_days,
+            archived_only,
+            no_description_only,
+            fork_filter,
+            own_only,
+            duplicates_only,
+            is_filtered,
+            visible_count,
+            unstar_filtered_count,
+            rate_limit,
+            read_only,
+            offline,
+            undo_count,
+            load_progress,
+            last_refreshed,
             repos_for_render,
         ) = {
             let state = cx.global::<AppState>();
-            let repos = &state.repositories;
+            let repos = state.visible_repositories();
             let selection_count = state.selection.count();
-            let total_count = repos.len();
-            let all_selected = selection_count == total_count && total_count > 0;
+            let total_count = state.repositories.len();
+            let all_selected = state.all_selected_in(self.select_scope);
 
+            // Fuzzy matches aren't contiguous, so there's no substring to highlight
+            let highlight = Some(state.filter_spec())
+                .filter(|spec| !spec.fuzzy)
+                .map(|spec| spec.query)
+                .filter(|query| !query.is_empty());
+            // Keyboard highlight is filled in once the list length is known
             let repos_for_render: Vec<_> = repos
                 .iter()
-                .map(|r| {
-                    let is_selected = state.selection.is_selected(r.id);
-                    (r.clone(), is_selected)
+                .enumerate()
+                .map(|(index, r)| {
+                    let row = RowState {
+                        index,
+                        is_selected: state.selection.is_selected(r.id),
+                        is_highlighted: false,
+                        can_unstar: !state.is_read_only(),
+                        readme: (state.expanded_readme == Some(r.id))
+                            .then(|| state.readme_cache.get(&r.id).cloned())
+                            .flatten(),
+                        copied: state
+                            .copied
+                            .filter(|(id, _)| *id == r.id)
+                            .map(|(_, target)| target),
+                        has_lists: !state.lists.is_empty(),
+                        list_menu: (state.list_menu_open == Some(r.id)).then(|| state.lists.clone()),
+                        would_unstar: state.would_unstar.contains(&r.id),
+                        star_age: state.star_age_label(r),
+                        density: state.row_density,
+                        highlight: highlight.clone(),
+                        is_own: state.is_own_repo(r),
+                        is_pinned: state.is_pinned(r.id),
+                        activity: state.repo_activity.get(&r.id).copied().flatten(),
+                    };
+                    ((*r).clone(), row)
                 })
                 .collect();
 
@@ -59,16 +299,88 @@ impl Render for RepositoryListView {
                 state.loading,
                 state.sort_field,
                 state.sort_direction,
+                state.unstar_progress.clone(),
+                state.config.unstar.type_to_confirm_threshold,
+                state.search_query.clone(),
+                state.topic_filter.clone(),
+                state.stale_threshold_days,
+                state.archived_only,
+                state.no_description_only,
+                state.fork_filter,
+                state.own_only,
+                state.duplicates_only,
+                state.is_filtered(),
+                repos.len(),
+                state.unstar_filter_targets(&state.filter_spec()).len(),
+                state.rate_limit.clone(),
+                state.is_read_only(),
+                state.offline,
+                state.can_undo().then_some(state.last_unstarred.len()),
+                if state.showing_cached {
+                    Some("Refreshing cached list...".to_string())
+                } else {
+                    (state.has_more && !state.offline).then(|| state.load_progress_label())
+                },
+                state.last_refreshed,
                 repos_for_render,
             )
         };
 
+        // Dialogs take focus while open and hand it back to the list when closed.
+        // Typed confirmation only lives as long as its dialog.
+        match &pending_action {
+            Some(action) if action.requires_typed_confirmation(type_to_confirm_threshold) => {
+                if !self.confirm_focus_handle.is_focused(window) {
+                    self.confirm_focus_handle.focus(window);
+                }
+            }
+            Some(_) => {
+                self.confirm_input.clear();
+                if !self.dialog_focus_handle.is_focused(window) {
+                    self.dialog_focus_handle.focus(window);
+                }
+            }
+            None => {
+                self.confirm_input.clear();
+                self.skip_confirm = false;
+                if self.dialog_focus_handle.is_focused(window) || self.confirm_focus_handle.is_focused(window) {
+                    self.list_focus_handle.focus(window);
+                }
+            }
+        }
+        // Keep the highlight inside the list as filters and unstars shrink it
+        self.highlighted_index = self
+            .highlighted_index
+            .map(|index| index.min(visible_count.saturating_sub(1)))
+            .filter(|_| visible_count > 0);
+        let highlighted_index = self.highlighted_index;
+        if window.focused(cx).is_none() {
+            self.list_focus_handle.focus(window);
+        }
+        // Nothing starred at all, as opposed to a filter hiding everything
+        let no_stars = total_count == 0 && !loading && !has_more;
+        let stats = (self.show_stats && !no_stars).then(|| cx.global::<AppState>().compute_stats());
+        let session_diff = cx.global::<AppState>().session_diff.as_ref().map(|diff| diff.summary());
+        let session_unstars = cx.global::<AppState>().session_unstar_label();
+        let focus_mode = cx.global::<AppState>().focus_mode;
+        let row_density = cx.global::<AppState>().row_density;
+        let view_mode = cx.global::<AppState>().view_mode;
+        let search_focused = self.search_focus_handle.is_focused(window);
+        let star_focused = self.star_focus_handle.is_focused(window);
+        let visible_rows = self.visible_rows(loading, visible_count);
+        self.schedule_activity_fetch(visible_rows.map(|(first, last)| first - 1..last), cx);
+        self.tick_refreshed_label(last_refreshed.is_some(), cx);
+
         div()
             .size_full()
             .relative()
             .flex()
             .flex_col()
-            .bg(rgb(catppuccin::BASE))
+            .bg(rgb(theme.base))
+            .track_focus(&self.list_focus_handle)
+            .on_key_down(cx.listener(|this, event, window, cx| {
+                this.handle_list_key_down(event, window, cx);
+            }))
             // Header
             .child(
                 div()
@@ -79,8 +391,8 @@ impl Render for RepositoryListView {
                     .items_center()
                     .gap_4()
                     .border_b_1()
-                    .border_color(rgb(catppuccin::SURFACE1))
-                    .bg(rgb(catppuccin::MANTLE))
+                    .border_color(rgb(theme.surface1))
+                    .bg(rgb(theme.mantle))
                     // Title
                     .child(
                         div()
@@ -91,43 +403,401 @@ impl Render for RepositoryListView {
                                 div()
                                     .text_lg()
                                     .font_weight(FontWeight::BOLD)
-                                    .text_color(rgb(catppuccin::TEXT))
+                                    .text_color(rgb(theme.text))
                                     .child(format!("Starred Repositories ({})", total_count)),
                             )
                             .when(!username.is_empty(), |this| {
                                 this.child(
                                     div()
                                         .text_sm()
-                                        .text_color(rgb(catppuccin::OVERLAY0))
+                                        .text_color(rgb(theme.overlay0))
                                         .child(format!("@{}", username)),
                                 )
-                            }),
+                            })
+                            .when(focus_mode, |this| {
+                                this.child(
+                                    div()
+                                        .px_2()
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .bg(rgb(theme.yellow))
+                                        .text_color(rgb(theme.base))
+                                        .child("Focus mode: u unstars without asking, f or Esc exits"),
+                                )
+                            })
+                            .when_some(session_unstars, |this, label| {
+                                this.child(
+                                    div()
+                                        .px_2()
+                                        .rounded_sm()
+                                        .text_xs()
+                                        .bg(rgb(theme.surface0))
+                                        .text_color(rgb(theme.sapphire))
+                                        .child(label),
+                                )
+                            })
+                            // Data freshness and manual refresh
+                            .when_some(last_refreshed, |this, refreshed| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(rgb(theme.overlay0))
+                                        .child(format!("Refreshed {}", relative_time(refreshed, Utc::now()))),
+                                )
+                            })
+                            .child(
+                                div()
+                                    .id("refresh-btn")
+                                    .px_2()
+                                    .rounded_sm()
+                                    .text_sm()
+                                    .text_color(if loading || loading_more {
+                                        rgb(theme.overlay0)
+                                    } else {
+                                        rgb(theme.subtext0)
+                                    })
+                                    .child("⟳")
+                                    .when(!loading && !loading_more, |this| {
+                                        this.cursor_pointer()
+                                            .hover(|style| style.bg(rgb(theme.surface1)))
+                                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                                this.reload_repos(cx);
+                                            }))
+                                    }),
+                            ),
                     )
                     // Spacer
                     .child(div().flex_1())
-                    // Unstar Selected button
+                    // Star by owner/repo or URL
+                    .child(self.render_star_input(star_focused, read_only, cx))
+                    // API rate limit
+                    .when_some(rate_limit, |this, rate_limit| {
+                        let label = if rate_limit.is_exhausted() {
+                            format!("{} · {}", rate_limit.label(), rate_limit.reset_label())
+                        } else {
+                            rate_limit.label()
+                        };
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(if rate_limit.is_exhausted() {
+                                    rgb(theme.red)
+                                } else {
+                                    rgb(theme.overlay0)
+                                })
+                                .child(label),
+                        )
+                    })
+                    // Loading progress while more pages remain
+                    .when_some(load_progress, |this, label| {
+                        this.child(
+                            div()
+                                .text_sm()
+                                .text_color(rgb(theme.subtext0))
+                                .child(label),
+                        )
+                    })
+                    .when(auto_loading, |this| {
+                        this.child(
+                            div()
+                                .id("stop-loading-btn")
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .bg(rgb(theme.surface1))
+                                .text_xs()
+                                .text_color(rgb(theme.subtext0))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.surface2)))
+                                .child("Stop loading")
+                                .on_click(cx.listener(|_this, _event, _window, cx| {
+                                    cx.update_global::<AppState, _>(|state, _cx| state.stop_loading_all());
+                                    cx.notify();
+                                })),
+                        )
+                    })
+                    // Bulk unstar progress bar
+                    .when_some(unstar_progress.clone(), |this, progress| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .child(
+                                    div()
+                                        .w(px(120.))
+                                        .h(px(6.))
+                                        .rounded_sm()
+                                        .bg(rgb(theme.surface1))
+                                        .child(
+                                            div()
+                                                .h_full()
+                                                .w(px(120. * progress.fraction()))
+                                                .rounded_sm()
+                                                .bg(rgb(theme.blue)),
+                                        ),
+                                )
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(rgb(theme.subtext0))
+                                        .child(progress.label()),
+                                ),
+                        )
+                    })
+                    // Open Selected button
                     .when(selection_count > 0, |this| {
                         let count = selection_count;
                         this.child(
                             div()
-                                .id("unstar-selected-btn")
+                                .id("open-selected-btn")
                                 .px_4()
                                 .py_2()
                                 .rounded_md()
-                                .bg(rgb(catppuccin::RED))
+                                .bg(rgb(theme.surface1))
                                 .text_sm()
-                                .text_color(rgb(catppuccin::BASE))
+                                .text_color(rgb(theme.text))
                                 .font_weight(FontWeight::MEDIUM)
                                 .cursor_pointer()
-                                .hover(|style| style.opacity(0.9))
-                                .child(format!("Unstar Selected ({})", selection_count))
-                                .on_click(cx.listener(move |_this, _event, _window, cx| {
-                                    cx.update_global::<AppState, _>(|state, _cx| {
-                                        state.pending_action = Some(PendingAction::UnstarSelected(count));
-                                    });
+                                .hover(|style| style.bg(rgb(theme.surface2)))
+                                .child(format!("Open Selected ({})", count))
+                                .on_click(cx.listener(|_this, _event, _window, cx| {
+                                    Self::request_open_selected(cx);
+                                })),
+                        )
+                    })
+                    // Copy the selection as a markdown list
+                    .when(selection_count > 0, |this| {
+                        this.child(
+                            div()
+                                .id("copy-markdown-btn")
+                                .px_4()
+                                .py_2()
+                                .rounded_md()
+                                .bg(rgb(theme.surface1))
+                                .text_sm()
+                                .text_color(if self.markdown_copied.is_some() {
+                                    rgb(theme.blue)
+                                } else {
+                                    rgb(theme.text)
+                                })
+                                .font_weight(FontWeight::MEDIUM)
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.surface2)))
+                                .child(if self.markdown_copied.is_some() {
+                                    "Copied!"
+                                } else {
+                                    "Copy as Markdown"
+                                })
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.copy_selected_as_markdown(cx);
                                 })),
                         )
                     })
+                    // Unstar Selected button
+                    .when(selection_count > 0 && unstar_progress.is_none(), |this| {
+                        this.child(
+                            div()
+                                .id("unstar-selected-btn")
+                                .px_4()
+                                .py_2()
+                                .rounded_md()
+                                .bg(if read_only {
+                                    rgb(theme.surface1)
+                                } else {
+                                    rgb(theme.red)
+                                })
+                                .text_sm()
+                                .text_color(if read_only {
+                                    rgb(theme.overlay0)
+                                } else {
+                                    rgb(theme.base)
+                                })
+                                .font_weight(FontWeight::MEDIUM)
+                                .child(format!("Unstar Selected ({})", selection_count))
+                                .when(!read_only, |this| {
+                                    this.cursor_pointer()
+                                        .hover(|style| style.opacity(0.9))
+                                        .on_click(cx.listener(|this, _event, _window, cx| {
+                                            this.request_unstar_selected(cx);
+                                        }))
+                                }),
+                        )
+                    })
+                    // Unstar everything the active filters match
+                    .when(is_filtered && unstar_filtered_count > 0 && unstar_progress.is_none(), |this| {
+                        this.child(
+                            div()
+                                .id("unstar-filtered-btn")
+                                .px_4()
+                                .py_2()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(if read_only {
+                                    rgb(theme.surface2)
+                                } else {
+                                    rgb(theme.red)
+                                })
+                                .text_sm()
+                                .text_color(if read_only {
+                                    rgb(theme.overlay0)
+                                } else {
+                                    rgb(theme.red)
+                                })
+                                .font_weight(FontWeight::MEDIUM)
+                                .child(format!("Unstar All Filtered ({})", unstar_filtered_count))
+                                .when(!read_only, |this| {
+                                    this.cursor_pointer()
+                                        .hover(|style| style.bg(rgb(theme.surface0)))
+                                        .on_click(cx.listener(move |_this, _event, _window, cx| {
+                                            cx.update_global::<AppState, _>(|state, _cx| {
+                                                state.confirm_unstar_filtered();
+                                            });
+                                        }))
+                                }),
+                        )
+                    })
+                    // Cards or table
+                    .child(
+                        div()
+                            .id("view-mode-toggle-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(rgb(theme.surface1))
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child(view_mode.toggle_label())
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.toggle_view_mode();
+                                });
+                            })),
+                    )
+                    // Row density toggle, which only applies to cards
+                    .child(
+                        div()
+                            .when(view_mode.is_table(), |this| this.hidden())
+                            .id("density-toggle-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(rgb(theme.surface1))
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child(row_density.toggle_label())
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.toggle_row_density();
+                                });
+                            })),
+                    )
+                    // Theme toggle
+                    .child(
+                        div()
+                            .id("theme-toggle-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(rgb(theme.surface1))
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child(theme_mode.toggle_label())
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.toggle_theme();
+                                });
+                            })),
+                    )
+                    // Dry-run toggle
+                    .child(
+                        div()
+                            .id("dry-run-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(if dry_run {
+                                rgb(theme.yellow)
+                            } else {
+                                rgb(theme.surface1)
+                            })
+                            .text_sm()
+                            .text_color(if dry_run {
+                                rgb(theme.base)
+                            } else {
+                                rgb(theme.subtext0)
+                            })
+                            .cursor_pointer()
+                            .hover(|style| style.opacity(0.9))
+                            .child(if dry_run { "Dry run: on" } else { "Dry run" })
+                            .on_click(cx.listener(move |_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.set_dry_run(!dry_run);
+                                });
+                            })),
+                    )
+                    // Stats panel toggle
+                    .child(
+                        div()
+                            .id("stats-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(if self.show_stats {
+                                rgb(theme.surface2)
+                            } else {
+                                rgb(theme.surface1)
+                            })
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child(if self.show_stats { "Stats ▴" } else { "Stats ▾" })
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.show_stats = !this.show_stats;
+                                cx.notify();
+                            })),
+                    )
+                    // Export button
+                    .child(
+                        div()
+                            .id("export-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(rgb(theme.surface1))
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child("Export")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.export_repos(cx);
+                            })),
+                    )
+                    // Diagnostics button
+                    .child(
+                        div()
+                            .id("diagnostics-btn")
+                            .px_3()
+                            .py_2()
+                            .rounded_md()
+                            .bg(rgb(theme.surface1))
+                            .text_sm()
+                            .text_color(rgb(theme.subtext0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child("Diagnostics")
+                            .on_click(cx.listener(|_this, _event, _window, cx| open_diagnostics(cx))),
+                    )
+                    // Account switcher
+                    .child(self.render_profiles(window, cx))
                     // Logout button
                     .child(
                         div()
@@ -135,11 +805,11 @@ impl Render for RepositoryListView {
                             .px_3()
                             .py_2()
                             .rounded_md()
-                            .bg(rgb(catppuccin::SURFACE1))
+                            .bg(rgb(theme.surface1))
                             .text_sm()
-                            .text_color(rgb(catppuccin::SUBTEXT0))
+                            .text_color(rgb(theme.subtext0))
                             .cursor_pointer()
-                            .hover(|style| style.bg(rgb(catppuccin::SURFACE2)))
+                            .hover(|style| style.bg(rgb(theme.surface2)))
                             .child("Logout")
                             .on_click(cx.listener(|_this, _event, _window, cx| {
                                 cx.update_global::<AppState, _>(|state, _cx| {
@@ -148,9 +818,13 @@ impl Render for RepositoryListView {
                             })),
                     ),
             )
+            .when_some(stats, |this, stats| this.child(Self::render_stats_panel(&stats, theme)))
+            .when(offline, |this| this.child(self.render_offline_banner(cx)))
+            .when_some(session_diff, |this, summary| this.child(Self::render_session_diff(summary, theme)))
             // Toolbar
             .child(
                 div()
+                    .when(no_stars, |this| this.hidden())
                     .w_full()
                     .px_4()
                     .py_2()
@@ -158,8 +832,8 @@ impl Render for RepositoryListView {
                     .items_center()
                     .gap_4()
                     .border_b_1()
-                    .border_color(rgb(catppuccin::SURFACE1))
-                    .bg(rgb(catppuccin::SURFACE0))
+                    .border_color(rgb(theme.surface1))
+                    .bg(rgb(theme.surface0))
                     // Select All checkbox
                     .child(
                         div()
@@ -178,17 +852,17 @@ impl Render for RepositoryListView {
                                     .rounded_sm()
                                     .border_1()
                                     .border_color(if all_selected {
-                                        rgb(catppuccin::BLUE)
+                                        rgb(theme.blue)
                                     } else {
-                                        rgb(catppuccin::SURFACE1)
+                                        rgb(theme.surface1)
                                     })
                                     .bg(if all_selected {
-                                        rgb(catppuccin::BLUE)
+                                        rgb(theme.blue)
                                     } else {
-                                        rgb(catppuccin::BASE)
+                                        rgb(theme.base)
                                     })
                                     .child(if all_selected {
-                                        div().text_xs().text_color(rgb(catppuccin::BASE)).child("✓")
+                                        div().text_xs().text_color(rgb(theme.base)).child("✓")
                                     } else {
                                         div()
                                     }),
@@ -196,14 +870,106 @@ impl Render for RepositoryListView {
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(catppuccin::TEXT))
-                                    .child("Select All"),
+                                    .text_color(rgb(theme.text))
+                                    .child("Select Visible"),
                             )
                             .on_click(cx.listener(|this, _event, _window, cx| {
-                                this.toggle_select_all(cx);
+                                this.toggle_select_visible(cx);
                             })),
                     )
-                    // Sort controls
+                    // Select every fetched repo, including ones hidden by filters
+                    .child(
+                        div()
+                            .id("select-all-loaded")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(rgb(theme.surface1))
+                            .text_color(rgb(theme.subtext0))
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child(format!("Select All Loaded ({})", total_count))
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.toggle_select_all_loaded(cx);
+                            })),
+                    )
+                    // Invert / deselect
+                    .child(
+                        div()
+                            .id("invert-selection")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(rgb(theme.surface1))
+                            .text_color(rgb(theme.subtext0))
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .child("Invert")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.invert_selection_filtered();
+                                });
+                            })),
+                    )
+                    .child(
+                        div()
+                            .id("deselect-all")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .bg(rgb(theme.surface1))
+                            .text_color(if selection_count > 0 {
+                                rgb(theme.subtext0)
+                            } else {
+                                rgb(theme.overlay0)
+                            })
+                            .when(selection_count > 0, |this| {
+                                this.cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.surface2)))
+                                    .on_click(cx.listener(|_this, _event, _window, cx| {
+                                        cx.update_global::<AppState, _>(|state, _cx| {
+                                            state.selection.clear();
+                                        });
+                                    }))
+                            })
+                            .child("Deselect All"),
+                    )
+                    .child(self.render_regex_select(cx))
+                    // Search box
+                    .child(self.render_search_box(&search_query, search_focused, cx))
+                    .child(self.render_fuzzy_toggle(cx))
+                    .when(!search_query.trim().is_empty(), |this| {
+                        this.child(self.render_search_all_button(&search_query, cx))
+                    })
+                    // Active topic filter
+                    .when_some(topic_filter, |this, topic| {
+                        this.child(
+                            div()
+                                .id("topic-filter-pill")
+                                .px_2()
+                                .py_1()
+                                .flex()
+                                .items_center()
+                                .gap_1()
+                                .rounded_full()
+                                .bg(rgb(theme.blue))
+                                .text_xs()
+                                .text_color(rgb(theme.base))
+                                .cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.sapphire)))
+                                .child(format!("Filtered by #{}", topic))
+                                .child("✕")
+                                .on_click(cx.listener(|_this, _event, _window, cx| {
+                                    cx.update_global::<AppState, _>(|state, _cx| {
+                                        state.topic_filter = None;
+                                    });
+                                })),
+                        )
+                    })
+                    // Stale filter presets
                     .child(
                         div()
                             .flex()
@@ -212,53 +978,241 @@ impl Render for RepositoryListView {
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(catppuccin::OVERLAY0))
-                                    .child("Sort:"),
+                                    .text_color(rgb(theme.overlay0))
+                                    .child("Stale:"),
                             )
-                            // Sort field buttons
-                            .children(SortField::all().iter().map(|field| {
-                                let is_active = *field == sort_field;
-                                let field_copy = *field;
+                            .children(STALE_PRESETS.iter().map(|&(label, days)| {
+                                let is_active = stale_threshold_days == Some(days);
                                 div()
-                                    .id(ElementId::Name(format!("sort-{}", field.label()).into()))
+                                    .id(ElementId::Name(format!("stale-{}", label).into()))
                                     .px_2()
                                     .py_1()
                                     .rounded_sm()
                                     .text_xs()
                                     .cursor_pointer()
                                     .bg(if is_active {
-                                        rgb(catppuccin::BLUE)
+                                        rgb(theme.blue)
                                     } else {
-                                        rgb(catppuccin::SURFACE1)
+                                        rgb(theme.surface1)
                                     })
                                     .text_color(if is_active {
-                                        rgb(catppuccin::BASE)
+                                        rgb(theme.base)
                                     } else {
-                                        rgb(catppuccin::SUBTEXT0)
+                                        rgb(theme.subtext0)
                                     })
-                                    .hover(|style| {
-                                        if is_active {
-                                            style
-                                        } else {
-                                            style.bg(rgb(catppuccin::SURFACE2))
-                                        }
+                                    .when(!is_active, |this| {
+                                        this.hover(|style| style.bg(rgb(theme.surface2)))
                                     })
-                                    .child(field.label())
-                                    .on_click(cx.listener(move |this, _event, _window, cx| {
-                                        let needs_reload = cx.update_global::<AppState, _>(|state, _cx| {
-                                            if state.sort_field == field_copy {
-                                                // Toggle direction if same field
-                                                state.sort_direction = state.sort_direction.toggle();
-                                            } else {
-                                                // Change field, reset to ascending
-                                                state.sort_field = field_copy;
-                                                state.sort_direction = SortDirection::Asc;
-                                            }
-                                            true
+                                    .child(label)
+                                    .on_click(cx.listener(move |_this, _event, _window, cx| {
+                                        cx.update_global::<AppState, _>(|state, _cx| {
+                                            // Clicking the active preset turns the filter off
+                                            state.stale_threshold_days = (!is_active).then_some(days);
                                         });
-                                        if needs_reload {
-                                            this.reload_repos(cx);
-                                        }
+                                    }))
+                            })),
+                    )
+                    // Archived-only toggle
+                    .child(
+                        div()
+                            .id("archived-only")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(if archived_only {
+                                rgb(theme.blue)
+                            } else {
+                                rgb(theme.surface1)
+                            })
+                            .text_color(if archived_only {
+                                rgb(theme.base)
+                            } else {
+                                rgb(theme.subtext0)
+                            })
+                            .when(!archived_only, |this| {
+                                this.hover(|style| style.bg(rgb(theme.surface2)))
+                            })
+                            .child("Archived")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.archived_only = !state.archived_only;
+                                });
+                            })),
+                    )
+                    // No-description toggle
+                    .child(
+                        div()
+                            .id("no-description-only")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(if no_description_only {
+                                rgb(theme.blue)
+                            } else {
+                                rgb(theme.surface1)
+                            })
+                            .text_color(if no_description_only {
+                                rgb(theme.base)
+                            } else {
+                                rgb(theme.subtext0)
+                            })
+                            .when(!no_description_only, |this| {
+                                this.hover(|style| style.bg(rgb(theme.surface2)))
+                            })
+                            .child("No description")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.no_description_only = !state.no_description_only;
+                                });
+                            })),
+                    )
+                    // Own-repositories toggle
+                    .child(
+                        div()
+                            .id("own-only")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(if own_only {
+                                rgb(theme.blue)
+                            } else {
+                                rgb(theme.surface1)
+                            })
+                            .text_color(if own_only {
+                                rgb(theme.base)
+                            } else {
+                                rgb(theme.subtext0)
+                            })
+                            .when(!own_only, |this| {
+                                this.hover(|style| style.bg(rgb(theme.surface2)))
+                            })
+                            .child("Mine")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.own_only = !state.own_only;
+                                });
+                            })),
+                    )
+                    // Likely duplicate stars, e.g. after a transfer or rename
+                    .child(
+                        div()
+                            .id("duplicates-only")
+                            .px_2()
+                            .py_1()
+                            .rounded_sm()
+                            .text_xs()
+                            .cursor_pointer()
+                            .bg(if duplicates_only {
+                                rgb(theme.blue)
+                            } else {
+                                rgb(theme.surface1)
+                            })
+                            .text_color(if duplicates_only {
+                                rgb(theme.base)
+                            } else {
+                                rgb(theme.subtext0)
+                            })
+                            .when(!duplicates_only, |this| {
+                                this.hover(|style| style.bg(rgb(theme.surface2)))
+                            })
+                            .child("Find duplicates")
+                            .on_click(cx.listener(|_this, _event, _window, cx| {
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    state.duplicates_only = !state.duplicates_only;
+                                });
+                            })),
+                    )
+                    // Fork filter
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .children([ForkFilter::Only, ForkFilter::Exclude].map(|filter| {
+                                let is_active = fork_filter == filter;
+                                div()
+                                    .id(ElementId::Name(format!("fork-{:?}", filter).into()))
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .cursor_pointer()
+                                    .bg(if is_active {
+                                        rgb(theme.blue)
+                                    } else {
+                                        rgb(theme.surface1)
+                                    })
+                                    .text_color(if is_active {
+                                        rgb(theme.base)
+                                    } else {
+                                        rgb(theme.subtext0)
+                                    })
+                                    .when(!is_active, |this| {
+                                        this.hover(|style| style.bg(rgb(theme.surface2)))
+                                    })
+                                    .child(filter.label())
+                                    .on_click(cx.listener(move |_this, _event, _window, cx| {
+                                        cx.update_global::<AppState, _>(|state, _cx| {
+                                            // Clicking the active option turns the filter off
+                                            state.fork_filter =
+                                                if is_active { ForkFilter::All } else { filter };
+                                        });
+                                    }))
+                            })),
+                    )
+                    // License filter
+                    .child(self.render_license_filter(cx))
+                    // Saved filter presets
+                    .child(self.render_presets(window, cx))
+                    // Sort controls
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(rgb(theme.overlay0))
+                                    .child("Sort:"),
+                            )
+                            // Sort field buttons
+                            .children(SortField::all().iter().map(|field| {
+                                let is_active = *field == sort_field;
+                                let field_copy = *field;
+                                div()
+                                    .id(ElementId::Name(format!("sort-{}", field.label()).into()))
+                                    .px_2()
+                                    .py_1()
+                                    .rounded_sm()
+                                    .text_xs()
+                                    .cursor_pointer()
+                                    .bg(if is_active {
+                                        rgb(theme.blue)
+                                    } else {
+                                        rgb(theme.surface1)
+                                    })
+                                    .text_color(if is_active {
+                                        rgb(theme.base)
+                                    } else {
+                                        rgb(theme.subtext0)
+                                    })
+                                    .hover(|style| {
+                                        if is_active {
+                                            style
+                                        } else {
+                                            style.bg(rgb(theme.surface2))
+                                        }
+                                    })
+                                    .child(field.label())
+                                    .on_click(cx.listener(move |this, _event, _window, cx| {
+                                        this.sort_by(field_copy, cx);
                                     }))
                             }))
                             // Direction indicator
@@ -270,69 +1224,119 @@ impl Render for RepositoryListView {
                                     .rounded_sm()
                                     .text_xs()
                                     .cursor_pointer()
-                                    .bg(rgb(catppuccin::SURFACE1))
-                                    .text_color(rgb(catppuccin::TEXT))
-                                    .hover(|style| style.bg(rgb(catppuccin::SURFACE2)))
+                                    .bg(rgb(theme.surface1))
+                                    .text_color(rgb(theme.text))
+                                    .hover(|style| style.bg(rgb(theme.surface2)))
                                     .child(sort_direction.label())
                                     .on_click(cx.listener(|this, _event, _window, cx| {
-                                        cx.update_global::<AppState, _>(|state, _cx| {
+                                        let needs_reload = cx.update_global::<AppState, _>(|state, _cx| {
+                                            let previous_params = state.api_sort_params();
                                             state.sort_direction = state.sort_direction.toggle();
+                                            state.sort_loaded_repositories();
+                                            state.persist_sort();
+                                            state.api_sort_params() != previous_params
                                         });
-                                        this.reload_repos(cx);
+                                        if needs_reload {
+                                            this.schedule_sort_reload(cx);
+                                        }
                                     })),
                             ),
                     )
                     // Spacer
                     .child(div().flex_1())
-                    // Filter info
+                    // Filter info, following the rows in view as the list scrolls
                     .child(
                         div()
                             .text_sm()
-                            .text_color(rgb(catppuccin::OVERLAY0))
-                            .child(format!("{} repositories", total_count)),
+                            .text_color(rgb(theme.overlay0))
+                            .child(list_summary(visible_rows, visible_count, total_count, is_filtered)),
                     ),
             )
-            // Repository list
+            // Column headers stay put above the scrolling table rows
+            .when(view_mode.is_table() && !loading && !no_stars, |this| {
+                this.child(render_table_header(sort_field, sort_direction, theme, {
+                    let view = view.clone();
+                    move |field, cx| {
+                        view.update(cx, |this, cx| this.sort_by(field, cx)).ok();
+                    }
+                }))
+            })
+            // Repository list. Everything above is outside this container, so
+            // the header and toolbar stay reachable at any scroll position.
             .child(
                 div()
                     .id("repo-list-scroll")
                     .flex_1()
+                    .min_h(MIN_LIST_HEIGHT)
                     .overflow_y_scroll()
-                    .child(if loading {
+                    // Rows are direct children so keyboard navigation can scroll to them
+                    .track_scroll(&self.list_scroll_handle)
+                    .flex()
+                    .flex_col()
+                    .when(loading, |this| {
                         // Loading indicator
-                        div()
-                            .size_full()
-                            .flex()
-                            .items_center()
-                            .justify_center()
-                            .py_8()
-                            .child(
-                                div()
-                                    .text_color(rgb(catppuccin::OVERLAY0))
-                                    .child("Loading...")
-                            )
-                            .into_any_element()
-                    } else {
-                        div()
-                            .flex()
-                            .flex_col()
+                        this.child(
+                            div()
+                                .size_full()
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .py_8()
+                                .child(spinner("list-loading-spinner", theme.overlay0)),
+                        )
+                    })
+                    .when(no_stars, |this| this.child(Self::render_empty_state(theme)))
+                    .when(!loading, |this| {
+                        this
                             .children(
                                 repos_for_render
                                     .into_iter()
-                                    .map(|(repo, is_selected)| {
+                                    .map(|(repo, mut row)| {
+                                        row.is_highlighted = highlighted_index == Some(row.index);
                                         let owner = repo.owner.clone();
                                         let name = repo.name.clone();
                                         let full_name = repo.full_name.clone();
-                                        render_repository_row(repo, is_selected, move |repo_id, cx| {
-                                            cx.update_global::<AppState, _>(|state, _cx| {
-                                                state.pending_action = Some(PendingAction::UnstarSingle(
+                                        let readme_owner = owner.clone();
+                                        let readme_name = name.clone();
+                                        let view = view.clone();
+                                        let on_unstar = move |repo_id, cx: &mut App| {
+                                            Self::request_unstar(
+                                                repo_id,
+                                                owner.clone(),
+                                                name.clone(),
+                                                full_name.clone(),
+                                                cx,
+                                            );
+                                        };
+                                        let on_select = move |index, shift, cx: &mut App| {
+                                            view.update(cx, |this, cx| this.select_row(index, shift, cx))
+                                                .ok();
+                                        };
+                                        if view_mode.is_table() {
+                                            return render_repository_table_row(repo, row, theme, on_unstar, on_select)
+                                                .into_any_element();
+                                        }
+                                        render_repository_row(
+                                            repo,
+                                            row,
+                                            theme,
+                                            on_unstar,
+                                            move |repo_id, cx| {
+                                                Self::toggle_readme(
                                                     repo_id,
-                                                    owner.clone(),
-                                                    name.clone(),
-                                                    full_name.clone(),
-                                                ));
-                                            });
-                                        })
+                                                    readme_owner.clone(),
+                                                    readme_name.clone(),
+                                                    cx,
+                                                );
+                                            },
+                                            |topic, cx| {
+                                                cx.update_global::<AppState, _>(|state, _cx| {
+                                                    state.topic_filter = Some(topic);
+                                                });
+                                            },
+                                            on_select,
+                                        )
+                                        .into_any_element()
                                     }),
                             )
                             // Load More button
@@ -349,24 +1353,27 @@ impl Render for RepositoryListView {
                                                 .px_6()
                                                 .py_2()
                                                 .rounded_md()
-                                                .bg(if loading_more {
-                                                    rgb(catppuccin::SURFACE1)
+                                                .bg(if loading_more || read_only {
+                                                    rgb(theme.surface1)
                                                 } else {
-                                                    rgb(catppuccin::BLUE)
+                                                    rgb(theme.blue)
                                                 })
                                                 .text_sm()
-                                                .text_color(rgb(catppuccin::BASE))
+                                                .text_color(if read_only {
+                                                    rgb(theme.overlay0)
+                                                } else {
+                                                    rgb(theme.base)
+                                                })
                                                 .font_weight(FontWeight::MEDIUM)
-                                                .cursor_pointer()
-                                                .when(!loading_more, |this| {
-                                                    this.hover(|style| style.bg(rgb(catppuccin::SAPPHIRE)))
+                                                .when(!loading_more && !read_only, |this| {
+                                                    this.cursor_pointer()
+                                                        .hover(|style| style.bg(rgb(theme.sapphire)))
                                                 })
-                                                .child(if loading_more {
-                                                    "Loading..."
-                                                } else {
-                                                    "Load More"
+                                                .when(loading_more, |this| {
+                                                    this.child(spinner("load-more-spinner", theme.subtext0))
                                                 })
-                                                .when(!loading_more, |this| {
+                                                .when(!loading_more, |this| this.child("Load More"))
+                                                .when(!loading_more && !read_only, |this| {
                                                     this.on_click(cx.listener(|this, _event, _window, cx| {
                                                         this.load_more(cx);
                                                     }))
@@ -374,19 +1381,169 @@ impl Render for RepositoryListView {
                                         ),
                                 )
                             })
-                            .into_any_element()
                     }),
             )
+            // Undo toast for the most recent unstar
+            .when_some(undo_count, |this, count| {
+                this.child(self.render_undo_toast(count, cx))
+            })
             // Confirmation dialog overlay - must be last child to be on top
             .when_some(pending_action, |this, action| {
-                this.child(Self::render_confirmation_dialog(action, cx))
+                this.child(self.render_confirmation_dialog(action, type_to_confirm_threshold, cx))
+            })
+            .when_some(self.command_palette.as_ref(), |this, (palette, _)| {
+                this.child(palette.clone())
             })
     }
 }
 
 impl RepositoryListView {
+    /// Show the command palette and give it the keyboard
+    fn open_command_palette(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let palette = cx.new(CommandPaletteView::new);
+        let subscription = cx.subscribe_in(&palette, window, |this, _palette, event, window, cx| {
+            this.close_command_palette(window, cx);
+            if let PaletteEvent::Run(command) = event {
+                this.run_command(*command, cx);
+            }
+        });
+        window.focus(&palette.focus_handle(cx));
+        self.command_palette = Some((palette, subscription));
+        cx.notify();
+    }
+
+    fn close_command_palette(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.command_palette = None;
+        self.list_focus_handle.focus(window);
+        cx.notify();
+    }
+
+    /// Run a command picked from the palette through the same handler as
+    /// its button or shortcut
+    fn run_command(&mut self, command: PaletteCommand, cx: &mut Context<Self>) {
+        match command {
+            PaletteCommand::UnstarSelected => self.request_unstar_selected(cx),
+            PaletteCommand::OpenSelected => Self::request_open_selected(cx),
+            PaletteCommand::SelectAll => {
+                if !cx.global::<AppState>().all_filtered_selected() {
+                    self.toggle_select_visible(cx);
+                }
+            }
+            PaletteCommand::DeselectAll => {
+                cx.update_global::<AppState, _>(|state, _cx| state.selection.clear());
+            }
+            PaletteCommand::InvertSelection => {
+                cx.update_global::<AppState, _>(|state, _cx| state.invert_selection_filtered());
+            }
+            PaletteCommand::CopyMarkdown => self.copy_selected_as_markdown(cx),
+            PaletteCommand::Reload => self.reload_repos(cx),
+            PaletteCommand::Export => self.export_repos(cx),
+            PaletteCommand::ToggleFocusMode => {
+                let focus_mode = cx.global::<AppState>().focus_mode;
+                self.set_focus_mode(!focus_mode, cx);
+            }
+            PaletteCommand::ToggleStats => self.show_stats = !self.show_stats,
+            PaletteCommand::ToggleDryRun => {
+                cx.update_global::<AppState, _>(|state, _cx| state.set_dry_run(!state.config.unstar.dry_run));
+            }
+            PaletteCommand::ToggleDensity => {
+                cx.update_global::<AppState, _>(|state, _cx| state.toggle_row_density());
+            }
+            PaletteCommand::ToggleViewMode => {
+                cx.update_global::<AppState, _>(|state, _cx| state.toggle_view_mode());
+            }
+            PaletteCommand::ToggleTheme => {
+                cx.update_global::<AppState, _>(|state, _cx| state.toggle_theme());
+            }
+            PaletteCommand::SortBy(field) => self.sort_by(field, cx),
+            PaletteCommand::FindDuplicates => {
+                cx.update_global::<AppState, _>(|state, _cx| state.duplicates_only = !state.duplicates_only);
+            }
+            PaletteCommand::Diagnostics => open_diagnostics(cx),
+            PaletteCommand::Logout => {
+                cx.update_global::<AppState, _>(|state, _cx| {
+                    state.pending_action = Some(PendingAction::Logout);
+                });
+            }
+        }
+        cx.notify();
+    }
+
+    /// Unstar the selected repositories, asking first unless the batch is
+    /// below the confirmation threshold
+    fn request_unstar_selected(&mut self, cx: &mut Context<Self>) {
+        let state = cx.global::<AppState>();
+        let count = state.selection.count();
+        if count == 0 || state.unstar_progress.is_some() || state.is_read_only() {
+            return;
+        }
+        if state.config.unstar.needs_batch_confirmation(count) {
+            cx.update_global::<AppState, _>(|state, _cx| {
+                state.pending_action = Some(PendingAction::UnstarSelected(count));
+            });
+        } else {
+            self.unstar_selected(cx);
+        }
+    }
+
+    /// Put the selection on the clipboard as a markdown list and briefly
+    /// show that it was copied
+    fn copy_selected_as_markdown(&mut self, cx: &mut Context<Self>) {
+        let markdown = cx.global::<AppState>().selected_as_markdown();
+        if markdown.is_empty() {
+            return;
+        }
+        cx.write_to_clipboard(ClipboardItem::new_string(markdown));
+        // Replacing the task restarts the feedback for a repeat click
+        self.markdown_copied = Some(cx.spawn(async move |view, cx| {
+            cx.background_executor().timer(COPIED_FEEDBACK).await;
+            view.update(cx, |view, cx| {
+                view.markdown_copied = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// Open the selected repositories in the browser, asking first for many
+    fn request_open_selected(cx: &mut App) {
+        let count = cx.global::<AppState>().selection.count();
+        if count > OPEN_SELECTED_CONFIRM_THRESHOLD {
+            cx.update_global::<AppState, _>(|state, _cx| {
+                state.pending_action = Some(PendingAction::OpenSelected(count));
+            });
+        } else if count > 0 {
+            Self::open_selected(cx);
+        }
+    }
+
+    /// Sort by `field`, or flip the direction if it's already the sort field
+    fn sort_by(&mut self, field: SortField, cx: &mut Context<Self>) {
+        let needs_reload = cx.update_global::<AppState, _>(|state, _cx| {
+            let previous_params = state.api_sort_params();
+            if state.sort_field == field {
+                // Toggle direction if same field
+                state.sort_direction = state.sort_direction.toggle();
+            } else {
+                // Change field, reset to ascending
+                state.sort_field = field;
+                state.sort_direction = SortDirection::Asc;
+            }
+
+            // Client-side fields reorder what's loaded; only
+            // refetch when the server order actually changes
+            state.sort_loaded_repositories();
+            state.persist_sort();
+            state.api_sort_params() != previous_params
+        });
+        if needs_reload {
+            self.schedule_sort_reload(cx);
+        }
+    }
+
     /// Reload repositories from page 1 with current sort options
-    fn reload_repos(&mut self, cx: &mut Context<Self>) {
+    pub fn reload_repos(&mut self, cx: &mut Context<Self>) {
         // Check if already loading
         let is_loading = {
             let state = cx.global::<AppState>();
@@ -397,20 +1554,50 @@ impl RepositoryListView {
             return;
         }
 
-        cx.update_global::<AppState, _>(|state, _cx| {
-            state.loading = true;
-            state.repositories.clear();
-            state.selection.clear();
-            state.current_page = 1;
-            state.has_more = true;
-        });
+        // Offline there's no session to reload with, so sign in again
+        if cx.global::<AppState>().offline {
+            cx.update_global::<AppState, _>(|state, _cx| state.retry_load());
+            return;
+        }
+
+        self.start_reload(cx);
+    }
+
+    /// Refetch after the sort settles, so a burst of clicks causes one reload
+    fn schedule_sort_reload(&mut self, cx: &mut Context<Self>) {
+        // Dropping the previous task cancels it
+        self.pending_sort_reload = Some(cx.spawn(async move |view, cx| {
+            cx.background_executor().timer(SORT_RELOAD_DELAY).await;
+            view.update(cx, |view, cx| {
+                view.pending_sort_reload = None;
+                // The new order supersedes whatever is loading in the old one
+                view.start_reload(cx);
+            })
+            .ok();
+        }));
+    }
+
+    /// Clear the list and fetch page 1, ignoring the response if another
+    /// reload starts before it arrives
+    fn start_reload(&mut self, cx: &mut Context<Self>) {
+        // Until the startup load signs in there is nothing to fetch with, and
+        // superseding that load would drop the service it brings
+        if cx.global::<AppState>().github_service.is_none() {
+            return;
+        }
+        let epoch = cx.update_global::<AppState, _>(|state, _cx| state.begin_reload());
         cx.notify();
 
         cx.spawn(async move |_view, cx| {
-            let (service, sort_field, sort_direction) = {
+            let (service, per_page, sort, graphql) = {
                 let result = cx.update(|cx| {
                     let state = cx.global::<AppState>();
-                    (state.github_service.clone(), state.sort_field, state.sort_direction)
+                    (
+                        state.github_service.clone(),
+                        state.config.per_page,
+                        state.api_sort_params(),
+                        state.config.graphql_stars,
+                    )
                 });
                 match result {
                     Ok(v) => v,
@@ -420,22 +1607,31 @@ impl RepositoryListView {
 
             if let Some(service) = service {
                 let result = service
-                    .fetch_starred_repos_page(1, 100, sort_field.api_value(), sort_direction.api_value())
+                    .fetch_starred_page(1, per_page, sort, None, graphql)
                     .await;
 
                 cx.update(|cx| {
                     let state = cx.global_mut::<AppState>();
+                    if !state.is_current_epoch(epoch) {
+                        return;
+                    }
                     state.loading = false;
                     match result {
-                        Ok((repos, has_more)) => {
-                            state.repositories = repos;
+                        Ok(page) => {
+                            state.repositories = page.repos;
                             state.current_page = 1;
-                            state.has_more = has_more;
+                            state.has_more = page.has_more;
+                            state.starred_cursor = page.cursor;
+                            state.last_refreshed = Some(Utc::now());
+                            state.sort_loaded_repositories();
+                            state.check_session_diff();
+                            state.queue_cache_save();
                         }
                         Err(e) => {
                             state.handle_api_error(e, "Failed to reload");
                         }
                     }
+                    Self::refresh_rate_limit(cx);
                 })
                 .ok();
             }
@@ -460,14 +1656,17 @@ impl RepositoryListView {
         cx.notify();
 
         cx.spawn(async move |_view, cx| {
-            let (service, next_page, sort_field, sort_direction) = {
+            let (service, epoch, next_page, per_page, sort, cursor, graphql) = {
                 let result = cx.update(|cx| {
                     let state = cx.global::<AppState>();
                     (
                         state.github_service.clone(),
+                        state.epoch,
                         state.current_page + 1,
-                        state.sort_field,
-                        state.sort_direction,
+                        state.config.per_page,
+                        state.api_sort_params(),
+                        state.starred_cursor.clone(),
+                        state.config.graphql_stars,
                     )
                 });
                 match result {
@@ -478,22 +1677,34 @@ impl RepositoryListView {
 
             if let Some(service) = service {
                 let result = service
-                    .fetch_starred_repos_page(next_page, 100, sort_field.api_value(), sort_direction.api_value())
+                    .fetch_starred_page(next_page, per_page, sort, cursor.as_deref(), graphql)
                     .await;
 
                 cx.update(|cx| {
                     let state = cx.global_mut::<AppState>();
+                    // A reload replaced the list this page belonged to
+                    if !state.is_current_epoch(epoch) {
+                        return;
+                    }
                     state.loading_more = false;
                     match result {
-                        Ok((repos, has_more)) => {
-                            state.repositories.extend(repos);
+                        Ok(page) => {
+                            state.merge_new_repos(page.repos);
                             state.current_page = next_page;
-                            state.has_more = has_more;
+                            state.has_more = page.has_more;
+                            state.starred_cursor = page.cursor;
+                            state.last_refreshed = Some(Utc::now());
+                            state.sort_loaded_repositories();
+                            state.check_session_diff();
+                            state.queue_cache_save();
                         }
                         Err(e) => {
+                            // Don't let `load_all` keep retrying a failing request
+                            state.load_all_paused = true;
                             state.handle_api_error(e, "Failed to load more");
                         }
                     }
+                    Self::refresh_rate_limit(cx);
                 })
                 .ok();
             }
@@ -501,66 +1712,1946 @@ impl RepositoryListView {
         .detach();
     }
 
-    fn toggle_select_all(&mut self, cx: &mut Context<Self>) {
-        cx.update_global::<AppState, _>(|state, _cx| {
-            if state.selection.count() == state.repositories.len() {
-                state.selection.clear();
-            } else {
-                state.selection.select_all(&state.repositories);
-            }
-        });
-        cx.notify();
-    }
+    /// Queue the next background page load when `load_all` is on
+    fn maybe_auto_load(&mut self, cx: &mut Context<Self>) {
+        if self.auto_load_scheduled || !cx.global::<AppState>().should_auto_load() {
+            return;
+        }
 
-    fn unstar_selected(&mut self, cx: &mut Context<Self>) {
-        let (repos_to_unstar, ids_to_remove): (Vec<_>, Vec<_>) = {
-            let state = cx.global::<AppState>();
-            state
-                .repositories
-                .iter()
-                .filter(|r| state.selection.is_selected(r.id))
-                .map(|r| ((r.owner.clone(), r.name.clone()), r.id))
-                .unzip()
-        };
+        self.auto_load_scheduled = true;
+        let cancel = cx.global::<AppState>().load_all_cancel.clone();
+        cx.spawn(async move |view, cx| {
+            // Pace requests so loading everything doesn't hammer the API
+            cx.background_executor().timer(AUTO_LOAD_DELAY).await;
+            view.update(cx, |view, cx| {
+                view.auto_load_scheduled = false;
+                // "Stop loading" may have been clicked during the pause
+                if !cancel.load(Ordering::Relaxed) && cx.global::<AppState>().should_auto_load() {
+                    view.load_more(cx);
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
 
-        if repos_to_unstar.is_empty() {
+    /// Keep the relative time in "Refreshed 5 minutes ago" current while
+    /// it's `shown`; nothing else redraws the header while the app sits idle
+    fn tick_refreshed_label(&mut self, shown: bool, cx: &mut Context<Self>) {
+        if !shown {
+            self.refreshed_label_ticker = None;
+            return;
+        }
+        if self.refreshed_label_ticker.is_some() {
             return;
         }
+        self.refreshed_label_ticker = Some(cx.spawn(async move |view, cx| loop {
+            cx.background_executor().timer(REFRESHED_LABEL_INTERVAL).await;
+            if view.update(cx, |_view, cx| cx.notify()).is_err() {
+                break;
+            }
+        }));
+    }
 
-        cx.spawn(async move |_view, cx| {
-            let service = cx
-                .update(|cx| cx.global::<AppState>().github_service.clone())
-                .ok()
-                .flatten();
+    /// Fetch activity for `rows` once scrolling has settled on them
+    fn schedule_activity_fetch(&mut self, rows: Option<Range<usize>>, cx: &mut Context<Self>) {
+        if rows == self.activity_rows {
+            // Rows in view that became fetchable without a scroll, e.g. once
+            // signed in or after the rate limit reset
+            let ready = rows.is_some_and(|rows| !cx.global::<AppState>().activity_to_fetch(rows).is_empty());
+            if ready && self.pending_activity_fetch.is_none() {
+                self.fetch_activity(cx);
+            }
+            return;
+        }
+        self.activity_rows = rows;
+        if self.activity_rows.is_none() {
+            self.pending_activity_fetch = None;
+            return;
+        }
+        // Dropping the previous task cancels it
+        self.pending_activity_fetch = Some(cx.spawn(async move |view, cx| {
+            cx.background_executor().timer(ACTIVITY_FETCH_DELAY).await;
+            view.update(cx, |view, cx| {
+                view.pending_activity_fetch = None;
+                view.fetch_activity(cx);
+            })
+            .ok();
+        }));
+    }
 
-            if let Some(service) = service {
-                let results = service.unstar_repos(&repos_to_unstar).await;
+    /// Fetch the last commit and release of the repositories in view that
+    /// haven't been asked for yet, one at a time. Rows scrolled away before
+    /// their turn are skipped, and fetching stops if GitHub rate limits it.
+    fn fetch_activity(&mut self, cx: &mut Context<Self>) {
+        let Some(rows) = self.activity_rows.clone() else {
+            return;
+        };
+        let (epoch, service, claimed) = cx.update_global::<AppState, _>(|state, _cx| {
+            (state.epoch, state.github_service.clone(), state.claim_activity_fetches(rows))
+        });
+        let Some(service) = service.filter(|_| !claimed.is_empty()) else {
+            return;
+        };
 
-                // Check for token expiration
-                let token_expired = results
-                    .iter()
-                    .any(|(_, _, result)| result.as_ref().err().map(is_token_expired_error).unwrap_or(false));
+        cx.spawn(async move |view, cx| {
+            let mut claimed = claimed.into_iter();
+            for (repo_id, owner, name) in claimed.by_ref() {
+                let in_view = view
+                    .update(cx, |view, cx| {
+                        let state = cx.global_mut::<AppState>();
+                        let in_view = state.is_current_epoch(epoch)
+                            && view.activity_rows.clone().is_some_and(|rows| state.is_in_rows(repo_id, rows));
+                        if !in_view {
+                            state.release_activity_fetch(repo_id);
+                        }
+                        in_view
+                    })
+                    .unwrap_or(false);
+                if !in_view {
+                    continue;
+                }
 
-                if token_expired {
-                    cx.update(|cx| {
+                let result = service.fetch_repo_activity(&owner, &name).await;
+                let keep_going = cx
+                    .update(|cx| {
                         let state = cx.global_mut::<AppState>();
-                        let _ = state.logout();
-                        state.error = Some("Token expired. Please login again.".to_string());
+                        if !state.is_current_epoch(epoch) {
+                            return false;
+                        }
+                        match result {
+                            Ok(activity) => {
+                                state.repo_activity.insert(repo_id, Some(activity));
+                                true
+                            }
+                            Err(e) if is_token_expired_error(&e) => {
+                                state.handle_api_error(e, "Failed to fetch activity");
+                                false
+                            }
+                            Err(e) if is_rate_limit_error(&e) => {
+                                tracing::warn!("Rate limited while fetching activity, stopping: {}", e);
+                                state.release_activity_fetch(repo_id);
+                                false
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to fetch activity of {}/{}: {}", owner, name, e);
+                                true
+                            }
+                        }
                     })
-                    .ok();
-                    return;
+                    .unwrap_or(false);
+                if !keep_going {
+                    break;
                 }
+            }
 
-                let success_ids: Vec<u64> = results
-                    .iter()
-                    .zip(ids_to_remove.iter())
-                    .filter(|((_, _, result), _)| result.is_ok())
-                    .map(|(_, id)| *id)
+            // Hand back what wasn't fetched, and update the remaining count
+            // the reserve is measured against
+            cx.update(|cx| {
+                let state = cx.global_mut::<AppState>();
+                if state.is_current_epoch(epoch) {
+                    for (repo_id, _, _) in claimed {
+                        state.release_activity_fetch(repo_id);
+                    }
+                    Self::refresh_rate_limit(cx);
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Once the rate limit is used up, fetch it again when it resets so the
+    /// list leaves read-only mode without a restart
+    fn maybe_recheck_rate_limit(&mut self, cx: &mut Context<Self>) {
+        let Some(reset_at) = cx
+            .global::<AppState>()
+            .rate_limit
+            .as_ref()
+            .filter(|rate_limit| rate_limit.is_exhausted())
+            .map(|rate_limit| rate_limit.reset_at)
+        else {
+            return;
+        };
+        if self.rate_limit_recheck == Some(reset_at) {
+            return;
+        }
+
+        self.rate_limit_recheck = Some(reset_at);
+        // A second of slack so GitHub has reset by the time we ask
+        let wait = (reset_at - Utc::now()).to_std().unwrap_or_default() + Duration::from_secs(1);
+        cx.spawn(async move |view, cx| {
+            cx.background_executor().timer(wait).await;
+            view.update(cx, |view, cx| {
+                view.rate_limit_recheck = None;
+                Self::refresh_rate_limit(cx);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Write the list to the cache on the background executor once
+    /// `CACHE_SAVE_DELAY` has passed, one write at a time
+    fn maybe_save_cache(&mut self, cx: &mut Context<Self>) {
+        if self.pending_cache_save.is_some() || !cx.global::<AppState>().cache_dirty {
+            return;
+        }
+        self.pending_cache_save = Some(cx.spawn(async move |view, cx| {
+            cx.background_executor().timer(CACHE_SAVE_DELAY).await;
+            let write = cx
+                .update(|cx| cx.update_global::<AppState, _>(|state, _cx| state.take_cache_write()))
+                .ok()
+                .flatten();
+            if let Some(write) = write {
+                cx.background_executor().spawn(async move { write() }).await;
+            }
+            view.update(cx, |view, cx| {
+                view.pending_cache_save = None;
+                // Changes made while this one was written
+                view.maybe_save_cache(cx);
+            })
+            .ok();
+        }));
+    }
+
+    /// Fetch the latest API rate limit in the background
+    fn refresh_rate_limit(cx: &mut App) {
+        let state = cx.global::<AppState>();
+        let epoch = state.epoch;
+        let Some(service) = state.github_service.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |cx| match service.get_rate_limit().await {
+            Ok(rate_limit) => {
+                cx.update(|cx| {
+                    let state = cx.global_mut::<AppState>();
+                    if state.is_current_epoch(epoch) {
+                        state.rate_limit = Some(rate_limit);
+                    }
+                })
+                .ok();
+            }
+            Err(e) => tracing::warn!("Failed to fetch rate limit: {}", e),
+        })
+        .detach();
+    }
+
+    /// Expand or collapse a repository's README preview, fetching it once per session
+    fn toggle_readme(repo_id: u64, owner: String, name: String, cx: &mut App) {
+        let needs_fetch = cx.update_global::<AppState, _>(|state, _cx| {
+            if state.expanded_readme == Some(repo_id) {
+                state.expanded_readme = None;
+                return false;
+            }
+
+            state.expanded_readme = Some(repo_id);
+            // Retry previously failed fetches, reuse everything else
+            match state.readme_cache.get(&repo_id) {
+                None | Some(ReadmePreview::Failed(_)) => {
+                    state.readme_cache.insert(repo_id, ReadmePreview::Loading);
+                    true
+                }
+                Some(_) => false,
+            }
+        });
+
+        if !needs_fetch {
+            return;
+        }
+
+        let epoch = cx.global::<AppState>().epoch;
+        cx.spawn(async move |cx| {
+            let service = cx
+                .update(|cx| cx.global::<AppState>().github_service.clone())
+                .ok()
+                .flatten();
+
+            if let Some(service) = service {
+                let result = service.fetch_readme(&owner, &name).await;
+
+                cx.update(|cx| {
+                    let state = cx.global_mut::<AppState>();
+                    if !state.is_current_epoch(epoch) {
+                        return;
+                    }
+                    let preview = match result {
+                        Ok(Some(content)) => ReadmePreview::Loaded(content),
+                        Ok(None) => ReadmePreview::Missing,
+                        Err(e) if is_token_expired_error(&e) => {
+                            state.handle_api_error(e, "Failed to load README");
+                            return;
+                        }
+                        Err(e) => ReadmePreview::Failed(format!("Failed to load README: {}", e)),
+                    };
+                    state.readme_cache.insert(repo_id, preview);
+                })
+                .ok();
+            }
+        })
+        .detach();
+    }
+
+    /// List shortcuts: arrows move the highlight, Space selects, `u` unstars,
+    /// `a` toggles select all, `f` toggles focus mode, `/` focuses search
+    fn handle_list_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let key = event.keystroke.key.as_str();
+        let modifiers = &event.keystroke.modifiers;
+
+        // Cmd/Ctrl+K works from the search box too, but not over a dialog
+        if key == "k"
+            && (modifiers.platform || modifiers.control)
+            && self.command_palette.is_none()
+            && cx.global::<AppState>().pending_action.is_none()
+        {
+            self.open_command_palette(window, cx);
+            cx.stop_propagation();
+            return;
+        }
+
+        // Keys typed into the search, star, or confirm inputs bubble up here too
+        if !self.list_focus_handle.is_focused(window) {
+            return;
+        }
+
+        if modifiers.platform || modifiers.control || modifiers.alt {
+            return;
+        }
+
+        // The open dialog handles its own keys
+        let state = cx.global::<AppState>();
+        if state.pending_action.is_some() {
+            return;
+        }
+
+        let highlighted = self
+            .highlighted_index
+            .and_then(|index| state.visible_repositories().get(index).map(|r| (*r).clone()));
+        let visible_count = state.visible_repositories().len();
+        let read_only = state.is_read_only();
+        let focus_mode = state.focus_mode;
+        let pinned = highlighted.as_ref().is_some_and(|r| state.is_pinned(r.id));
+
+        match key {
+            "down" | "up" if visible_count > 0 => {
+                let index = match (self.highlighted_index, key) {
+                    (None, _) => 0,
+                    (Some(index), "down") => (index + 1).min(visible_count - 1),
+                    (Some(index), _) => index.saturating_sub(1),
+                };
+                self.highlighted_index = Some(index);
+                self.list_scroll_handle.scroll_to_item(index);
+            }
+            "space" => {
+                if let Some(repo) = highlighted {
+                    cx.update_global::<AppState, _>(|state, _cx| {
+                        state.selection.toggle(repo.id);
+                    });
+                }
+            }
+            // Pinned keepers still ask first, even in focus mode
+            "u" if !read_only && focus_mode && !pinned => {
+                if let (Some(repo), Some(index)) = (highlighted, self.highlighted_index) {
+                    Self::do_unstar_repo(repo.id, repo.owner, repo.name, cx);
+                    // The row leaves the list, so the next one moves up under the
+                    // highlight; a dry run keeps it, so step past it instead
+                    if cx.global::<AppState>().config.unstar.dry_run {
+                        let next = (index + 1).min(visible_count - 1);
+                        self.highlighted_index = Some(next);
+                        self.list_scroll_handle.scroll_to_item(next);
+                    }
+                }
+            }
+            "u" if !read_only => {
+                if let Some(repo) = highlighted {
+                    Self::request_unstar(repo.id, repo.owner, repo.name, repo.full_name, cx);
+                }
+            }
+            "f" => self.set_focus_mode(!focus_mode, cx),
+            "escape" if focus_mode => self.set_focus_mode(false, cx),
+            "a" => self.toggle_select_visible(cx),
+            // Match the typed character so layouts where `/` needs Shift work too
+            _ if event.keystroke.key_char.as_deref() == Some("/") => self.search_focus_handle.focus(window),
+            _ => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    /// Enter or leave focus mode. Leaving gives the usual undo window for
+    /// everything unstarred meanwhile, then confirmations apply again.
+    fn set_focus_mode(&mut self, on: bool, cx: &mut Context<Self>) {
+        cx.update_global::<AppState, _>(|state, _cx| state.set_focus_mode(on));
+        if on {
+            // Start on the first row `u` unstars straight away, past pinned ones
+            let first = cx.global::<AppState>().next_unpinned_index(0).unwrap_or(0);
+            self.highlighted_index.get_or_insert(first);
+        } else {
+            Self::schedule_undo_expiry(cx);
+        }
+    }
+
+    /// Toggle a row's checkbox, or with shift held select the range from
+    /// the previously clicked row
+    fn select_row(&mut self, index: usize, shift: bool, cx: &mut Context<Self>) {
+        let anchor = self.last_clicked_index.filter(|_| shift);
+        cx.update_global::<AppState, _>(|state, _cx| match anchor {
+            Some(anchor) => state.select_filtered_range(anchor, index),
+            None => {
+                let id = state.visible_repositories().get(index).map(|r| r.id);
+                if let Some(id) = id {
+                    state.selection.toggle(id);
+                }
+            }
+        });
+        self.last_clicked_index = Some(index);
+        cx.notify();
+    }
+
+    /// 1-based first and last rows scrolled into view, from the last layout.
+    /// Scrolling re-renders the view, so this stays current.
+    fn visible_rows(&self, loading: bool, visible_count: usize) -> Option<(usize, usize)> {
+        // Not laid out yet, or the list is showing a spinner instead of rows
+        if loading || visible_count == 0 || self.list_scroll_handle.bounds_for_item(0).is_none() {
+            return None;
+        }
+        let last_row = visible_count - 1;
+        let top = self.list_scroll_handle.top_item().min(last_row);
+        let bottom = self.list_scroll_handle.bottom_item().clamp(top, last_row);
+        Some((top + 1, bottom + 1))
+    }
+
+    /// Select only the repositories matching the current filters and search
+    fn toggle_select_visible(&mut self, cx: &mut Context<Self>) {
+        self.select_scope = SelectScope::Visible;
+        cx.update_global::<AppState, _>(|state, _cx| {
+            state.toggle_select_all_filtered();
+        });
+        cx.notify();
+    }
+
+    /// Select every fetched repository, including ones the filters hide
+    fn toggle_select_all_loaded(&mut self, cx: &mut Context<Self>) {
+        self.select_scope = SelectScope::AllLoaded;
+        cx.update_global::<AppState, _>(|state, _cx| {
+            state.toggle_select_all_loaded();
+        });
+        cx.notify();
+    }
+
+    fn render_search_box(&self, query: &str, is_focused: bool, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let focus_handle = self.search_focus_handle.clone();
+
+        div()
+            .id("search-box")
+            .w(px(240.))
+            .h(px(28.))
+            .px_2()
+            .flex()
+            .items_center()
+            .rounded_md()
+            .bg(rgb(theme.base))
+            .border_1()
+            .border_color(if is_focused {
+                rgb(theme.blue)
+            } else {
+                rgb(theme.surface1)
+            })
+            .cursor_text()
+            .track_focus(&self.search_focus_handle)
+            .on_click(move |_event, window, _cx| {
+                focus_handle.focus(window);
+            })
+            .on_key_down(cx.listener(|this, event, window, cx| {
+                this.handle_search_key_down(event, window, cx);
+            }))
+            .text_sm()
+            .child(if query.is_empty() && !is_focused {
+                div().text_color(rgb(theme.overlay0)).child("Search name, topics, language:rust")
+            } else if is_focused {
+                div().text_color(rgb(theme.text)).child(format!("{}|", query))
+            } else {
+                div().text_color(rgb(theme.text)).child(query.to_string())
+            })
+    }
+
+    /// "Select by regex" button, or while open, the pattern input with its
+    /// match count or compile error
+    fn render_regex_select(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let Some(input) = self.regex_input.as_ref() else {
+            return div().child(
+                div()
+                    .id("select-by-regex")
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .bg(rgb(theme.surface1))
+                    .text_color(rgb(theme.subtext0))
+                    .hover(|style| style.bg(rgb(theme.surface2)))
+                    .child("Select by regex")
+                    .on_click(cx.listener(|this, _event, window, cx| {
+                        this.regex_input = Some(TextInput::new());
+                        this.regex_preview = None;
+                        this.regex_focus_handle.focus(window);
+                        cx.notify();
+                    })),
+            );
+        };
+
+        let focus_handle = self.regex_focus_handle.clone();
+        let (before_cursor, after_cursor) = input.split_at_cursor();
+        let match_count = match &self.regex_preview {
+            Some(Ok(count)) => *count,
+            _ => 0,
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .child(
+                div()
+                    .id("regex-input")
+                    .w(px(180.))
+                    .h(px(28.))
+                    .px_2()
+                    .flex()
+                    .items_center()
+                    .rounded_md()
+                    .bg(rgb(theme.base))
+                    .border_1()
+                    .border_color(if matches!(self.regex_preview, Some(Err(_))) {
+                        rgb(theme.red)
+                    } else {
+                        rgb(theme.blue)
+                    })
+                    .cursor_text()
+                    .track_focus(&self.regex_focus_handle)
+                    .on_click(move |_event, window, _cx| {
+                        focus_handle.focus(window);
+                    })
+                    .on_key_down(cx.listener(|this, event, window, cx| {
+                        this.handle_regex_key_down(event, window, cx);
+                    }))
+                    .text_sm()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .child(if input.is_empty() {
+                        div().text_color(rgb(theme.overlay0)).child("|^awesome-")
+                    } else {
+                        div()
+                            .text_color(rgb(theme.text))
+                            .child(format!("{}|{}", before_cursor, after_cursor))
+                    }),
+            )
+            .when_some(self.regex_preview.clone(), |this, preview| {
+                this.child(match preview {
+                    Ok(count) => div()
+                        .text_xs()
+                        .text_color(rgb(theme.subtext0))
+                        .child(if count == 1 {
+                            "1 match".to_string()
+                        } else {
+                            format!("{} matches", count)
+                        }),
+                    Err(err) => div().text_xs().text_color(rgb(theme.red)).child(err),
+                })
+            })
+            .child(
+                div()
+                    .id("regex-select-btn")
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .bg(rgb(theme.surface1))
+                    .text_color(if match_count > 0 {
+                        rgb(theme.blue)
+                    } else {
+                        rgb(theme.overlay0)
+                    })
+                    .child("Select")
+                    .when(match_count > 0, |this| {
+                        this.cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .on_click(cx.listener(|this, _event, window, cx| {
+                                this.select_by_regex(window, cx);
+                            }))
+                    }),
+            )
+            .child(
+                div()
+                    .id("regex-cancel-btn")
+                    .px_1()
+                    .text_xs()
+                    .text_color(rgb(theme.overlay0))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(rgb(theme.text)))
+                    .child("✕")
+                    .on_click(cx.listener(|this, _event, window, cx| {
+                        this.close_regex_select(window, cx);
+                    })),
+            )
+    }
+
+    fn handle_regex_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let key = event.keystroke.key.as_str();
+        let modifiers = &event.keystroke.modifiers;
+        let Some(input) = self.regex_input.as_mut() else {
+            return;
+        };
+
+        match key {
+            "escape" => {
+                self.close_regex_select(window, cx);
+                return;
+            }
+            "enter" => {
+                self.select_by_regex(window, cx);
+                return;
+            }
+            _ if input.handle_key(key) => {}
+            _ if (modifiers.platform || modifiers.control) && key == "v" => {
+                if let Some(text) = cx.read_from_clipboard().and_then(|clipboard| clipboard.text()) {
+                    input.insert(text.trim());
+                }
+            }
+            _ => match &event.keystroke.key_char {
+                Some(ch) if !modifiers.platform && !modifiers.control => {
+                    let text: String = ch.chars().filter(|c| !c.is_control()).collect();
+                    input.insert(&text);
+                }
+                _ => return,
+            },
+        }
+
+        let pattern = input.value();
+        self.regex_preview = (!pattern.is_empty()).then(|| {
+            cx.global::<AppState>()
+                .count_regex_matches(pattern)
+                .map_err(|e| e.to_string())
+        });
+        cx.notify();
+    }
+
+    /// Select what the typed pattern matches and close the input
+    fn select_by_regex(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pattern) = self.regex_input.as_ref().map(|input| input.value().to_string()) else {
+            return;
+        };
+        if pattern.is_empty() {
+            return;
+        }
+        match cx.update_global::<AppState, _>(|state, _cx| state.select_by_regex(&pattern)) {
+            Ok(count) => {
+                tracing::info!("Selected {} repositories matching /{}/", count, pattern);
+                self.close_regex_select(window, cx);
+            }
+            Err(e) => {
+                self.regex_preview = Some(Err(e.to_string()));
+                cx.notify();
+            }
+        }
+    }
+
+    fn close_regex_select(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.regex_input = None;
+        self.regex_preview = None;
+        self.list_focus_handle.focus(window);
+        cx.notify();
+    }
+
+    /// Switch the search box between substring and fuzzy matching
+    fn render_fuzzy_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let fuzzy = cx.global::<AppState>().config.fuzzy_search;
+
+        div()
+            .id("fuzzy-search-toggle")
+            .px_2()
+            .py_1()
+            .rounded_sm()
+            .text_xs()
+            .cursor_pointer()
+            .bg(if fuzzy {
+                rgb(theme.blue)
+            } else {
+                rgb(theme.surface1)
+            })
+            .text_color(if fuzzy {
+                rgb(theme.base)
+            } else {
+                rgb(theme.subtext0)
+            })
+            .when(!fuzzy, |this| {
+                this.hover(|style| style.bg(rgb(theme.surface2)))
+            })
+            .child("Fuzzy")
+            .on_click(cx.listener(|_this, _event, _window, cx| {
+                cx.update_global::<AppState, _>(|state, _cx| {
+                    state.set_fuzzy_search(!state.config.fuzzy_search);
+                });
+            }))
+    }
+
+    /// Searching all stars goes to the API, unlike the search box, so it's a
+    /// separate button that says so
+    fn render_search_all_button(&self, query: &str, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let searching = self.searching_all;
+        let found = self
+            .search_all_result
+            .as_ref()
+            .filter(|(searched, _)| searched == query.trim())
+            .map(|(_, count)| *count);
+
+        div()
+            .id("search-all-btn")
+            .px_2()
+            .py_1()
+            .flex()
+            .items_center()
+            .gap_2()
+            .rounded_md()
+            .bg(rgb(theme.surface1))
+            .text_xs()
+            .text_color(rgb(theme.subtext0))
+            .when(searching, |this| {
+                this.child(spinner("search-all-spinner", theme.subtext0))
+                    .child("Searching all stars...")
+            })
+            .when(!searching, |this| {
+                this.cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.surface2)))
+                    .child(match found {
+                        Some(count) => format!("{} found in all stars · Search again (API)", count),
+                        None => "Search all stars (API)".to_string(),
+                    })
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.search_all(cx);
+                    }))
+            })
+    }
+
+    /// Search every starred repo on GitHub, not just the loaded pages, and
+    /// merge the matches into the list so the search box shows them
+    fn search_all(&mut self, cx: &mut Context<Self>) {
+        let state = cx.global::<AppState>();
+        let search = state.search_query.trim().to_string();
+        let Some(service) = state.github_service.clone() else {
+            return;
+        };
+        if search.is_empty() || self.searching_all {
+            return;
+        }
+
+        self.searching_all = true;
+        let epoch = state.epoch;
+        cx.notify();
+
+        cx.spawn(async move |view, cx| {
+            let (query, language) = split_language_filter(&search);
+            let result = service.search_starred(&query, language.as_deref()).await;
+
+            view.update(cx, |view, cx| {
+                view.searching_all = false;
+                if !cx.global::<AppState>().is_current_epoch(epoch) {
+                    cx.notify();
+                    return;
+                }
+                match result {
+                    Ok(repos) => {
+                        view.search_all_result = Some((search, repos.len()));
+                        cx.update_global::<AppState, _>(|state, _cx| {
+                            state.merge_new_repos(repos);
+                            state.sort_loaded_repositories();
+                        });
+                    }
+                    Err(e) => {
+                        tracing::error!("Search API error: {}", e);
+                        cx.update_global::<AppState, _>(|state, _cx| {
+                            state.handle_api_error(e, "Failed to search all stars");
+                        });
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_star_input(&self, is_focused: bool, read_only: bool, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let focus_handle = self.star_focus_handle.clone();
+        let input = self.star_input.clone();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_2()
+            .when_some(self.star_error.clone(), |this, err| {
+                this.child(div().text_xs().text_color(rgb(theme.red)).child(err))
+            })
+            .child(
+                div()
+                    .id("star-input")
+                    .w(px(220.))
+                    .h(px(28.))
+                    .px_2()
+                    .flex()
+                    .items_center()
+                    .rounded_md()
+                    .bg(rgb(theme.base))
+                    .border_1()
+                    .border_color(if is_focused {
+                        rgb(theme.blue)
+                    } else {
+                        rgb(theme.surface1)
+                    })
+                    .cursor_text()
+                    .track_focus(&self.star_focus_handle)
+                    .on_click(move |_event, window, _cx| {
+                        focus_handle.focus(window);
+                    })
+                    .on_key_down(cx.listener(|this, event, _window, cx| {
+                        this.handle_star_key_down(event, cx);
+                    }))
+                    .text_sm()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .child(if self.starring {
+                        div().text_color(rgb(theme.overlay0)).child("Starring...")
+                    } else if input.is_empty() && !is_focused {
+                        div().text_color(rgb(theme.overlay0)).child("Star owner/repo or URL")
+                    } else if is_focused {
+                        div().text_color(rgb(theme.text)).child(format!("{}|", input))
+                    } else {
+                        div().text_color(rgb(theme.text)).child(input)
+                    }),
+            )
+            .child(
+                div()
+                    .id("star-btn")
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .bg(rgb(theme.surface1))
+                    .text_sm()
+                    .text_color(if read_only || self.starring {
+                        rgb(theme.overlay0)
+                    } else {
+                        rgb(theme.yellow)
+                    })
+                    .child("Star")
+                    .when(!read_only && !self.starring, |this| {
+                        this.cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.star_from_input(cx);
+                            }))
+                    }),
+            )
+    }
+
+    fn handle_star_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        if self.starring {
+            return;
+        }
+
+        let key = &event.keystroke.key;
+        let modifiers = &event.keystroke.modifiers;
+
+        if key == "backspace" {
+            self.star_input.pop();
+        } else if key == "escape" {
+            self.star_input.clear();
+            self.star_error = None;
+        } else if key == "enter" {
+            if !cx.global::<AppState>().is_read_only() {
+                self.star_from_input(cx);
+            }
+            return;
+        } else if (modifiers.platform || modifiers.control) && key == "v" {
+            if let Some(text) = cx.read_from_clipboard().and_then(|clipboard| clipboard.text()) {
+                self.star_input.extend(text.chars().filter(|c| !c.is_whitespace()));
+            }
+        } else if let Some(ch) = &event.keystroke.key_char
+            && !modifiers.platform
+            && !modifiers.control
+        {
+            self.star_input.extend(ch.chars().filter(|c| !c.is_control() && !c.is_whitespace()));
+        }
+        cx.notify();
+    }
+
+    /// Star the repo named in the star input and prepend it to the list
+    fn star_from_input(&mut self, cx: &mut Context<Self>) {
+        let Some((owner, name)) = parse_repo_reference(&self.star_input) else {
+            self.star_error = Some("Enter owner/repo or a GitHub URL".to_string());
+            cx.notify();
+            return;
+        };
+
+        self.starring = true;
+        self.star_error = None;
+        cx.notify();
+
+        let epoch = cx.global::<AppState>().epoch;
+        cx.spawn(async move |view, cx| {
+            let service = cx
+                .update(|cx| cx.global::<AppState>().github_service.clone())
+                .ok()
+                .flatten();
+
+            let Some(service) = service else {
+                view.update(cx, |view, cx| {
+                    view.starring = false;
+                    cx.notify();
+                })
+                .ok();
+                return;
+            };
+
+            let result = async {
+                service.star_repo(&owner, &name).await?;
+                service.fetch_repo(&owner, &name).await
+            }
+            .await;
+
+            view.update(cx, |view, cx| {
+                view.starring = false;
+                if !cx.global::<AppState>().is_current_epoch(epoch) {
+                    cx.notify();
+                    return;
+                }
+                match result {
+                    Ok(repo) => {
+                        view.star_input.clear();
+                        cx.update_global::<AppState, _>(|state, _cx| {
+                            state.add_starred_repo(repo);
+                        });
+                    }
+                    Err(e) if is_token_expired_error(&e) => {
+                        cx.update_global::<AppState, _>(|state, _cx| {
+                            state.handle_api_error(e, "Failed to star");
+                        });
+                    }
+                    Err(e) => {
+                        tracing::error!("Star API error: {}", e);
+                        view.star_error = Some(e.to_string());
+                    }
+                }
+                Self::refresh_rate_limit(cx);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_license_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = cx.global::<AppState>();
+        let theme = Theme::for_mode(state.config.theme);
+        let active = state.license_filter.clone();
+        let options = if self.license_menu_open { state.license_options() } else { Vec::new() };
+
+        div()
+            .flex()
+            .flex_col()
+            .items_end()
+            .child(
+                div()
+                    .id("license-filter-btn")
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .bg(if active.is_some() {
+                        rgb(theme.blue)
+                    } else if self.license_menu_open {
+                        rgb(theme.surface2)
+                    } else {
+                        rgb(theme.surface1)
+                    })
+                    .text_color(if active.is_some() {
+                        rgb(theme.base)
+                    } else {
+                        rgb(theme.subtext0)
+                    })
+                    .when(active.is_none(), |this| {
+                        this.hover(|style| style.bg(rgb(theme.surface2)))
+                    })
+                    .child(match &active {
+                        Some(license) => format!("License: {} ▾", license),
+                        None => "License ▾".to_string(),
+                    })
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.license_menu_open = !this.license_menu_open;
+                        cx.notify();
+                    })),
+            )
+            // Painted last so it isn't covered by the list below
+            .when(self.license_menu_open, |this| {
+                let choices = std::iter::once(None).chain(options.into_iter().map(Some));
+                this.child(deferred(
+                    anchored()
+                        .anchor(Corner::TopRight)
+                        .snap_to_window_with_margin(px(8.))
+                        .child(
+                            div()
+                                .id("license-menu")
+                                .min_w(px(220.))
+                                .max_h(px(320.))
+                                .overflow_y_scroll()
+                                .py_1()
+                                .flex()
+                                .flex_col()
+                                .rounded_md()
+                                .bg(rgb(theme.surface0))
+                                .border_1()
+                                .border_color(rgb(theme.surface2))
+                                .shadow_md()
+                                .on_mouse_down_out(cx.listener(|this, _event, _window, cx| {
+                                    this.license_menu_open = false;
+                                    cx.notify();
+                                }))
+                                .children(choices.enumerate().map(|(index, license)| {
+                                    let is_active = license == active;
+                                    div()
+                                        .id(ElementId::Name(format!("license-{}", index).into()))
+                                        .px_3()
+                                        .py_1()
+                                        .text_xs()
+                                        .text_color(if is_active {
+                                            rgb(theme.blue)
+                                        } else {
+                                            rgb(theme.text)
+                                        })
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(rgb(theme.surface1)))
+                                        .child(license.clone().unwrap_or_else(|| "Any license".to_string()))
+                                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                                            this.license_menu_open = false;
+                                            let license = license.clone();
+                                            cx.update_global::<AppState, _>(|state, _cx| {
+                                                state.license_filter = license;
+                                            });
+                                        }))
+                                })),
+                        ),
+                ))
+            })
+    }
+
+    fn render_presets(&self, window: &Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let presets = cx.global::<AppState>().config.presets.clone();
+        let name_focused = self.preset_name_focus_handle.is_focused(window);
+        let can_save = !self.preset_name.trim().is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .items_end()
+            .child(
+                div()
+                    .id("presets-btn")
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .cursor_pointer()
+                    .bg(if self.preset_menu_open {
+                        rgb(theme.surface2)
+                    } else {
+                        rgb(theme.surface1)
+                    })
+                    .text_color(rgb(theme.subtext0))
+                    .hover(|style| style.bg(rgb(theme.surface2)))
+                    .child("Presets ▾")
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.preset_menu_open = !this.preset_menu_open;
+                        cx.notify();
+                    })),
+            )
+            // Painted last so it isn't covered by the list below
+            .when(self.preset_menu_open, |this| {
+                let focus_handle = self.preset_name_focus_handle.clone();
+                this.child(deferred(
+                    anchored()
+                        .anchor(Corner::TopRight)
+                        .snap_to_window_with_margin(px(8.))
+                        .child(
+                            div()
+                                .id("presets-menu")
+                                .min_w(px(220.))
+                                .py_1()
+                                .flex()
+                                .flex_col()
+                                .rounded_md()
+                                .bg(rgb(theme.surface0))
+                                .border_1()
+                                .border_color(rgb(theme.surface2))
+                                .shadow_md()
+                                .on_mouse_down_out(cx.listener(|this, _event, _window, cx| {
+                                    this.preset_menu_open = false;
+                                    cx.notify();
+                                }))
+                                .when(presets.is_empty(), |this| {
+                                    this.child(
+                                        div()
+                                            .px_3()
+                                            .py_1()
+                                            .text_xs()
+                                            .text_color(rgb(theme.overlay0))
+                                            .child("No saved presets"),
+                                    )
+                                })
+                                .children(presets.into_iter().enumerate().map(|(index, preset)| {
+                                    let apply = preset.clone();
+                                    let name = preset.name.clone();
+                                    div()
+                                        .id(ElementId::Name(format!("preset-{}", index).into()))
+                                        .px_3()
+                                        .py_1()
+                                        .flex()
+                                        .items_center()
+                                        .justify_between()
+                                        .gap_3()
+                                        .text_xs()
+                                        .text_color(rgb(theme.text))
+                                        .cursor_pointer()
+                                        .hover(|style| style.bg(rgb(theme.surface1)))
+                                        .child(preset.name)
+                                        .on_click(cx.listener(move |this, _event, _window, cx| {
+                                            this.preset_menu_open = false;
+                                            cx.update_global::<AppState, _>(|state, _cx| {
+                                                state.apply_preset(&apply);
+                                            });
+                                        }))
+                                        .child(
+                                            div()
+                                                .id(ElementId::Name(format!("preset-delete-{}", index).into()))
+                                                .text_color(rgb(theme.overlay0))
+                                                .hover(|style| style.text_color(rgb(theme.red)))
+                                                .child("✕")
+                                                .on_click(cx.listener(move |_this, _event, _window, cx| {
+                                                    cx.stop_propagation();
+                                                    cx.update_global::<AppState, _>(|state, _cx| {
+                                                        state.delete_preset(&name);
+                                                    });
+                                                })),
+                                        )
+                                }))
+                                // Save the current filters under a name
+                                .child(
+                                    div()
+                                        .mt_1()
+                                        .px_3()
+                                        .pt_2()
+                                        .pb_1()
+                                        .flex()
+                                        .items_center()
+                                        .gap_2()
+                                        .border_t_1()
+                                        .border_color(rgb(theme.surface1))
+                                        .child(
+                                            div()
+                                                .id("preset-name-input")
+                                                .flex_1()
+                                                .h(px(24.))
+                                                .px_2()
+                                                .flex()
+                                                .items_center()
+                                                .rounded_sm()
+                                                .bg(rgb(theme.base))
+                                                .border_1()
+                                                .border_color(if name_focused {
+                                                    rgb(theme.blue)
+                                                } else {
+                                                    rgb(theme.surface1)
+                                                })
+                                                .cursor_text()
+                                                .track_focus(&self.preset_name_focus_handle)
+                                                .on_click(move |_event, window, _cx| {
+                                                    focus_handle.focus(window);
+                                                })
+                                                .on_key_down(cx.listener(|this, event, _window, cx| {
+                                                    this.handle_preset_name_key_down(event, cx);
+                                                }))
+                                                .text_xs()
+                                                .overflow_hidden()
+                                                .whitespace_nowrap()
+                                                .child(if self.preset_name.is_empty() && !name_focused {
+                                                    div().text_color(rgb(theme.overlay0)).child("Preset name")
+                                                } else if name_focused {
+                                                    div()
+                                                        .text_color(rgb(theme.text))
+                                                        .child(format!("{}|", self.preset_name))
+                                                } else {
+                                                    div().text_color(rgb(theme.text)).child(self.preset_name.clone())
+                                                }),
+                                        )
+                                        .child(
+                                            div()
+                                                .id("preset-save-btn")
+                                                .px_2()
+                                                .py_1()
+                                                .rounded_sm()
+                                                .bg(rgb(theme.surface1))
+                                                .text_xs()
+                                                .text_color(if can_save {
+                                                    rgb(theme.blue)
+                                                } else {
+                                                    rgb(theme.overlay0)
+                                                })
+                                                .child("Save current")
+                                                .when(can_save, |this| {
+                                                    this.cursor_pointer()
+                                                        .hover(|style| style.bg(rgb(theme.surface2)))
+                                                        .on_click(cx.listener(|this, _event, _window, cx| {
+                                                            this.save_preset(cx);
+                                                        }))
+                                                }),
+                                        ),
+                                ),
+                        ),
+                ))
+            })
+    }
+
+    fn handle_preset_name_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let key = &event.keystroke.key;
+        let modifiers = &event.keystroke.modifiers;
+
+        if key == "backspace" {
+            self.preset_name.pop();
+        } else if key == "escape" {
+            self.preset_name.clear();
+            self.preset_menu_open = false;
+        } else if key == "enter" {
+            self.save_preset(cx);
+            return;
+        } else if let Some(ch) = &event.keystroke.key_char
+            && !modifiers.platform
+            && !modifiers.control
+        {
+            self.preset_name.extend(ch.chars().filter(|c| !c.is_control()));
+        }
+        cx.notify();
+    }
+
+    /// Save the current filters under the typed name
+    fn save_preset(&mut self, cx: &mut Context<Self>) {
+        if self.preset_name.trim().is_empty() {
+            return;
+        }
+        let name = std::mem::take(&mut self.preset_name);
+        cx.update_global::<AppState, _>(|state, _cx| {
+            state.save_preset(&name);
+        });
+        cx.notify();
+    }
+
+    fn render_profiles(&self, window: &Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let config = &cx.global::<AppState>().config;
+        let profiles: Vec<String> = config.profiles.iter().map(|p| p.name.clone()).collect();
+        let active = config.active_profile;
+        let active_name = config.active_profile_name().to_string();
+        let name_focused = self.profile_name_focus_handle.is_focused(window);
+        let can_add = !self.profile_name.trim().is_empty();
+
+        div()
+            .flex()
+            .flex_col()
+            .items_end()
+            .child(
+                div()
+                    .id("profiles-btn")
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(if self.profile_menu_open {
+                        rgb(theme.surface2)
+                    } else {
+                        rgb(theme.surface1)
+                    })
+                    .text_sm()
+                    .text_color(rgb(theme.subtext0))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.surface2)))
+                    .child(format!("{} ▾", active_name))
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.profile_menu_open = !this.profile_menu_open;
+                        cx.notify();
+                    })),
+            )
+            // Painted last so it isn't covered by the list below
+            .when(self.profile_menu_open, |this| {
+                let focus_handle = self.profile_name_focus_handle.clone();
+                this.child(deferred(
+                    anchored()
+                        .anchor(Corner::TopRight)
+                        .snap_to_window_with_margin(px(8.))
+                        .child(
+                            div()
+                                .id("profiles-menu")
+                                .min_w(px(220.))
+                                .py_1()
+                                .flex()
+                                .flex_col()
+                                .rounded_md()
+                                .bg(rgb(theme.surface0))
+                                .border_1()
+                                .border_color(rgb(theme.surface2))
+                                .shadow_md()
+                                .on_mouse_down_out(cx.listener(|this, _event, _window, cx| {
+                                    this.profile_menu_open = false;
+                                    cx.notify();
+                                }))
+                                .children(profiles.into_iter().enumerate().map(|(index, name)| {
+                                    let is_active = index == active;
+                                    div()
+                                        .id(ElementId::Name(format!("profile-{}", index).into()))
+                                        .px_3()
+                                        .py_1()
+                                        .text_xs()
+                                        .text_color(if is_active {
+                                            rgb(theme.blue)
+                                        } else {
+                                            rgb(theme.text)
+                                        })
+                                        .child(if is_active { format!("✓ {}", name) } else { name })
+                                        .when(!is_active, |this| {
+                                            this.cursor_pointer()
+                                                .hover(|style| style.bg(rgb(theme.surface1)))
+                                                .on_click(cx.listener(move |this, _event, _window, cx| {
+                                                    this.profile_menu_open = false;
+                                                    cx.update_global::<AppState, _>(|state, _cx| {
+                                                        if let Err(e) = state.switch_profile(index) {
+                                                            state.error = Some(format!("Failed to switch account: {}", e));
+                                                        }
+                                                    });
+                                                }))
+                                        })
+                                }))
+                                // Add an account under a new name
+                                .child(
+                                    div()
+                                        .mt_1()
+                                        .px_3()
+                                        .pt_2()
+                                        .pb_1()
+                                        .flex()
+                                        .items_center()
+                                        .gap_2()
+                                        .border_t_1()
+                                        .border_color(rgb(theme.surface1))
+                                        .child(
+                                            div()
+                                                .id("profile-name-input")
+                                                .flex_1()
+                                                .h(px(24.))
+                                                .px_2()
+                                                .flex()
+                                                .items_center()
+                                                .rounded_sm()
+                                                .bg(rgb(theme.base))
+                                                .border_1()
+                                                .border_color(if name_focused {
+                                                    rgb(theme.blue)
+                                                } else {
+                                                    rgb(theme.surface1)
+                                                })
+                                                .cursor_text()
+                                                .track_focus(&self.profile_name_focus_handle)
+                                                .on_click(move |_event, window, _cx| {
+                                                    focus_handle.focus(window);
+                                                })
+                                                .on_key_down(cx.listener(|this, event, _window, cx| {
+                                                    this.handle_profile_name_key_down(event, cx);
+                                                }))
+                                                .text_xs()
+                                                .overflow_hidden()
+                                                .whitespace_nowrap()
+                                                .child(if self.profile_name.is_empty() && !name_focused {
+                                                    div().text_color(rgb(theme.overlay0)).child("Account name")
+                                                } else if name_focused {
+                                                    div()
+                                                        .text_color(rgb(theme.text))
+                                                        .child(format!("{}|", self.profile_name))
+                                                } else {
+                                                    div().text_color(rgb(theme.text)).child(self.profile_name.clone())
+                                                }),
+                                        )
+                                        .child(
+                                            div()
+                                                .id("profile-add-btn")
+                                                .px_2()
+                                                .py_1()
+                                                .rounded_sm()
+                                                .bg(rgb(theme.surface1))
+                                                .text_xs()
+                                                .text_color(if can_add {
+                                                    rgb(theme.blue)
+                                                } else {
+                                                    rgb(theme.overlay0)
+                                                })
+                                                .child("Add account")
+                                                .when(can_add, |this| {
+                                                    this.cursor_pointer()
+                                                        .hover(|style| style.bg(rgb(theme.surface2)))
+                                                        .on_click(cx.listener(|this, _event, _window, cx| {
+                                                            this.add_profile(cx);
+                                                        }))
+                                                }),
+                                        ),
+                                ),
+                        ),
+                ))
+            })
+    }
+
+    fn handle_profile_name_key_down(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let key = &event.keystroke.key;
+        let modifiers = &event.keystroke.modifiers;
+
+        if key == "backspace" {
+            self.profile_name.pop();
+        } else if key == "escape" {
+            self.profile_name.clear();
+            self.profile_menu_open = false;
+        } else if key == "enter" {
+            self.add_profile(cx);
+            return;
+        } else if let Some(ch) = &event.keystroke.key_char
+            && !modifiers.platform
+            && !modifiers.control
+        {
+            self.profile_name.extend(ch.chars().filter(|c| !c.is_control()));
+        }
+        cx.notify();
+    }
+
+    /// Add an account under the typed name and switch to it, which shows the
+    /// setup screen until a token is entered
+    fn add_profile(&mut self, cx: &mut Context<Self>) {
+        if self.profile_name.trim().is_empty() {
+            return;
+        }
+        let name = std::mem::take(&mut self.profile_name);
+        self.profile_menu_open = false;
+        cx.update_global::<AppState, _>(|state, _cx| {
+            if let Err(e) = state.add_profile(&name) {
+                state.error = Some(format!("Failed to add account: {}", e));
+            }
+        });
+        cx.notify();
+    }
+
+    fn handle_search_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let key = &event.keystroke.key;
+
+        // Enter hands the keyboard back to the list so shortcuts work on the results
+        if key == "enter" {
+            self.list_focus_handle.focus(window);
+            cx.notify();
+            return;
+        }
+
+        // Escape clears the search and leaves the box, like a browser's find bar
+        if key == "escape" {
+            cx.update_global::<AppState, _>(|state, _cx| {
+                state.search_query.clear();
+            });
+            self.list_focus_handle.focus(window);
+            cx.stop_propagation();
+            cx.notify();
+            return;
+        }
+
+        // Typed characters, including `/`, go into the query here and
+        // never reach the list's shortcuts, which need the list focused
+        cx.update_global::<AppState, _>(|state, _cx| {
+            if key == "backspace" {
+                state.search_query.pop();
+            } else if let Some(ch) = &event.keystroke.key_char
+                && !event.keystroke.modifiers.platform
+                && !event.keystroke.modifiers.control
+            {
+                state.search_query.extend(ch.chars().filter(|c| !c.is_control()));
+            }
+        });
+        cx.notify();
+    }
+
+    fn unstar_selected(&mut self, cx: &mut Context<Self>) {
+        let (repos_to_unstar, ids_to_remove): (Vec<_>, Vec<_>) = {
+            let state = cx.global::<AppState>();
+            state
+                .repositories
+                .iter()
+                .filter(|r| state.selection.is_selected(r.id))
+                .map(|r| ((r.owner.clone(), r.name.clone()), r.id))
+                .unzip()
+        };
+        self.unstar_batch(repos_to_unstar, ids_to_remove, cx);
+    }
+
+    /// Unstar the repos "Unstar All Filtered" showed in its confirmation,
+    /// skipping any that have left the list since
+    fn unstar_matching(&mut self, ids: Vec<u64>, cx: &mut Context<Self>) {
+        let ids: HashSet<u64> = ids.into_iter().collect();
+        let (repos_to_unstar, ids_to_remove): (Vec<_>, Vec<_>) = cx
+            .global::<AppState>()
+            .repositories
+            .iter()
+            .filter(|r| ids.contains(&r.id))
+            .map(|r| ((r.owner.clone(), r.name.clone()), r.id))
+            .unzip();
+        self.unstar_batch(repos_to_unstar, ids_to_remove, cx);
+    }
+
+    /// Unstar (owner, name) pairs in parallel. Rows disappear right away and
+    /// are put back if their request fails.
+    fn unstar_batch(
+        &mut self,
+        repos_to_unstar: Vec<(String, String)>,
+        ids_to_remove: Vec<u64>,
+        cx: &mut Context<Self>,
+    ) {
+        if repos_to_unstar.is_empty() {
+            return;
+        }
+        if cx.global::<AppState>().config.unstar.dry_run {
+            cx.update_global::<AppState, _>(|state, _cx| state.record_dry_run(&ids_to_remove));
+            return;
+        }
+
+        let total = repos_to_unstar.len();
+        let unstar = cx.global::<AppState>().config.unstar.clone();
+        let chunk_delay = Duration::from_millis(unstar.chunk_delay_ms);
+        cx.update_global::<AppState, _>(|state, _cx| {
+            state.begin_removal(&ids_to_remove);
+            state.unstar_progress = Some(UnstarProgress::chunked(total, unstar.chunk_size));
+        });
+
+        cx.spawn(async move |_view, cx| {
+            let service = cx
+                .update(|cx| cx.global::<AppState>().github_service.clone())
+                .ok()
+                .flatten();
+
+            let mut failed = 0;
+            let mut moved = 0;
+            let mut confirmed = 0;
+            if let Some(service) = service {
+                // Handle results as they finish so progress and the ETA update as we go
+                let mut results = std::pin::pin!(service.unstar_repos_stream(
+                    &repos_to_unstar,
+                    unstar.concurrency,
+                    unstar.chunk_size,
+                    chunk_delay,
+                ));
+                let mut last_completed = Instant::now();
+                while let Some((index, result)) = results.next().await {
+                    // Check for token expiration
+                    if result.as_ref().err().map(is_token_expired_error).unwrap_or(false) {
+                        cx.update(|cx| {
+                            let state = cx.global_mut::<AppState>();
+                            state.logout();
+                            state.error = Some("Token expired. Please login again.".to_string());
+                        })
+                        .ok();
+                        return;
+                    }
+
+                    // With requests in parallel, the gap between completions is
+                    // the effective time per repo
+                    let elapsed = last_completed.elapsed();
+                    last_completed = Instant::now();
+                    cx.update(|cx| {
+                        let state = cx.global_mut::<AppState>();
+                        if let Some(progress) = state.unstar_progress.as_mut() {
+                            progress.record(elapsed);
+                        }
+                        // Recorded even if the list was reloaded meanwhile;
+                        // after a logout nothing is pending any more
+                        match result {
+                            Ok(_) => {
+                                if state.confirm_removal(ids_to_remove[index]) {
+                                    confirmed += 1;
+                                }
+                            }
+                            Err(e) => {
+                                tracing::error!("Unstar API error: {}", e);
+                                if state.rollback_removal(ids_to_remove[index]) {
+                                    failed += 1;
+                                    if is_repo_moved_error(&e) {
+                                        moved += 1;
+                                    }
+                                }
+                            }
+                        }
+                    })
+                    .ok();
+                }
+            }
+
+            cx.update(|cx| {
+                let state = cx.global_mut::<AppState>();
+                state.unstar_progress = None;
+                // Anything left pending never got a result, so it's still starred
+                let unfinished: Vec<u64> = ids_to_remove
+                    .iter()
+                    .copied()
+                    .filter(|id| state.pending_removal.contains_key(id))
                     .collect();
+                failed += unfinished.len();
+                for id in unfinished {
+                    state.rollback_removal(id);
+                }
+                if failed > 0 {
+                    let mut message = format!(
+                        "Failed to unstar {} of {} repositories; they were put back in the list",
+                        failed, total
+                    );
+                    if moved > 0 {
+                        message.push_str(&format!(
+                            ". {} may have been renamed or transferred, refresh the list and try again",
+                            moved
+                        ));
+                    }
+                    state.error = Some(message);
+                }
+                if confirmed == 0 && failed == 0 {
+                    return;
+                }
+                state.queue_cache_save();
+                Self::refresh_rate_limit(cx);
+                Self::schedule_undo_expiry(cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Hide the undo toast once its window has passed
+    fn schedule_undo_expiry(cx: &mut App) {
+        cx.spawn(async move |cx| {
+            cx.background_executor().timer(UNDO_WINDOW).await;
+            cx.update(|cx| {
+                cx.global_mut::<AppState>().expire_undo();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Restore the last unstarred repos locally, then re-star them on GitHub
+    fn undo_last_unstar(&mut self, cx: &mut Context<Self>) {
+        let restored = cx.update_global::<AppState, _>(|state, _cx| state.undo_last_unstar());
+        if restored.is_empty() {
+            return;
+        }
+
+        let epoch = cx.global::<AppState>().epoch;
+        cx.spawn(async move |_view, cx| {
+            let service = cx
+                .update(|cx| cx.global::<AppState>().github_service.clone())
+                .ok()
+                .flatten();
+
+            let Some(service) = service else {
+                return;
+            };
+
+            for repo in restored {
+                if let Err(e) = service.star_repo(&repo.owner, &repo.name).await {
+                    tracing::error!("Star API error: {}", e);
+                    // Still unstarred on GitHub, so take it back out of the list
+                    cx.update(|cx| {
+                        let state = cx.global_mut::<AppState>();
+                        if !state.is_current_epoch(epoch) {
+                            return;
+                        }
+                        state.undo_failed(repo.id);
+                        state.handle_api_error(e, "Failed to undo unstar");
+                    })
+                    .ok();
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn render_stats_panel(stats: &RepoStats, theme: Theme) -> impl IntoElement {
+        let stat = |label: &'static str, value: String| {
+            div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(div().text_xs().text_color(rgb(theme.overlay0)).child(label))
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::BOLD)
+                        .text_color(rgb(theme.text))
+                        .child(value),
+                )
+        };
+        let median_age = match stats.median_pushed_age_days {
+            Some(1) => "1 day".to_string(),
+            Some(days) => format!("{} days", days),
+            None => "—".to_string(),
+        };
+        // Bars are scaled against the most common language
+        let max_count = stats.top_languages.first().map_or(1, |(_, count)| *count).max(1);
+
+        div()
+            .w_full()
+            .px_4()
+            .py_3()
+            .flex()
+            .gap_8()
+            .border_b_1()
+            .border_color(rgb(theme.surface1))
+            .bg(rgb(theme.mantle))
+            .child(stat("Repositories", stats.total.to_string()))
+            .child(stat("Total stars", stats.total_stars_label()))
+            .child(stat("Median last push", median_age))
+            .child(stat("Archived", stats.archived.to_string()))
+            .child(stat("Forks", stats.forks.to_string()))
+            // Language histogram
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .child(div().text_xs().text_color(rgb(theme.overlay0)).child("Top languages"))
+                    .children(stats.top_languages.iter().map(|(language, count)| {
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_2()
+                            .text_xs()
+                            .child(
+                                div()
+                                    .w(px(80.))
+                                    .text_color(rgb(theme.subtext0))
+                                    .child(language.clone()),
+                            )
+                            .child(
+                                div()
+                                    .h(px(6.))
+                                    .w(px(120. * *count as f32 / max_count as f32))
+                                    .rounded_sm()
+                                    .bg(rgb(theme.blue)),
+                            )
+                            .child(div().text_color(rgb(theme.overlay0)).child(count.to_string()))
+                    })),
+            )
+    }
+
+    /// Note that GitHub couldn't be reached and the list is the read-only cache
+    fn render_offline_banner(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        div()
+            .w_full()
+            .px_4()
+            .py_2()
+            .flex()
+            .items_center()
+            .gap_3()
+            .border_b_1()
+            .border_color(rgb(theme.surface1))
+            .bg(rgb(theme.mantle))
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .text_color(rgb(theme.yellow))
+                    .child("Offline — showing cached data. Unstarring and loading more are off until GitHub can be reached."),
+            )
+            .child(
+                div()
+                    .id("offline-retry-btn")
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .bg(rgb(theme.surface1))
+                    .text_color(rgb(theme.blue))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.surface2)))
+                    .child("Retry")
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.reload_repos(cx);
+                    })),
+            )
+    }
+
+    /// Dismissible note of stars added or removed elsewhere since the last session
+    fn render_session_diff(summary: String, theme: Theme) -> impl IntoElement {
+        div()
+            .w_full()
+            .px_4()
+            .py_2()
+            .flex()
+            .items_center()
+            .gap_3()
+            .border_b_1()
+            .border_color(rgb(theme.surface1))
+            .bg(rgb(theme.mantle))
+            .child(
+                div()
+                    .flex_1()
+                    .text_sm()
+                    .text_color(rgb(theme.subtext0))
+                    .child(format!("Since last session: {}", summary)),
+            )
+            .child(
+                div()
+                    .id("dismiss-session-diff")
+                    .px_2()
+                    .rounded_sm()
+                    .text_sm()
+                    .text_color(rgb(theme.overlay0))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(rgb(theme.surface1)))
+                    .child("✕")
+                    .on_click(|_event, _window, cx| {
+                        cx.update_global::<AppState, _>(|state, _cx| {
+                            state.session_diff = None;
+                        });
+                    }),
+            )
+    }
+
+    fn render_empty_state(theme: Theme) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .items_center()
+            .justify_center()
+            .gap_3()
+            .py_8()
+            .child(
+                div()
+                    .text_lg()
+                    .text_color(rgb(theme.text))
+                    .child("You haven't starred any repositories yet"),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(theme.overlay0))
+                    .child("Stars you add on GitHub will show up here."),
+            )
+            .child(
+                div()
+                    .id("explore-github-link")
+                    .text_sm()
+                    .text_color(rgb(theme.blue))
+                    .cursor_pointer()
+                    .hover(|style| style.underline())
+                    .child("Explore repositories on github.com")
+                    .on_click(|_event, _window, _cx| {
+                        let _ = open::that("https://github.com/explore");
+                    }),
+            )
+    }
+
+    fn render_undo_toast(&self, count: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let message = if count == 1 {
+            "Unstarred 1 repository".to_string()
+        } else {
+            format!("Unstarred {} repositories", count)
+        };
+
+        div()
+            .absolute()
+            .bottom_4()
+            .left_0()
+            .right_0()
+            .flex()
+            .justify_center()
+            .child(
+                div()
+                    .px_4()
+                    .py_2()
+                    .flex()
+                    .items_center()
+                    .gap_4()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(rgb(theme.surface1))
+                    .bg(rgb(theme.surface0))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(theme.text))
+                            .child(message),
+                    )
+                    .child(
+                        div()
+                            .id("undo-unstar-btn")
+                            .px_3()
+                            .py_1()
+                            .rounded_md()
+                            .bg(rgb(theme.blue))
+                            .text_sm()
+                            .text_color(rgb(theme.base))
+                            .font_weight(FontWeight::MEDIUM)
+                            .cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.sapphire)))
+                            .child("Undo")
+                            .on_click(cx.listener(|this, _event, _window, cx| {
+                                this.undo_last_unstar(cx);
+                            })),
+                    ),
+            )
+    }
+
+    /// Save the visible repositories to a user-chosen JSON or CSV file
+    fn export_repos(&mut self, cx: &mut Context<Self>) {
+        let repos: Vec<_> = cx
+            .global::<AppState>()
+            .visible_repositories()
+            .into_iter()
+            .cloned()
+            .collect();
+        if repos.is_empty() {
+            return;
+        }
+
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("."));
+        // The chosen extension picks the format: `.csv` for CSV, `.jsonl` for
+        // JSON lines, anything else is JSON
+        let path = cx.prompt_for_new_path(&directory, Some("github-stars.json"));
+
+        cx.spawn(async move |_view, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
 
+            if let Err(e) = ExportService::export(&repos, &path) {
+                tracing::error!("Export error: {}", e);
                 cx.update(|cx| {
-                    let state = cx.global_mut::<AppState>();
-                    state.remove_repos(&success_ids);
+                    cx.global_mut::<AppState>().set_error(format!("Failed to export: {}", e));
                 })
                 .ok();
             }
@@ -570,12 +3661,20 @@ impl RepositoryListView {
 
     fn logout(&mut self, cx: &mut Context<Self>) {
         cx.update_global::<AppState, _>(|state, _cx| {
-            let _ = state.logout();
+            state.logout();
         });
         cx.notify();
     }
 
-    fn render_confirmation_dialog(action: PendingAction, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_confirmation_dialog(
+        &self,
+        action: PendingAction,
+        type_to_confirm_threshold: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let dry_run = cx.global::<AppState>().config.unstar.dry_run
+            && !matches!(action, PendingAction::Logout | PendingAction::OpenSelected(_));
         let (title, message) = match &action {
             PendingAction::UnstarSingle(_, _, _, full_name) => (
                 "Confirm Unstar".to_string(),
@@ -585,13 +3684,41 @@ impl RepositoryListView {
                 "Confirm Unstar".to_string(),
                 format!("Are you sure you want to unstar {} repositories?", count),
             ),
-            PendingAction::Logout => (
-                "Confirm Logout".to_string(),
-                "Are you sure you want to logout?".to_string(),
+            PendingAction::UnstarByFilter(spec, ids) => (
+                "Confirm Unstar".to_string(),
+                format!("Unstar {}?", spec.describe(ids.len())),
+            ),
+            PendingAction::OpenSelected(count) => (
+                "Open in Browser".to_string(),
+                format!("Open {} repositories in your browser? Each one opens in a new tab.", count),
             ),
+            PendingAction::Logout => {
+                let config = &cx.global::<AppState>().config;
+                let message = if config.profiles.len() > 1 {
+                    format!(
+                        "Are you sure you want to logout of '{}'? Other accounts stay signed in.",
+                        config.active_profile_name()
+                    )
+                } else {
+                    "Are you sure you want to logout?".to_string()
+                };
+                ("Confirm Logout".to_string(), message)
+            }
         };
 
+        let requires_typing = action.requires_typed_confirmation(type_to_confirm_threshold);
+        let skip_confirm_label = match &action {
+            PendingAction::UnstarSingle(..) => Some("Don't ask again for single unstars".to_string()),
+            PendingAction::UnstarSelected(count) if !requires_typing => {
+                Some(format!("Don't ask again for {} or fewer", count))
+            }
+            _ => None,
+        };
+        let can_confirm = !requires_typing || action.is_confirmation_text(&self.confirm_input);
         let action_clone = action.clone();
+        // Name the repositories a batch unstar will hit, as a last check
+        let selected_preview = matches!(action, PendingAction::UnstarSelected(_))
+            .then(|| cx.global::<AppState>().selected_names_preview(CONFIRM_LIST_LIMIT));
 
         // Full-screen overlay
         div()
@@ -601,6 +3728,11 @@ impl RepositoryListView {
             .flex()
             .items_center()
             .justify_center()
+            .track_focus(&self.dialog_focus_handle)
+            // Also receives keys bubbling up from the type-to-confirm input
+            .on_key_down(cx.listener(|this, event, _window, cx| {
+                this.handle_dialog_key_down(event, cx);
+            }))
             // Semi-transparent backdrop
             .child(
                 div()
@@ -620,9 +3752,9 @@ impl RepositoryListView {
                     .w(px(400.))
                     .p_6()
                     .rounded_lg()
-                    .bg(rgb(catppuccin::SURFACE0))
+                    .bg(rgb(theme.surface0))
                     .border_1()
-                    .border_color(rgb(catppuccin::SURFACE1))
+                    .border_color(rgb(theme.surface1))
                     .flex()
                     .flex_col()
                     .gap_4()
@@ -631,16 +3763,137 @@ impl RepositoryListView {
                         div()
                             .text_lg()
                             .font_weight(FontWeight::BOLD)
-                            .text_color(rgb(catppuccin::TEXT))
+                            .text_color(rgb(theme.text))
                             .child(title),
                     )
                     // Message
                     .child(
                         div()
                             .text_sm()
-                            .text_color(rgb(catppuccin::SUBTEXT0))
+                            .text_color(rgb(theme.subtext0))
                             .child(message),
                     )
+                    .when_some(selected_preview, |this, (names, more)| {
+                        this.child(
+                            div()
+                                .id("confirm-repo-list")
+                                .max_h(px(200.))
+                                .overflow_y_scroll()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(rgb(theme.base))
+                                .flex()
+                                .flex_col()
+                                .gap_1()
+                                .text_xs()
+                                .text_color(rgb(theme.text))
+                                .children(names)
+                                .when(more > 0, |this| {
+                                    this.child(
+                                        div()
+                                            .text_color(rgb(theme.overlay0))
+                                            .child(format!("…and {} more", more)),
+                                    )
+                                }),
+                        )
+                    })
+                    .when(dry_run, |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(rgb(theme.yellow))
+                                .child("Dry run is on: nothing will be unstarred, the repositories are only marked."),
+                        )
+                    })
+                    // Opt out of confirming single unstars or batches this small
+                    .when_some(skip_confirm_label, |this, label| {
+                        let checked = self.skip_confirm;
+                        this.child(
+                            div()
+                                .id("skip-confirm")
+                                .flex()
+                                .items_center()
+                                .gap_2()
+                                .cursor_pointer()
+                                .child(
+                                    div()
+                                        .w(px(16.))
+                                        .h(px(16.))
+                                        .flex()
+                                        .items_center()
+                                        .justify_center()
+                                        .rounded_sm()
+                                        .border_1()
+                                        .border_color(if checked {
+                                            rgb(theme.blue)
+                                        } else {
+                                            rgb(theme.surface2)
+                                        })
+                                        .bg(if checked {
+                                            rgb(theme.blue)
+                                        } else {
+                                            rgb(theme.base)
+                                        })
+                                        .when(checked, |this| {
+                                            this.text_xs().text_color(rgb(theme.base)).child("✓")
+                                        }),
+                                )
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(rgb(theme.subtext0))
+                                        .child(label),
+                                )
+                                .on_click(cx.listener(|this, _event, _window, cx| {
+                                    this.skip_confirm = !this.skip_confirm;
+                                    cx.notify();
+                                })),
+                        )
+                    })
+                    // Type-to-confirm input for very large batches
+                    .when(requires_typing, |this| {
+                        let count = action.bulk_count().unwrap_or_default();
+                        this.child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .gap_2()
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(rgb(theme.red))
+                                        .child(format!(
+                                            "This cannot be undone. Type {} or {} to confirm.",
+                                            count, TYPE_TO_CONFIRM_WORD
+                                        )),
+                                )
+                                .child(
+                                    div()
+                                        .id("confirm-input")
+                                        .w_full()
+                                        .h(px(36.))
+                                        .px_3()
+                                        .flex()
+                                        .items_center()
+                                        .rounded_md()
+                                        .bg(rgb(theme.base))
+                                        .border_1()
+                                        .border_color(if can_confirm {
+                                            rgb(theme.red)
+                                        } else {
+                                            rgb(theme.blue)
+                                        })
+                                        .track_focus(&self.confirm_focus_handle)
+                                        .on_key_down(cx.listener(|this, event, _window, cx| {
+                                            this.handle_confirm_key_down(event, cx);
+                                        }))
+                                        .text_sm()
+                                        .text_color(rgb(theme.text))
+                                        .child(format!("{}|", self.confirm_input)),
+                                ),
+                        )
+                    })
                     // Buttons
                     .child(
                         div()
@@ -655,11 +3908,11 @@ impl RepositoryListView {
                                     .px_4()
                                     .py_2()
                                     .rounded_md()
-                                    .bg(rgb(catppuccin::SURFACE1))
+                                    .bg(rgb(theme.surface1))
                                     .text_sm()
-                                    .text_color(rgb(catppuccin::TEXT))
+                                    .text_color(rgb(theme.text))
                                     .cursor_pointer()
-                                    .hover(|style| style.bg(rgb(catppuccin::SURFACE2)))
+                                    .hover(|style| style.bg(rgb(theme.surface2)))
                                     .child("Cancel")
                                     .on_click(cx.listener(|_this, _event, _window, cx| {
                                         cx.update_global::<AppState, _>(|state, _cx| {
@@ -674,25 +3927,54 @@ impl RepositoryListView {
                                     .px_4()
                                     .py_2()
                                     .rounded_md()
-                                    .bg(rgb(catppuccin::RED))
+                                    .bg(if can_confirm {
+                                        rgb(theme.red)
+                                    } else {
+                                        rgb(theme.surface1)
+                                    })
                                     .text_sm()
-                                    .text_color(rgb(catppuccin::BASE))
+                                    .text_color(if can_confirm {
+                                        rgb(theme.base)
+                                    } else {
+                                        rgb(theme.overlay0)
+                                    })
                                     .font_weight(FontWeight::MEDIUM)
-                                    .cursor_pointer()
-                                    .hover(|style| style.opacity(0.9))
                                     .child("Confirm")
-                                    .on_click(cx.listener(move |this, _event, _window, cx| {
-                                        this.execute_action(action_clone.clone(), cx);
-                                    })),
+                                    .when(can_confirm, |this| {
+                                        this.cursor_pointer()
+                                            .hover(|style| style.opacity(0.9))
+                                            .on_click(cx.listener(move |this, _event, _window, cx| {
+                                                this.execute_action(action_clone.clone(), cx);
+                                            }))
+                                    }),
                             ),
                     ),
             )
     }
 
+    /// Unstar a single repo, asking first unless the user opted out
+    fn request_unstar(repo_id: u64, owner: String, name: String, full_name: String, cx: &mut App) {
+        if cx.global::<AppState>().config.unstar.confirm_single_unstar {
+            cx.update_global::<AppState, _>(|state, _cx| {
+                state.pending_action = Some(PendingAction::UnstarSingle(repo_id, owner, name, full_name));
+            });
+        } else {
+            Self::do_unstar_repo(repo_id, owner, name, cx);
+        }
+    }
+
     fn execute_action(&mut self, action: PendingAction, cx: &mut Context<Self>) {
+        let skip_confirm = self.skip_confirm;
         // Clear pending action first
         cx.update_global::<AppState, _>(|state, _cx| {
             state.pending_action = None;
+            match action {
+                PendingAction::UnstarSingle(..) if skip_confirm => state.set_confirm_single_unstar(false),
+                PendingAction::UnstarSelected(count) if skip_confirm => {
+                    state.set_batch_confirm_threshold(count + 1)
+                }
+                _ => {}
+            }
         });
 
         match action {
@@ -702,37 +3984,92 @@ impl RepositoryListView {
             PendingAction::UnstarSelected(_) => {
                 self.unstar_selected(cx);
             }
+            PendingAction::UnstarByFilter(_, ids) => {
+                self.unstar_matching(ids, cx);
+            }
+            PendingAction::OpenSelected(_) => {
+                Self::open_selected(cx);
+            }
             PendingAction::Logout => {
                 self.logout(cx);
             }
         }
     }
 
-    fn do_unstar_repo(repo_id: u64, owner: String, name: String, cx: &mut Context<Self>) {
-        cx.spawn(async move |_view, cx| {
-            let service = cx
-                .update(|cx| cx.global::<AppState>().github_service.clone())
-                .ok()
-                .flatten();
-
-            if let Some(service) = service {
-                match service.unstar_repo(&owner, &name).await {
-                    Ok(_) => {
-                        cx.update(|cx| {
-                            let state = cx.global_mut::<AppState>();
-                            state.remove_repos(&[repo_id]);
-                        }).ok();
-                    }
-                    Err(e) => {
-                        tracing::error!("Unstar API error: {}", e);
-                        cx.update(|cx| {
-                            let state = cx.global_mut::<AppState>();
-                            state.handle_api_error(e, "Failed to unstar");
-                        }).ok();
+    /// Open every selected repository in the browser, off the UI thread since
+    /// each launch can block for a moment
+    fn open_selected(cx: &mut App) {
+        let urls = cx.global::<AppState>().get_selected_urls();
+        cx.background_executor()
+            .spawn(async move {
+                for url in urls {
+                    if let Err(e) = open::that(&url) {
+                        tracing::warn!("Failed to open {}: {}", url, e);
                     }
                 }
+            })
+            .detach();
+    }
+
+    fn do_unstar_repo(repo_id: u64, owner: String, name: String, cx: &mut App) {
+        if cx.global::<AppState>().config.unstar.dry_run {
+            cx.update_global::<AppState, _>(|state, _cx| state.record_dry_run(&[repo_id]));
+            return;
+        }
+        let Some(service) = cx.global::<AppState>().github_service.clone() else {
+            return;
+        };
+        cx.update_global::<AppState, _>(|state, _cx| state.begin_removal(&[repo_id]));
+
+        // Recorded even if the list was reloaded meanwhile; after a logout
+        // nothing is pending any more
+        cx.spawn(async move |cx| {
+            match service.unstar_repo(&owner, &name).await {
+                Ok(_) => {
+                    cx.update(|cx| {
+                        let state = cx.global_mut::<AppState>();
+                        if !state.confirm_removal(repo_id) {
+                            return;
+                        }
+                        state.queue_cache_save();
+                        Self::refresh_rate_limit(cx);
+                        Self::schedule_undo_expiry(cx);
+                    }).ok();
+                }
+                Err(e) => {
+                    tracing::error!("Unstar API error: {}", e);
+                    cx.update(|cx| {
+                        let state = cx.global_mut::<AppState>();
+                        if !state.rollback_removal(repo_id) {
+                            return;
+                        }
+                        state.handle_api_error(e, "Failed to unstar");
+                        Self::refresh_rate_limit(cx);
+                    }).ok();
+                }
             }
         })
         .detach();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    // Not a glob import: gpui's own `test` macro would shadow `#[test]`
+    use super::list_summary;
+
+    #[test]
+    fn test_list_summary_without_scroll_position() {
+        assert_eq!(list_summary(None, 1500, 1500, false), "1500 repositories");
+        assert_eq!(list_summary(None, 37, 1500, true), "37 of 1500 repositories");
+    }
+
+    #[test]
+    fn test_list_summary_follows_rows_in_view() {
+        assert_eq!(list_summary(Some((21, 40)), 1500, 1500, false), "21–40 of 1500 repositories");
+        assert_eq!(
+            list_summary(Some((1, 12)), 37, 1500, true),
+            "1–12 of 37 matching · 1500 repositories"
+        );
+    }
+}
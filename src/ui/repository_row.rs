@@ -1,18 +1,301 @@
-use crate::models::Repository;
-use crate::state::AppState;
-use crate::ui::catppuccin;
+use crate::models::{RepoActivity, Repository, RowDensity, SortDirection, SortField, StarList};
+use crate::state::{AppState, CopyTarget, ReadmePreview, COPIED_FEEDBACK};
+use crate::ui::{language_color, Theme};
+use crate::util::{match_segments, relative_time, truncate_words};
+use chrono::{DateTime, Utc};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use std::rc::Rc;
+
+/// Number of README lines shown in the inline preview
+const README_PREVIEW_LINES: usize = 20;
+
+/// Descriptions longer than this are shortened at a word boundary
+const DESCRIPTION_MAX_CHARS: usize = 100;
+
+/// Table columns in order with their widths; Name has none and takes the rest
+const TABLE_COLUMNS: [(SortField, Option<Pixels>); 5] = [
+    (SortField::Name, None),
+    (SortField::Language, Some(px(120.))),
+    (SortField::Stars, Some(px(72.))),
+    (SortField::Forks, Some(px(72.))),
+    (SortField::Pushed, Some(px(120.))),
+];
+
+/// Width of the table's last column, holding the Unstar button
+const TABLE_ACTIONS_WIDTH: Pixels = px(72.);
+
+/// Tooltip showing the absolute date behind a relative timestamp
+struct DateTooltip {
+    text: SharedString,
+    theme: Theme,
+}
+
+impl Render for DateTooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .rounded_sm()
+            .bg(rgb(self.theme.surface0))
+            .border_1()
+            .border_color(rgb(self.theme.surface2))
+            .text_xs()
+            .text_color(rgb(self.theme.text))
+            .child(self.text.clone())
+    }
+}
+
+/// "Pushed 3 days ago"-style label with the absolute date on hover
+fn render_timestamp(
+    id: String,
+    label: &str,
+    dt: DateTime<Utc>,
+    now: DateTime<Utc>,
+    theme: Theme,
+) -> impl IntoElement {
+    render_dated(id, format!("{} {}", label, relative_time(dt, now)), dt, theme)
+}
+
+/// `text` with the absolute date `dt` on hover
+fn render_dated(id: String, text: String, dt: DateTime<Utc>, theme: Theme) -> Stateful<Div> {
+    let absolute: SharedString = dt.format("%Y-%m-%d %H:%M UTC").to_string().into();
+    div()
+        .id(ElementId::Name(id.into()))
+        .child(text)
+        .tooltip(move |_window, cx| {
+            cx.new(|_| DateTooltip {
+                text: absolute.clone(),
+                theme,
+            })
+            .into()
+        })
+}
+
+/// `text` with every match of `query` given a highlight background
+fn render_highlighted(text: &str, query: Option<&str>, theme: Theme) -> StyledText {
+    let style = HighlightStyle {
+        background_color: Some(rgba((theme.yellow << 8) | 0x55).into()),
+        ..Default::default()
+    };
+    let mut start = 0;
+    let mut highlights = Vec::new();
+    for (segment, is_match) in match_segments(text, query.unwrap_or_default()) {
+        if is_match {
+            highlights.push((start..start + segment.len(), style));
+        }
+        start += segment.len();
+    }
+    StyledText::new(text.to_string()).with_highlights(highlights)
+}
+
+/// How a row should be displayed
+pub struct RowState {
+    /// Position in the displayed list
+    pub index: usize,
+    pub is_selected: bool,
+    /// Highlighted by keyboard navigation
+    pub is_highlighted: bool,
+    /// False while unstarring is blocked, e.g. by the rate limit
+    pub can_unstar: bool,
+    /// Some when the row's README preview is expanded
+    pub readme: Option<ReadmePreview>,
+    /// Copy button that was just clicked, shown as "Copied!"
+    pub copied: Option<CopyTarget>,
+    /// Whether the user has any star lists to add the repository to
+    pub has_lists: bool,
+    /// Some while the row's "Move to list" menu is open
+    pub list_menu: Option<Vec<StarList>>,
+    /// A dry run would have unstarred this repository
+    pub would_unstar: bool,
+    /// Where the star falls among all stars, e.g. "#1,234 of 1,500"
+    pub star_age: Option<String>,
+    /// Compact rows fit name, language and stats on one line
+    pub density: RowDensity,
+    /// Search text to highlight in the repository name
+    pub highlight: Option<String>,
+    /// The signed-in user owns the repository
+    pub is_own: bool,
+    /// Pinned as a keeper: listed first and skipped by bulk selection
+    pub is_pinned: bool,
+    /// Last commit and release, once fetched
+    pub activity: Option<RepoActivity>,
+}
+
+/// Put `text` on the clipboard and briefly mark the button as copied
+fn copy_to_clipboard(repo_id: u64, target: CopyTarget, text: String, cx: &mut App) {
+    cx.write_to_clipboard(ClipboardItem::new_string(text));
+    cx.update_global::<AppState, _>(|state, _cx| {
+        state.copied = Some((repo_id, target));
+    });
+    cx.spawn(async move |cx| {
+        cx.background_executor().timer(COPIED_FEEDBACK).await;
+        cx.update(|cx| {
+            cx.update_global::<AppState, _>(|state, _cx| {
+                if state.copied == Some((repo_id, target)) {
+                    state.copied = None;
+                }
+            });
+        })
+        .ok();
+    })
+    .detach();
+}
+
+/// Move a repository into a star list, reporting failures in the error banner
+fn move_to_list(owner: String, name: String, list: StarList, cx: &mut App) {
+    let state = cx.global_mut::<AppState>();
+    state.list_menu_open = None;
+    let epoch = state.epoch;
+    let Some(service) = state.github_service.clone() else {
+        return;
+    };
+    cx.spawn(async move |cx| {
+        let result = service.move_repo_to_list(&list.id, &owner, &name).await;
+        cx.update(|cx| {
+            cx.update_global::<AppState, _>(|state, _cx| match result {
+                Ok(()) => tracing::info!("Moved {}/{} to list {}", owner, name, list.name),
+                Err(e) if state.is_current_epoch(epoch) => {
+                    state.handle_api_error(e, &format!("Failed to move to \"{}\"", list.name))
+                }
+                Err(_) => {}
+            });
+        })
+        .ok();
+    })
+    .detach();
+}
+
+fn render_list_menu(
+    repo_id: u64,
+    owner: String,
+    name: String,
+    lists: Vec<StarList>,
+    theme: Theme,
+) -> impl IntoElement {
+    div()
+        .id(ElementId::Name(format!("list-menu-{}", repo_id).into()))
+        .min_w(px(160.))
+        .py_1()
+        .flex()
+        .flex_col()
+        .rounded_md()
+        .bg(rgb(theme.surface0))
+        .border_1()
+        .border_color(rgb(theme.surface2))
+        .shadow_md()
+        .on_mouse_down_out(|_event, _window, cx| {
+            cx.global_mut::<AppState>().list_menu_open = None;
+        })
+        .child(
+            div()
+                .px_3()
+                .py_1()
+                .text_xs()
+                .text_color(rgb(theme.overlay0))
+                .child("Takes it out of its other lists"),
+        )
+        .children(lists.into_iter().map(move |list| {
+            let owner = owner.clone();
+            let name = name.clone();
+            div()
+                .id(ElementId::Name(format!("list-{}-{}", repo_id, list.id).into()))
+                .px_3()
+                .py_1()
+                .text_xs()
+                .text_color(rgb(theme.text))
+                .cursor_pointer()
+                .hover(|style| style.bg(rgb(theme.surface1)))
+                .child(list.name.clone())
+                .on_click(move |_event, _window, cx| {
+                    move_to_list(owner.clone(), name.clone(), list.clone(), cx);
+                })
+        }))
+}
+
+fn render_copy_button(
+    repo_id: u64,
+    target: CopyTarget,
+    text: String,
+    copied: bool,
+    theme: Theme,
+) -> impl IntoElement {
+    let (id, label) = match target {
+        CopyTarget::Url => (format!("copy-url-{}", repo_id), "Copy URL"),
+        CopyTarget::FullName => (format!("copy-name-{}", repo_id), "Copy Name"),
+    };
+    div()
+        .id(ElementId::Name(id.into()))
+        .whitespace_nowrap()
+        .px_3()
+        .py_1()
+        .h_auto()
+        .rounded_md()
+        .bg(rgb(theme.surface1))
+        .text_xs()
+        .text_color(if copied {
+            rgb(theme.blue)
+        } else {
+            rgb(theme.subtext0)
+        })
+        .cursor_pointer()
+        .hover(|style| style.bg(rgb(theme.surface2)))
+        .child(if copied { "Copied!" } else { label })
+        .on_click(move |_event, _window, cx| {
+            copy_to_clipboard(repo_id, target, text.clone(), cx);
+        })
+}
+
+/// Small stats-row link that opens `url` in the browser
+fn render_link(id: String, label: String, url: String, theme: Theme) -> impl IntoElement {
+    div()
+        .id(ElementId::Name(id.into()))
+        .cursor_pointer()
+        .hover(|style| style.text_color(rgb(theme.blue)).underline())
+        .child(label)
+        .on_click(move |_event, _window, _cx| {
+            let _ = open::that(&url);
+        })
+}
 
 pub fn render_repository_row(
     repo: Repository,
-    is_selected: bool,
+    row: RowState,
+    theme: Theme,
     on_unstar: impl Fn(u64, &mut App) + 'static,
+    on_toggle_readme: impl Fn(u64, &mut App) + 'static,
+    on_topic_click: impl Fn(String, &mut App) + 'static,
+    on_select: impl Fn(usize, bool, &mut App) + 'static,
 ) -> impl IntoElement {
+    let RowState {
+        index,
+        is_selected,
+        is_highlighted,
+        can_unstar,
+        readme,
+        copied,
+        has_lists,
+        list_menu,
+        would_unstar,
+        star_age,
+        density,
+        highlight,
+        is_own,
+        is_pinned,
+        activity,
+    } = row;
+    let compact = density.is_compact();
+    let on_topic_click = Rc::new(on_topic_click);
+    let readme_expanded = readme.is_some();
+
+    let issues_url = repo.issues_url();
     let Repository {
         id: repo_id,
         full_name,
+        owner,
+        name,
         html_url,
+        homepage,
         description,
         language,
         stargazers_count,
@@ -22,23 +305,78 @@ pub fn render_repository_row(
         topics,
         updated_at,
         pushed_at,
+        archived,
+        fork,
+        starred_at,
         ..
     } = repo;
 
-    let updated_at = updated_at.format("%Y-%m-%d").to_string();
-    let pushed_at = pushed_at.map(|dt| dt.format("%Y-%m-%d").to_string());
+    let now = Utc::now();
+
+    // Compact rows put the stats beside the name instead of on their own line
+    let stats = div()
+        .flex()
+        .flex_shrink_0()
+        .gap_4()
+        .text_xs()
+        .text_color(rgb(theme.overlay0))
+        .child(format!("★ {}", stargazers_count))
+        .child(format!("⑂ {}", forks_count))
+        .child(render_link(
+            format!("issues-{}", repo_id),
+            format!("⚠ {}", open_issues_count),
+            issues_url,
+            theme,
+        ))
+        .when_some(homepage, |this, homepage| {
+            this.child(render_link(
+                format!("homepage-{}", repo_id),
+                "⌂ Homepage".to_string(),
+                homepage,
+                theme,
+            ))
+        })
+        .when_some(license, |this, lic| this.child(lic))
+        .when_some(starred_at, |this, starred| {
+            this.child(render_timestamp(
+                format!("starred-{}", repo_id),
+                "Starred",
+                starred,
+                now,
+                theme,
+            ))
+        })
+        .when_some(pushed_at, |this, pushed| {
+            this.child(render_timestamp(
+                format!("pushed-{}", repo_id),
+                "Pushed",
+                pushed,
+                now,
+                theme,
+            ))
+        })
+        .child(render_timestamp(
+            format!("updated-{}", repo_id),
+            "Updated",
+            updated_at,
+            now,
+            theme,
+        ));
+    let (inline_stats, stats_row) = if compact { (Some(stats), None) } else { (None, Some(stats)) };
 
     div()
         .id(ElementId::Name(format!("repo-row-{}", repo_id).into()))
         .w_full()
         .px_4()
-        .py_3()
+        .when(compact, |this| this.py_1())
+        .when(!compact, |this| this.py_3())
         .flex()
         .gap_3()
         .items_start() // Align children to top
         .border_b_1()
-        .border_color(rgb(catppuccin::SURFACE1))
-        .hover(|style| style.bg(rgb(catppuccin::SURFACE0)))
+        .border_color(rgb(theme.surface1))
+        .when(is_highlighted, |this| this.bg(rgb(theme.surface1)))
+        .when(!is_highlighted, |this| this.hover(|style| style.bg(rgb(theme.surface0))))
         // Checkbox - fixed width, aligned to top
         .child(
             div()
@@ -53,25 +391,24 @@ pub fn render_repository_row(
                 .rounded_sm()
                 .border_1()
                 .border_color(if is_selected {
-                    rgb(catppuccin::BLUE)
+                    rgb(theme.blue)
                 } else {
-                    rgb(catppuccin::SURFACE1)
+                    rgb(theme.surface1)
                 })
                 .bg(if is_selected {
-                    rgb(catppuccin::BLUE)
+                    rgb(theme.blue)
                 } else {
-                    rgb(catppuccin::BASE)
+                    rgb(theme.base)
                 })
                 .cursor_pointer()
                 .child(if is_selected {
-                    div().text_sm().text_color(rgb(catppuccin::BASE)).child("✓")
+                    div().text_sm().text_color(rgb(theme.base)).child("✓")
                 } else {
                     div()
                 })
-                .on_click(move |_event, _window, cx| {
-                    cx.update_global::<AppState, _>(|state, _cx| {
-                        state.selection.toggle(repo_id);
-                    });
+                // Shift-click selects a range from the last clicked row
+                .on_click(move |event, _window, cx| {
+                    on_select(index, event.modifiers().shift, cx);
                 }),
         )
         // Middle: content area (flexible, will shrink)
@@ -98,10 +435,10 @@ pub fn render_repository_row(
                                 .whitespace_nowrap()
                                 .text_base()
                                 .font_weight(FontWeight::SEMIBOLD)
-                                .text_color(rgb(catppuccin::BLUE))
+                                .text_color(rgb(theme.blue))
                                 .cursor_pointer()
                                 .hover(|style| style.underline())
-                                .child(full_name)
+                                .child(render_highlighted(&full_name, highlight.as_deref(), theme))
                                 .on_click({
                                     let url = html_url.clone();
                                     move |_event, _window, _cx| {
@@ -114,48 +451,133 @@ pub fn render_repository_row(
                             this.child(
                                 div()
                                     .flex_shrink_0()
+                                    .flex()
+                                    .items_center()
+                                    .gap_1()
                                     .px_2()
                                     .py(px(2.))
                                     .rounded_sm()
-                                    .bg(rgb(catppuccin::SURFACE1))
+                                    .bg(rgb(theme.surface1))
                                     .text_xs()
-                                    .text_color(rgb(catppuccin::SUBTEXT0))
+                                    .text_color(rgb(theme.subtext0))
+                                    .child(
+                                        div()
+                                            .size(px(8.))
+                                            .rounded_full()
+                                            .bg(rgb(language_color(&lang))),
+                                    )
                                     .child(lang),
                             )
-                        }),
+                        })
+                        // Own-repository badge
+                        .when(is_own, |this| {
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .px_2()
+                                    .py(px(2.))
+                                    .rounded_sm()
+                                    .bg(rgb(theme.sapphire))
+                                    .text_xs()
+                                    .text_color(rgb(theme.base))
+                                    .child("Owned by you"),
+                            )
+                        })
+                        // Archived badge
+                        .when(archived, |this| {
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .px_2()
+                                    .py(px(2.))
+                                    .rounded_sm()
+                                    .bg(rgb(theme.yellow))
+                                    .text_xs()
+                                    .text_color(rgb(theme.base))
+                                    .child("Archived"),
+                            )
+                        })
+                        // Active/dormant badge, dated by the last commit or release
+                        .when_some(activity, |this, activity| {
+                            let now = Utc::now();
+                            let dormant = activity.is_dormant(now);
+                            let label = match activity.last_active() {
+                                Some(at) => format!("{} · {}", activity.label(now), relative_time(at, now)),
+                                None => activity.label(now).to_string(),
+                            };
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .px_2()
+                                    .py(px(2.))
+                                    .rounded_sm()
+                                    .bg(rgb(theme.surface1))
+                                    .text_xs()
+                                    .text_color(rgb(if dormant { theme.yellow } else { theme.sapphire }))
+                                    .child(label),
+                            )
+                        })
+                        // Fork badge
+                        .when(fork, |this| {
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .px_2()
+                                    .py(px(2.))
+                                    .rounded_sm()
+                                    .bg(rgb(theme.surface1))
+                                    .text_xs()
+                                    .text_color(rgb(theme.sapphire))
+                                    .child("⑂ Fork"),
+                            )
+                        })
+                        // Star age badge
+                        .when_some(star_age, |this, star_age| {
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .px_2()
+                                    .py(px(2.))
+                                    .rounded_sm()
+                                    .bg(rgb(theme.surface1))
+                                    .text_xs()
+                                    .text_color(rgb(theme.overlay0))
+                                    .child(star_age),
+                            )
+                        })
+                        // Dry-run badge
+                        .when(would_unstar, |this| {
+                            this.child(
+                                div()
+                                    .flex_shrink_0()
+                                    .px_2()
+                                    .py(px(2.))
+                                    .rounded_sm()
+                                    .border_1()
+                                    .border_color(rgb(theme.red))
+                                    .text_xs()
+                                    .text_color(rgb(theme.red))
+                                    .child("Would unstar"),
+                            )
+                        })
+                        .when_some(inline_stats, |this, stats| this.child(stats)),
                 )
                 // Description
-                .when_some(description, |this, desc| {
-                    let truncated = if desc.chars().count() > 100 {
-                        format!("{}...", desc.chars().take(100).collect::<String>())
-                    } else {
-                        desc
-                    };
+                .when_some(description.filter(|_| !compact), |this, desc| {
+                    let truncated = truncate_words(&desc, DESCRIPTION_MAX_CHARS);
                     this.child(
                         div()
                             .text_sm()
-                            .text_color(rgb(catppuccin::SUBTEXT0))
+                            .text_color(rgb(theme.subtext0))
                             .overflow_hidden()
                             .whitespace_nowrap()
                             .child(truncated),
                     )
                 })
                 // Stats row
-                .child(
-                    div()
-                        .flex()
-                        .gap_4()
-                        .text_xs()
-                        .text_color(rgb(catppuccin::OVERLAY0))
-                        .child(format!("★ {}", stargazers_count))
-                        .child(format!("⑂ {}", forks_count))
-                        .child(format!("⚠ {}", open_issues_count))
-                        .when_some(license, |this, lic| this.child(lic))
-                        .when_some(pushed_at, |this, pushed| this.child(format!("Pushed: {}", pushed)))
-                        .child(format!("Updated: {}", updated_at)),
-                )
+                .when_some(stats_row, |this, stats| this.child(stats))
                 // Topics
-                .when(!topics.is_empty(), |this| {
+                .when(!compact && !topics.is_empty(), |this| {
                     this.child(
                         div()
                             .flex()
@@ -163,36 +585,396 @@ pub fn render_repository_row(
                             .flex_wrap()
                             .mt_1()
                             .children(topics.iter().take(5).map(|topic| {
+                                let on_topic_click = on_topic_click.clone();
+                                let clicked_topic = topic.clone();
                                 div()
+                                    .id(ElementId::Name(format!("topic-{}-{}", repo_id, topic).into()))
                                     .px_2()
                                     .py(px(2.))
                                     .rounded_full()
-                                    .bg(rgb(catppuccin::SURFACE0))
+                                    .bg(rgb(theme.surface0))
                                     .text_xs()
-                                    .text_color(rgb(catppuccin::SUBTEXT0))
+                                    .text_color(rgb(theme.subtext0))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.surface2)))
                                     .child(topic.clone())
+                                    .on_click(move |_event, _window, cx| {
+                                        on_topic_click(clicked_topic.clone(), cx);
+                                    })
                             })),
                     )
-                }),
+                })
+                // README preview
+                .when_some(readme, |this, readme| this.child(render_readme_preview(readme, theme))),
         )
-        // Right: Unstar button (fixed width, top aligned)
+        // Right: pin, copy, README and Unstar buttons (fixed width, top aligned)
         .child(
             div()
-                .id(ElementId::Name(format!("unstar-btn-{}", repo_id).into()))
                 .flex_shrink_0()
-                .whitespace_nowrap()
-                .px_3()
-                .py_1()
-                .h_auto()
-                .rounded_md()
-                .bg(rgb(catppuccin::SURFACE1))
-                .text_xs()
-                .text_color(rgb(catppuccin::RED))
+                .flex()
+                .gap_2()
+                .child(
+                    div()
+                        .id(ElementId::Name(format!("pin-btn-{}", repo_id).into()))
+                        .whitespace_nowrap()
+                        .px_3()
+                        .py_1()
+                        .h_auto()
+                        .rounded_md()
+                        .bg(if is_pinned {
+                            rgb(theme.surface2)
+                        } else {
+                            rgb(theme.surface1)
+                        })
+                        .text_xs()
+                        .text_color(if is_pinned {
+                            rgb(theme.yellow)
+                        } else {
+                            rgb(theme.subtext0)
+                        })
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(theme.surface2)))
+                        .child(if is_pinned { "📌 Pinned" } else { "📌 Pin" })
+                        .on_click(move |_event, _window, cx| {
+                            cx.update_global::<AppState, _>(|state, _cx| state.toggle_pin(repo_id));
+                        }),
+                )
+                .child(render_copy_button(
+                    repo_id,
+                    CopyTarget::Url,
+                    html_url.clone(),
+                    copied == Some(CopyTarget::Url),
+                    theme,
+                ))
+                .child(render_copy_button(
+                    repo_id,
+                    CopyTarget::FullName,
+                    full_name,
+                    copied == Some(CopyTarget::FullName),
+                    theme,
+                ))
+                // Move to list, with a dropdown of the user's lists
+                .when(has_lists, |this| {
+                    let menu_open = list_menu.is_some();
+                    this.child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .items_end()
+                            .child(
+                                div()
+                                    .id(ElementId::Name(format!("list-btn-{}", repo_id).into()))
+                                    .whitespace_nowrap()
+                                    .px_3()
+                                    .py_1()
+                                    .h_auto()
+                                    .rounded_md()
+                                    .bg(if menu_open {
+                                        rgb(theme.surface2)
+                                    } else {
+                                        rgb(theme.surface1)
+                                    })
+                                    .text_xs()
+                                    .text_color(rgb(theme.subtext0))
+                                    .cursor_pointer()
+                                    .hover(|style| style.bg(rgb(theme.surface2)))
+                                    .child("Move to list ▾")
+                                    .on_click(move |_event, _window, cx| {
+                                        let state = cx.global_mut::<AppState>();
+                                        state.list_menu_open =
+                                            (state.list_menu_open != Some(repo_id)).then_some(repo_id);
+                                    }),
+                            )
+                            // Painted last so it isn't covered by the rows below
+                            .when_some(list_menu, |this, lists| {
+                                this.child(deferred(
+                                    anchored()
+                                        .anchor(Corner::TopRight)
+                                        .snap_to_window_with_margin(px(8.))
+                                        .child(render_list_menu(repo_id, owner, name, lists, theme)),
+                                ))
+                            }),
+                    )
+                })
+                .child(
+                    div()
+                        .id(ElementId::Name(format!("readme-btn-{}", repo_id).into()))
+                        .whitespace_nowrap()
+                        .px_3()
+                        .py_1()
+                        .h_auto()
+                        .rounded_md()
+                        .bg(if readme_expanded {
+                            rgb(theme.surface2)
+                        } else {
+                            rgb(theme.surface1)
+                        })
+                        .text_xs()
+                        .text_color(rgb(theme.subtext0))
+                        .cursor_pointer()
+                        .hover(|style| style.bg(rgb(theme.surface2)))
+                        .child(if readme_expanded { "Hide README" } else { "README" })
+                        .on_click(move |_event, _window, cx| {
+                            on_toggle_readme(repo_id, cx);
+                        }),
+                )
+                .child(
+                    div()
+                        .id(ElementId::Name(format!("unstar-btn-{}", repo_id).into()))
+                        .whitespace_nowrap()
+                        .px_3()
+                        .py_1()
+                        .h_auto()
+                        .rounded_md()
+                        .bg(rgb(theme.surface1))
+                        .text_xs()
+                        .text_color(if can_unstar {
+                            rgb(theme.red)
+                        } else {
+                            rgb(theme.overlay0)
+                        })
+                        .child("Unstar")
+                        .when(can_unstar, |this| {
+                            this.cursor_pointer()
+                                .hover(|style| style.bg(rgb(theme.surface2)))
+                                .on_click(move |_event, _window, cx| {
+                                    on_unstar(repo_id, cx);
+                                })
+                        }),
+                ),
+        )
+}
+
+/// A table cell sized for its column
+fn table_cell(width: Option<Pixels>) -> Div {
+    match width {
+        Some(width) => div().w(width).flex_shrink_0(),
+        None => div().flex_1().min_w(px(100.)),
+    }
+    .overflow_hidden()
+    .whitespace_nowrap()
+}
+
+/// Header row of the table view. Clicking a column sorts by it, and the
+/// active column shows the direction.
+pub fn render_table_header(
+    sort_field: SortField,
+    sort_direction: SortDirection,
+    theme: Theme,
+    on_sort: impl Fn(SortField, &mut App) + 'static,
+) -> impl IntoElement {
+    let on_sort = Rc::new(on_sort);
+    div()
+        .w_full()
+        .px_4()
+        .py_1()
+        .flex()
+        .items_center()
+        .gap_3()
+        .border_b_1()
+        .border_color(rgb(theme.surface1))
+        .bg(rgb(theme.mantle))
+        .text_xs()
+        .font_weight(FontWeight::SEMIBOLD)
+        // Checkbox column
+        .child(div().w(px(20.)).flex_shrink_0())
+        .children(TABLE_COLUMNS.iter().map(|&(field, width)| {
+            let on_sort = on_sort.clone();
+            let active = field == sort_field;
+            table_cell(width)
+                .child(
+                    div()
+                        .id(ElementId::Name(format!("table-sort-{}", field.label()).into()))
+                        .flex()
+                        .gap_1()
+                        .cursor_pointer()
+                        .text_color(if active {
+                            rgb(theme.text)
+                        } else {
+                            rgb(theme.subtext0)
+                        })
+                        .hover(|style| style.text_color(rgb(theme.blue)))
+                        .child(field.label())
+                        .when(active, |this| this.child(sort_direction.label()))
+                        .on_click(move |_event, _window, cx| {
+                            on_sort(field, cx);
+                        }),
+                )
+        }))
+        .child(div().w(TABLE_ACTIONS_WIDTH).flex_shrink_0())
+}
+
+/// One line of the table view: the name and the sortable columns, with
+/// selection and Unstar. Cards-only parts of `row` like the README are ignored.
+pub fn render_repository_table_row(
+    repo: Repository,
+    row: RowState,
+    theme: Theme,
+    on_unstar: impl Fn(u64, &mut App) + 'static,
+    on_select: impl Fn(usize, bool, &mut App) + 'static,
+) -> impl IntoElement {
+    let RowState {
+        index,
+        is_selected,
+        is_highlighted,
+        can_unstar,
+        would_unstar,
+        highlight,
+        is_pinned,
+        ..
+    } = row;
+    let repo_id = repo.id;
+    let now = Utc::now();
+
+    div()
+        .id(ElementId::Name(format!("repo-row-{}", repo_id).into()))
+        .w_full()
+        .px_4()
+        .py_1()
+        .flex()
+        .items_center()
+        .gap_3()
+        .border_b_1()
+        .border_color(rgb(theme.surface1))
+        .text_sm()
+        .when(is_highlighted, |this| this.bg(rgb(theme.surface1)))
+        .when(!is_highlighted, |this| this.hover(|style| style.bg(rgb(theme.surface0))))
+        .child(
+            div()
+                .id(ElementId::Name(format!("checkbox-{}", repo_id).into()))
+                .flex_shrink_0()
+                .w(px(20.))
+                .h(px(20.))
+                .flex()
+                .items_center()
+                .justify_center()
+                .rounded_sm()
+                .border_1()
+                .border_color(if is_selected {
+                    rgb(theme.blue)
+                } else {
+                    rgb(theme.surface1)
+                })
+                .bg(if is_selected {
+                    rgb(theme.blue)
+                } else {
+                    rgb(theme.base)
+                })
                 .cursor_pointer()
-                .hover(|style| style.bg(rgb(catppuccin::SURFACE2)))
-                .child("Unstar")
-                .on_click(move |_event, _window, cx| {
-                    on_unstar(repo_id, cx);
+                .when(is_selected, |this| {
+                    this.child(div().text_sm().text_color(rgb(theme.base)).child("✓"))
+                })
+                .on_click(move |event, _window, cx| {
+                    on_select(index, event.modifiers().shift, cx);
                 }),
         )
+        .children(TABLE_COLUMNS.iter().map(|&(field, width)| {
+            let cell = table_cell(width).text_color(rgb(theme.subtext0));
+            match field {
+                SortField::Name => cell
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .when(is_pinned, |this| this.child("📌"))
+                    .child(
+                        div()
+                            .id(ElementId::Name(format!("repo-name-{}", repo_id).into()))
+                            .overflow_hidden()
+                            .font_weight(FontWeight::SEMIBOLD)
+                            .text_color(rgb(theme.blue))
+                            .cursor_pointer()
+                            .hover(|style| style.underline())
+                            .child(render_highlighted(&repo.full_name, highlight.as_deref(), theme))
+                            .on_click({
+                                let url = repo.html_url.clone();
+                                move |_event, _window, _cx| {
+                                    let _ = open::that(&url);
+                                }
+                            }),
+                    )
+                    .when(repo.archived, |this| {
+                        this.child(div().text_xs().text_color(rgb(theme.yellow)).child("Archived"))
+                    })
+                    .when(would_unstar, |this| {
+                        this.child(div().text_xs().text_color(rgb(theme.red)).child("Would unstar"))
+                    }),
+                SortField::Language => match repo.language.clone() {
+                    Some(language) => cell
+                        .flex()
+                        .items_center()
+                        .gap_1()
+                        .child(
+                            div()
+                                .flex_shrink_0()
+                                .size(px(8.))
+                                .rounded_full()
+                                .bg(rgb(language_color(&language))),
+                        )
+                        .child(language),
+                    None => cell.text_color(rgb(theme.overlay0)).child("—"),
+                },
+                SortField::Stars => cell.child(repo.stargazers_count.to_string()),
+                SortField::Forks => cell.child(repo.forks_count.to_string()),
+                SortField::Pushed => match repo.pushed_at {
+                    Some(pushed) => cell.child(render_dated(
+                        format!("pushed-{}", repo_id),
+                        relative_time(pushed, now),
+                        pushed,
+                        theme,
+                    )),
+                    None => cell.text_color(rgb(theme.overlay0)).child("—"),
+                },
+                _ => cell,
+            }
+        }))
+        .child(
+            div().w(TABLE_ACTIONS_WIDTH).flex_shrink_0().flex().justify_end().child(
+                div()
+                    .id(ElementId::Name(format!("unstar-btn-{}", repo_id).into()))
+                    .whitespace_nowrap()
+                    .px_2()
+                    .rounded_md()
+                    .bg(rgb(theme.surface1))
+                    .text_xs()
+                    .text_color(if can_unstar {
+                        rgb(theme.red)
+                    } else {
+                        rgb(theme.overlay0)
+                    })
+                    .child("Unstar")
+                    .when(can_unstar, |this| {
+                        this.cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .on_click(move |_event, _window, cx| {
+                                on_unstar(repo_id, cx);
+                            })
+                    }),
+            ),
+        )
+}
+
+fn render_readme_preview(readme: ReadmePreview, theme: Theme) -> impl IntoElement {
+    let (text, color) = match readme {
+        ReadmePreview::Loading => ("Loading README...".to_string(), theme.overlay0),
+        ReadmePreview::Missing => ("This repository has no README.".to_string(), theme.overlay0),
+        ReadmePreview::Failed(err) => (err, theme.red),
+        ReadmePreview::Loaded(content) => {
+            let mut lines: Vec<&str> = content.lines().take(README_PREVIEW_LINES).collect();
+            if content.lines().count() > README_PREVIEW_LINES {
+                lines.push("...");
+            }
+            (lines.join("\n"), theme.subtext0)
+        }
+    };
+
+    div()
+        .mt_2()
+        .p_3()
+        .rounded_md()
+        .bg(rgb(theme.mantle))
+        .border_1()
+        .border_color(rgb(theme.surface1))
+        .text_xs()
+        .text_color(rgb(color))
+        .child(text)
 }
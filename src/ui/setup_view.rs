@@ -1,22 +1,82 @@
-use crate::services::{ConfigService, GitHubService};
+use crate::services::{
+    has_starring_scope, is_transient_error, proxy_error_message, retry_transient, ConfigService,
+    GitHubService,
+};
 use crate::state::{AppScreen, AppState};
-use crate::ui::catppuccin;
+use crate::ui::{TextInput, Theme};
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use std::ops::Range;
+use std::time::Duration;
+
+/// Wait before retrying a token check that failed on the network; auth
+/// errors are never retried
+const VALIDATE_RETRY_BACKOFF: [Duration; 1] = [Duration::from_secs(1)];
+
+/// GitHub documents tokens as at most 255 characters
+const MAX_TOKEN_LEN: usize = 255;
+
+/// Clean up pasted or typed token text by trimming surrounding whitespace,
+/// leaving `validate_token` to reject bad tokens. Text with whitespace or
+/// non-ASCII characters inside is refused rather than pieced together.
+/// Returns at most `room` characters.
+fn sanitize_token_input(text: &str, room: usize) -> Result<String, &'static str> {
+    let text = text.trim();
+    if text.chars().any(char::is_whitespace) {
+        return Err("Tokens don't contain spaces or line breaks; check what was copied");
+    }
+    // Tokens are ASCII, which the IME offsets below rely on
+    if !text.is_ascii() {
+        return Err("Tokens only contain ASCII letters, digits and punctuation");
+    }
+    Ok(text.chars().take(room).collect())
+}
+
+/// Classic token prefixes, each followed by 36 alphanumeric characters
+const CLASSIC_TOKEN_PREFIXES: [&str; 5] = ["ghp_", "gho_", "ghu_", "ghs_", "ghr_"];
+
+/// Fine-grained tokens are `github_pat_`, 22 characters, `_`, then 59 characters
+const FINE_GRAINED_TOKEN_PREFIX: &str = "github_pat_";
+
+/// Whether `s` has the shape of a GitHub token. Only a hint for the user;
+/// `validate_token` is the real check.
+fn looks_like_github_token(s: &str) -> bool {
+    let alphanumeric = |part: &str, len: usize| {
+        part.len() == len && part.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+
+    if let Some(rest) = s.strip_prefix(FINE_GRAINED_TOKEN_PREFIX) {
+        return rest
+            .split_once('_')
+            .is_some_and(|(id, secret)| alphanumeric(id, 22) && alphanumeric(secret, 59));
+    }
+    CLASSIC_TOKEN_PREFIXES
+        .iter()
+        .any(|prefix| s.strip_prefix(prefix).is_some_and(|rest| alphanumeric(rest, 36)))
+}
 
 pub struct SetupView {
-    token_input: String,
+    token_input: TextInput,
+    /// In-progress IME composition, committed to `token_input` once finished
+    marked_text: Option<String>,
+    /// Show asterisks instead of the token text
+    mask_token: bool,
     error: Option<String>,
     validating: bool,
+    /// The first token check failed on the network and is being retried
+    retrying: bool,
     focus_handle: FocusHandle,
 }
 
 impl SetupView {
     pub fn new(cx: &mut Context<Self>) -> Self {
         Self {
-            token_input: String::new(),
+            token_input: TextInput::new(),
+            marked_text: None,
+            mask_token: true,
             error: None,
             validating: false,
+            retrying: false,
             focus_handle: cx.focus_handle(),
         }
     }
@@ -29,9 +89,8 @@ impl SetupView {
         let key = &event.keystroke.key;
         let key_char = &event.keystroke.key_char;
 
-        // Handle backspace
-        if key == "backspace" {
-            self.token_input.pop();
+        // Cursor movement and deletion
+        if self.token_input.handle_key(key) {
             cx.notify();
             return;
         }
@@ -42,37 +101,141 @@ impl SetupView {
             return;
         }
 
+        // Swallow auto-repeat so a held key can't double up characters;
+        // stopping propagation keeps the platform from inserting the text
+        if event.is_held && key_char.is_some() {
+            cx.stop_propagation();
+            return;
+        }
+
         // Handle paste (Cmd+V / Ctrl+V)
         if (event.keystroke.modifiers.platform || event.keystroke.modifiers.control)
             && key == "v"
         {
             // Paste from clipboard
-            if let Some(clipboard) = cx.read_from_clipboard() {
-                if let Some(text) = clipboard.text() {
-                    // Filter to only allow valid token characters
-                    let filtered: String = text
-                        .chars()
-                        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
-                        .collect();
-                    self.token_input.push_str(&filtered);
-                    cx.notify();
-                }
+            if let Some(text) = cx.read_from_clipboard().and_then(|clipboard| clipboard.text()) {
+                self.insert_token_text(&text);
+                cx.notify();
             }
-            return;
+            cx.stop_propagation();
         }
 
-        // Handle regular character input
-        if let Some(ch) = key_char {
-            // Only allow alphanumeric and underscore (valid for GitHub tokens)
-            let filtered: String = ch
-                .chars()
-                .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
-                .collect();
-            if !filtered.is_empty() {
-                self.token_input.push_str(&filtered);
-                cx.notify();
+        // Regular character input arrives through the `EntityInputHandler`
+        // impl below, so IME composition and dead keys are resolved first
+    }
+
+    /// Characters that still fit under `MAX_TOKEN_LEN`
+    fn remaining_room(&self) -> usize {
+        MAX_TOKEN_LEN.saturating_sub(self.token_input.value().len())
+    }
+
+    /// Insert pasted or typed text, or say why it was refused
+    fn insert_token_text(&mut self, text: &str) {
+        match sanitize_token_input(text, self.remaining_room()) {
+            Ok(text) if text.is_empty() => {}
+            Ok(text) => {
+                self.token_input.insert(&text);
+                self.error = None;
             }
+            Err(message) => self.error = Some(message.to_string()),
+        }
+    }
+
+    fn utf16_len(text: &str) -> usize {
+        text.encode_utf16().count()
+    }
+}
+
+impl EntityInputHandler for SetupView {
+    fn text_for_range(
+        &mut self,
+        range: Range<usize>,
+        adjusted_range: &mut Option<Range<usize>>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<String> {
+        // Tokens are ASCII, so UTF-16 offsets match byte offsets
+        let value = self.token_input.value();
+        let range = range.start.min(value.len())..range.end.min(value.len());
+        adjusted_range.replace(range.clone());
+        Some(value[range].to_string())
+    }
+
+    fn selected_text_range(
+        &mut self,
+        _ignore_disabled_input: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<UTF16Selection> {
+        let cursor = self.token_input.cursor();
+        Some(UTF16Selection {
+            range: cursor..cursor,
+            reversed: false,
+        })
+    }
+
+    fn marked_text_range(
+        &self,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<Range<usize>> {
+        let start = self.token_input.cursor();
+        self.marked_text
+            .as_deref()
+            .map(|marked| start..start + Self::utf16_len(marked))
+    }
+
+    fn unmark_text(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
+        self.marked_text = None;
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        _range: Option<Range<usize>>,
+        text: &str,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Committed text replaces any composition in progress
+        self.marked_text = None;
+        if self.validating {
+            return;
         }
+
+        self.insert_token_text(text);
+        cx.notify();
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        _range: Option<Range<usize>>,
+        new_text: &str,
+        _new_selected_range: Option<Range<usize>>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.marked_text = (!new_text.is_empty()).then(|| new_text.to_string());
+        cx.notify();
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        _range_utf16: Range<usize>,
+        element_bounds: Bounds<Pixels>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<Bounds<Pixels>> {
+        // Anchor the IME candidate window to the input field
+        Some(element_bounds)
+    }
+
+    fn character_index_for_point(
+        &mut self,
+        _point: Point<Pixels>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<usize> {
+        None
     }
 }
 
@@ -84,6 +247,7 @@ impl Focusable for SetupView {
 
 impl Render for SetupView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
         let error = self.error.clone();
         let validating = self.validating;
         let has_token = !self.token_input.is_empty();
@@ -99,7 +263,7 @@ impl Render for SetupView {
             .flex()
             .items_center()
             .justify_center()
-            .bg(rgb(catppuccin::BASE))
+            .bg(rgb(theme.base))
             .track_focus(&self.focus_handle)
             .on_key_down(cx.listener(|this, event, _window, cx| {
                 this.handle_key_down(event, cx);
@@ -108,10 +272,10 @@ impl Render for SetupView {
                 div()
                     .w(px(420.))
                     .p_8()
-                    .bg(rgb(catppuccin::SURFACE0))
+                    .bg(rgb(theme.surface0))
                     .rounded_lg()
                     .border_1()
-                    .border_color(rgb(catppuccin::SURFACE1))
+                    .border_color(rgb(theme.surface1))
                     .child(
                         div()
                             .flex()
@@ -127,16 +291,20 @@ impl Render for SetupView {
                                         div()
                                             .text_xl()
                                             .font_weight(FontWeight::BOLD)
-                                            .text_color(rgb(catppuccin::TEXT))
+                                            .text_color(rgb(theme.text))
                                             .child("GitHub StarCleaner"),
                                     )
                                     .child(
                                         div()
                                             .text_sm()
-                                            .text_color(rgb(catppuccin::SUBTEXT0))
+                                            .text_color(rgb(theme.subtext0))
                                             .child("Enter your GitHub Personal Access Token to manage your starred repositories."),
                                     ),
                             )
+                            // Which account the token is for, with a way back to the others
+                            .when(cx.global::<AppState>().config.profiles.len() > 1, |this| {
+                                this.child(self.render_profile_picker(cx))
+                            })
                             // Input section
                             .child(
                                 div()
@@ -147,15 +315,33 @@ impl Render for SetupView {
                                         div()
                                             .text_sm()
                                             .font_weight(FontWeight::MEDIUM)
-                                            .text_color(rgb(catppuccin::TEXT))
+                                            .text_color(rgb(theme.text))
                                             .child("Personal Access Token"),
                                     )
                                     .child(self.render_input(window, cx))
+                                    // Format hint while typing; doesn't block submitting
+                                    .when(has_token && error.is_none(), |this| {
+                                        let looks_valid = looks_like_github_token(self.token_input.value());
+                                        this.child(
+                                            div()
+                                                .text_xs()
+                                                .text_color(if looks_valid {
+                                                    rgb(theme.subtext0)
+                                                } else {
+                                                    rgb(theme.yellow)
+                                                })
+                                                .child(if looks_valid {
+                                                    "✓ Looks like a GitHub token"
+                                                } else {
+                                                    "Doesn't look like a GitHub token (ghp_… or github_pat_…)"
+                                                }),
+                                        )
+                                    })
                                     .when_some(error, |this, err| {
                                         this.child(
                                             div()
                                                 .text_sm()
-                                                .text_color(rgb(catppuccin::RED))
+                                                .text_color(rgb(theme.red))
                                                 .child(err),
                                         )
                                     }),
@@ -166,14 +352,14 @@ impl Render for SetupView {
                             .child(
                                 div()
                                     .text_xs()
-                                    .text_color(rgb(catppuccin::OVERLAY0))
+                                    .text_color(rgb(theme.overlay0))
                                     .child("Token requires 'repo' or 'public_repo' scope for starring/unstarring."),
                             )
                             // Instructions
                             .child(
                                 div()
                                     .text_xs()
-                                    .text_color(rgb(catppuccin::OVERLAY0))
+                                    .text_color(rgb(theme.overlay0))
                                     .mt_2()
                                     .child("Type your token or paste with Cmd+V. Press Enter to connect."),
                             ),
@@ -183,45 +369,142 @@ impl Render for SetupView {
 }
 
 impl SetupView {
-    fn render_input(&self, window: &Window, _cx: &mut Context<Self>) -> impl IntoElement {
-        let input_len = self.token_input.len();
+    fn render_profile_picker(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let config = &cx.global::<AppState>().config;
+        let active = config.active_profile;
+        let profiles: Vec<String> = config.profiles.iter().map(|p| p.name.clone()).collect();
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(theme.overlay0))
+                    .child("Account:"),
+            )
+            .children(profiles.into_iter().enumerate().map(|(index, name)| {
+                let is_active = index == active;
+                div()
+                    .id(ElementId::Name(format!("setup-profile-{}", index).into()))
+                    .px_2()
+                    .py_1()
+                    .rounded_sm()
+                    .text_xs()
+                    .bg(if is_active {
+                        rgb(theme.blue)
+                    } else {
+                        rgb(theme.surface1)
+                    })
+                    .text_color(if is_active {
+                        rgb(theme.base)
+                    } else {
+                        rgb(theme.subtext0)
+                    })
+                    .child(name)
+                    .when(!is_active, |this| {
+                        this.cursor_pointer()
+                            .hover(|style| style.bg(rgb(theme.surface2)))
+                            .on_click(cx.listener(move |view, _event, _window, cx| {
+                                view.token_input.clear();
+                                view.error = None;
+                                cx.update_global::<AppState, _>(|state, _cx| {
+                                    if let Err(e) = state.switch_profile(index) {
+                                        state.error = Some(format!("Failed to switch account: {}", e));
+                                    }
+                                });
+                            }))
+                    })
+            }))
+    }
+
+    fn render_input(&self, window: &Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = Theme::current(cx);
+        let is_empty = self.token_input.is_empty();
+        let (before_cursor, after_cursor) = self.token_input.split_at_cursor();
+        let display = if self.mask_token {
+            format!("{}|{}", "*".repeat(before_cursor.len()), "*".repeat(after_cursor.len()))
+        } else {
+            format!("{}|{}", before_cursor, after_cursor)
+        };
+        let mask_token = self.mask_token;
         let is_focused = self.focus_handle.is_focused(window);
         let focus_handle = self.focus_handle.clone();
+        let input_focus_handle = self.focus_handle.clone();
+        let entity = cx.entity();
 
         div()
             .id("token-input")
             .w_full()
             .h(px(40.))
             .px_3()
-            .bg(rgb(catppuccin::BASE))
+            .bg(rgb(theme.base))
             .border_1()
             .border_color(if is_focused {
-                rgb(catppuccin::BLUE)
+                rgb(theme.blue)
             } else {
-                rgb(catppuccin::SURFACE1)
+                rgb(theme.surface1)
             })
             .rounded_md()
             .flex()
             .items_center()
             .cursor_pointer()
+            .relative()
             .on_click(move |_event, window, _cx| {
                 focus_handle.focus(window);
             })
+            // Register for platform text input so IME and dead keys work
+            .child(
+                canvas(
+                    |_bounds, _window, _cx| {},
+                    move |bounds, _, window, cx| {
+                        window.handle_input(
+                            &input_focus_handle,
+                            ElementInputHandler::new(bounds, entity.clone()),
+                            cx,
+                        );
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
             .child(
                 div()
                     .flex_1()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
                     .text_sm()
-                    .text_color(if input_len == 0 {
-                        rgb(catppuccin::OVERLAY0)
+                    .text_color(if is_empty {
+                        rgb(theme.overlay0)
                     } else {
-                        rgb(catppuccin::TEXT)
+                        rgb(theme.text)
                     })
-                    .child(if input_len == 0 {
+                    .child(if is_empty {
                         "ghp_xxxxxxxxxxxx".to_string()
                     } else {
-                        format!("{}|", "*".repeat(input_len.min(39)))
+                        display
                     }),
             )
+            // Show/hide toggle; clicking it must not submit the form
+            .child(
+                div()
+                    .id("mask-toggle")
+                    .flex_shrink_0()
+                    .px_2()
+                    .text_xs()
+                    .text_color(rgb(theme.subtext0))
+                    .cursor_pointer()
+                    .hover(|style| style.text_color(rgb(theme.text)))
+                    .child(if mask_token { "👁 Show" } else { "👁 Hide" })
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.mask_token = !this.mask_token;
+                        cx.stop_propagation();
+                        cx.notify();
+                    })),
+            )
     }
 
     fn render_button(
@@ -230,6 +513,7 @@ impl SetupView {
         has_token: bool,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
+        let theme = Theme::current(cx);
         let is_disabled = validating || !has_token;
 
         div()
@@ -242,23 +526,25 @@ impl SetupView {
             .rounded_md()
             .cursor_pointer()
             .bg(if is_disabled {
-                rgb(catppuccin::SURFACE1)
+                rgb(theme.surface1)
             } else {
-                rgb(catppuccin::BLUE)
+                rgb(theme.blue)
             })
             .text_color(if is_disabled {
-                rgb(catppuccin::OVERLAY0)
+                rgb(theme.overlay0)
             } else {
-                rgb(catppuccin::BASE)
+                rgb(theme.base)
             })
             .font_weight(FontWeight::MEDIUM)
-            .child(if validating {
+            .child(if self.retrying {
+                "Retrying..."
+            } else if validating {
                 "Validating..."
             } else {
                 "Connect"
             })
             .when(!is_disabled, |this| {
-                this.hover(|style| style.bg(rgb(catppuccin::SAPPHIRE)))
+                this.hover(|style| style.bg(rgb(theme.sapphire)))
                     .on_click(cx.listener(|this, _event, _window, cx| {
                         this.submit_token(cx);
                     }))
@@ -266,7 +552,7 @@ impl SetupView {
     }
 
     fn submit_token(&mut self, cx: &mut Context<Self>) {
-        let token = self.token_input.clone();
+        let token = self.token_input.value().to_string();
 
         if token.is_empty() {
             self.error = Some("Please enter a Personal Access Token".to_string());
@@ -279,35 +565,64 @@ impl SetupView {
         cx.notify();
 
         let token_clone = token.clone();
+        let proxy = cx.global::<AppState>().config.github.proxy.clone();
         cx.spawn(async move |view, cx| {
             let result = async {
-                let service = GitHubService::new(&token_clone)?;
-                let (username, _) = service.validate_token().await?;
+                let service = GitHubService::new(&token_clone, proxy.as_deref())?;
+                let (username, _) = retry_transient(
+                    &VALIDATE_RETRY_BACKOFF,
+                    || service.validate_token(),
+                    || {
+                        view.update(cx, |view, cx| {
+                            view.retrying = true;
+                            cx.notify();
+                        })
+                        .ok();
+                    },
+                )
+                .await?;
+
+                // Fine-grained tokens use per-repo permissions instead of OAuth scopes
+                if !token_clone.starts_with("github_pat_") {
+                    let scopes = service.fetch_token_scopes().await?;
+                    if !has_starring_scope(&scopes) {
+                        anyhow::bail!("Token missing 'public_repo' scope");
+                    }
+                }
                 Ok::<_, anyhow::Error>((service, username))
             }
             .await;
 
-            view.update(cx, |view, cx| match result {
-                Ok((service, username)) => {
-                    if let Err(e) = ConfigService::save_token(&token_clone) {
-                        view.error = Some(format!("Failed to save token: {}", e));
+            view.update(cx, |view, cx| {
+                view.retrying = false;
+                match result {
+                    Ok((service, username)) => {
+                        // Signs in for this session even if the token can't be saved
+                        let (saved, warning) = ConfigService::save_token(&token_clone);
+
+                        // Ready for the next sign-in, after a logout or with another account
+                        view.validating = false;
+                        view.token_input.clear();
+                        cx.update_global::<AppState, _>(|state, _cx| {
+                            state.config.github = saved.github;
+                            state.github_service = Some(service);
+                            state.username = Some(username);
+                            state.screen = AppScreen::Loading;
+                            state.warning = warning;
+                        });
+                        cx.notify();
+                    }
+                    Err(e) => {
+                        view.error = Some(proxy_error_message(&e).unwrap_or_else(|| {
+                            if is_transient_error(&e) {
+                                format!("Couldn't reach GitHub: {}", e)
+                            } else {
+                                format!("Invalid token: {}", e)
+                            }
+                        }));
                         view.validating = false;
                         cx.notify();
-                        return;
                     }
-
-                    cx.update_global::<AppState, _>(|state, _cx| {
-                        state.config.github.personal_access_token = Some(token_clone);
-                        state.github_service = Some(service);
-                        state.username = Some(username);
-                        state.screen = AppScreen::Loading;
-                    });
-                    cx.notify();
-                }
-                Err(e) => {
-                    view.error = Some(format!("Invalid token: {}", e));
-                    view.validating = false;
-                    cx.notify();
                 }
             })
             .ok();
@@ -315,3 +630,60 @@ impl SetupView {
         .detach();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{looks_like_github_token, sanitize_token_input, MAX_TOKEN_LEN};
+
+    #[test]
+    fn test_sanitize_keeps_the_whole_token() {
+        assert_eq!(sanitize_token_input("  ghp_abc-DEF.123\n", MAX_TOKEN_LEN).unwrap(), "ghp_abc-DEF.123");
+        assert_eq!(
+            sanitize_token_input("github_pat_11AB_cd-ef", MAX_TOKEN_LEN).unwrap(),
+            "github_pat_11AB_cd-ef"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_refuses_whitespace_and_non_ascii_inside() {
+        assert_eq!(sanitize_token_input(" ", MAX_TOKEN_LEN).unwrap(), "");
+        assert!(sanitize_token_input("ghp_ab\tcd", MAX_TOKEN_LEN).is_err());
+        assert!(sanitize_token_input("ghp_ab\ncd", MAX_TOKEN_LEN).is_err());
+        assert!(sanitize_token_input("ghp_abcdé", MAX_TOKEN_LEN).is_err());
+    }
+
+    #[test]
+    fn test_sanitize_respects_length_guard() {
+        assert_eq!(sanitize_token_input("abcdef", 4).unwrap(), "abcd");
+        assert_eq!(sanitize_token_input("abcdef", 0).unwrap(), "");
+        assert_eq!(sanitize_token_input(&"x".repeat(400), MAX_TOKEN_LEN).unwrap().len(), MAX_TOKEN_LEN);
+    }
+
+    #[test]
+    fn test_classic_tokens() {
+        assert!(looks_like_github_token(&format!("ghp_{}", "a1B2".repeat(9))));
+        assert!(looks_like_github_token(&format!("gho_{}", "x".repeat(36))));
+        assert!(!looks_like_github_token(&format!("ghp_{}", "x".repeat(35))));
+        assert!(!looks_like_github_token(&format!("ghp_{}", "x".repeat(37))));
+        assert!(!looks_like_github_token(&format!("ghx_{}", "x".repeat(36))));
+    }
+
+    #[test]
+    fn test_fine_grained_tokens() {
+        let token = format!("github_pat_{}_{}", "A".repeat(22), "b9".repeat(29) + "c");
+        assert!(looks_like_github_token(&token));
+
+        let short_secret = format!("github_pat_{}_{}", "A".repeat(22), "b".repeat(58));
+        assert!(!looks_like_github_token(&short_secret));
+        let no_separator = format!("github_pat_{}", "A".repeat(82));
+        assert!(!looks_like_github_token(&no_separator));
+    }
+
+    #[test]
+    fn test_obviously_invalid() {
+        assert!(!looks_like_github_token(""));
+        assert!(!looks_like_github_token("hunter2"));
+        assert!(!looks_like_github_token("ghp_"));
+        assert!(!looks_like_github_token("github_pat_"));
+    }
+}
@@ -0,0 +1,49 @@
+use gpui::*;
+use std::time::Duration;
+
+/// Time for one pulse to travel across all the dots
+const PULSE_PERIOD: Duration = Duration::from_millis(1200);
+const DOT_COUNT: usize = 3;
+
+/// Three dots pulsing in sequence, for anything that's loading.
+/// `id` must be unique among spinners on screen at the same time.
+pub fn spinner(id: impl Into<SharedString>, color: u32) -> impl IntoElement {
+    let id = id.into();
+    div()
+        .flex()
+        .items_center()
+        .gap_1()
+        .children((0..DOT_COUNT).map(move |index| {
+            // Stagger each dot by an equal share of the period
+            let offset = index as f32 / DOT_COUNT as f32;
+            div()
+                .size(px(6.))
+                .rounded_full()
+                .bg(rgb(color))
+                .with_animation(
+                    ElementId::named_usize(id.clone(), index),
+                    Animation::new(PULSE_PERIOD).repeat(),
+                    move |dot, delta| dot.opacity(dot_opacity((delta + offset).fract())),
+                )
+        }))
+}
+
+/// Opacity for a dot at `phase` (0..1) of its pulse: fades in, then back out
+fn dot_opacity(phase: f32) -> f32 {
+    let level = bounce(ease_in_out)(phase);
+    0.25 + 0.75 * level
+}
+
+#[cfg(test)]
+mod tests {
+    // Not a glob import: gpui's own `test` macro would shadow `#[test]`
+    use super::dot_opacity;
+
+    #[test]
+    fn test_dot_opacity_peaks_mid_pulse() {
+        assert!((dot_opacity(0.0) - 0.25).abs() < 1e-6);
+        assert!((dot_opacity(0.5) - 1.0).abs() < 1e-6);
+        assert!(dot_opacity(0.25) < dot_opacity(0.5));
+        assert!(dot_opacity(0.75) < dot_opacity(0.5));
+    }
+}
@@ -0,0 +1,198 @@
+/// Single-line text buffer with a cursor, for views that render their own input fields.
+/// The cursor is a byte offset that always sits on a char boundary.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the contents and move the cursor to the end
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.set_value(String::new());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Cursor position as a byte offset into `value()`
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Text before and after the cursor, for rendering
+    pub fn split_at_cursor(&self) -> (&str, &str) {
+        self.value.split_at(self.cursor)
+    }
+
+    /// Insert text at the cursor (typing or paste)
+    pub fn insert(&mut self, text: &str) {
+        self.value.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Delete the character before the cursor
+    pub fn backspace(&mut self) {
+        if let Some(previous) = self.previous_boundary() {
+            self.value.replace_range(previous..self.cursor, "");
+            self.cursor = previous;
+        }
+    }
+
+    /// Delete the character after the cursor
+    pub fn delete(&mut self) {
+        if let Some(next) = self.next_boundary() {
+            self.value.replace_range(self.cursor..next, "");
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if let Some(previous) = self.previous_boundary() {
+            self.cursor = previous;
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if let Some(next) = self.next_boundary() {
+            self.cursor = next;
+        }
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.value.len();
+    }
+
+    /// Apply an editing key (arrows, Home/End, Backspace/Delete), returns
+    /// whether the key was handled
+    pub fn handle_key(&mut self, key: &str) -> bool {
+        match key {
+            "left" => self.move_left(),
+            "right" => self.move_right(),
+            "home" => self.move_home(),
+            "end" => self.move_end(),
+            "backspace" => self.backspace(),
+            "delete" => self.delete(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn previous_boundary(&self) -> Option<usize> {
+        self.value[..self.cursor]
+            .char_indices()
+            .next_back()
+            .map(|(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> Option<usize> {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map(|ch| self.cursor + ch.len_utf8())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_with(value: &str) -> TextInput {
+        let mut input = TextInput::new();
+        input.set_value(value);
+        input
+    }
+
+    #[test]
+    fn test_insert_at_cursor() {
+        let mut input = input_with("ghp_abc");
+        input.move_home();
+        input.move_right();
+        input.insert("XY");
+
+        assert_eq!(input.value(), "gXYhp_abc");
+        assert_eq!(input.split_at_cursor(), ("gXY", "hp_abc"));
+    }
+
+    #[test]
+    fn test_backspace_and_delete_at_cursor() {
+        let mut input = input_with("abcd");
+        input.move_left();
+        input.move_left();
+
+        input.backspace();
+        assert_eq!(input.value(), "acd");
+        assert_eq!(input.cursor(), 1);
+
+        input.delete();
+        assert_eq!(input.value(), "ad");
+        assert_eq!(input.cursor(), 1);
+    }
+
+    #[test]
+    fn test_edits_at_the_ends_are_no_ops() {
+        let mut input = input_with("ab");
+        input.delete();
+        input.move_right();
+        assert_eq!(input.value(), "ab");
+        assert_eq!(input.cursor(), 2);
+
+        input.move_home();
+        input.backspace();
+        input.move_left();
+        assert_eq!(input.value(), "ab");
+        assert_eq!(input.cursor(), 0);
+    }
+
+    #[test]
+    fn test_handle_key() {
+        let mut input = input_with("abc");
+        assert!(input.handle_key("home"));
+        assert!(input.handle_key("delete"));
+        assert!(input.handle_key("end"));
+        assert!(!input.handle_key("a"));
+
+        assert_eq!(input.value(), "bc");
+        assert_eq!(input.cursor(), 2);
+    }
+
+    #[test]
+    fn test_cursor_respects_multibyte_chars() {
+        let mut input = input_with("aé");
+        input.move_left();
+        assert_eq!(input.cursor(), 1);
+
+        input.move_right();
+        input.backspace();
+        assert_eq!(input.value(), "a");
+    }
+
+    #[test]
+    fn test_set_value_moves_cursor_to_end() {
+        let mut input = input_with("abc");
+        input.move_home();
+        input.set_value("hello");
+        assert_eq!(input.cursor(), 5);
+
+        input.clear();
+        assert!(input.is_empty());
+        assert_eq!(input.cursor(), 0);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod text;
+pub mod time;
+
+pub use text::*;
+pub use time::*;
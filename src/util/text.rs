@@ -0,0 +1,216 @@
+/// Shorten `s` to at most `max_chars` characters plus "...", breaking at the
+/// last word boundary that fits. A single word longer than the limit is cut
+/// mid-word. Counts chars rather than bytes, so multibyte text is safe.
+pub fn truncate_words(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.to_string();
+    }
+
+    let cut = s
+        .char_indices()
+        .nth(max_chars)
+        .map(|(index, _)| index)
+        .unwrap_or(s.len());
+    let head = &s[..cut];
+    // Keep the cut if it already falls between words
+    let next_is_space = s[cut..].starts_with(char::is_whitespace);
+    let head = match head.rfind(char::is_whitespace) {
+        Some(space) if !next_is_space && space > 0 => &head[..space],
+        _ => head,
+    };
+    format!("{}...", head.trim_end())
+}
+
+/// Split `s` into `(segment, is_match)` pairs around case-insensitive
+/// occurrences of `query`, for highlighting search matches. An empty query
+/// or no match gives the whole string as one unmatched segment.
+pub fn match_segments<'a>(s: &'a str, query: &str) -> Vec<(&'a str, bool)> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+    if query.is_empty() {
+        return vec![(s, false)];
+    }
+
+    let mut segments = Vec::new();
+    let mut unmatched_start = 0;
+    let mut index = 0;
+    while index < s.len() {
+        match match_len_at(&s[index..], &query) {
+            Some(len) => {
+                if unmatched_start < index {
+                    segments.push((&s[unmatched_start..index], false));
+                }
+                segments.push((&s[index..index + len], true));
+                index += len;
+                unmatched_start = index;
+            }
+            None => index += s[index..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if unmatched_start < s.len() || segments.is_empty() {
+        segments.push((&s[unmatched_start..], false));
+    }
+    segments
+}
+
+/// Score how well `query` fuzzy-matches `target`: every query character has
+/// to appear in `target` in order, ignoring case and spaces. Matches at word
+/// starts and runs of consecutive characters score higher, and characters
+/// skipped between matches cost a point each. None if it doesn't match.
+pub fn fuzzy_score(query: &str, target: &str) -> Option<i64> {
+    let wanted: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let Some(&first) = wanted.first() else {
+        return Some(0);
+    };
+    let target: Vec<(Option<char>, char)> = target
+        .chars()
+        .scan(None, |previous, ch| {
+            let item = (*previous, ch);
+            *previous = Some(ch);
+            Some(item)
+        })
+        .collect();
+
+    // Matching greedily from the first occurrence can miss a tighter match
+    // later on, e.g. "reqw" in "rust-lang/reqwest", so try every start
+    (0..target.len())
+        .filter(|&start| target[start].1.to_lowercase().eq(std::iter::once(first)))
+        .filter_map(|start| fuzzy_score_from(&wanted, &target[start..]))
+        .max()
+}
+
+/// Greedy match of `wanted` starting at the first character of `target`,
+/// given as (previous, current) character pairs
+fn fuzzy_score_from(wanted: &[char], target: &[(Option<char>, char)]) -> Option<i64> {
+    let mut wanted = wanted.iter().peekable();
+    let mut score = 0;
+    let mut run = 0;
+    for &(previous, ch) in target {
+        let Some(&&next) = wanted.peek() else {
+            break;
+        };
+        if ch.to_lowercase().eq(std::iter::once(next)) {
+            wanted.next();
+            run += 1;
+            score += run;
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 5;
+            }
+        } else {
+            run = 0;
+            score -= 1;
+        }
+    }
+    wanted.peek().is_none().then_some(score)
+}
+
+/// Byte length of the prefix of `s` that lowercases to `query`, if any
+fn match_len_at(s: &str, query: &[char]) -> Option<usize> {
+    let mut remaining = query;
+    for (index, ch) in s.char_indices() {
+        if remaining.is_empty() {
+            return Some(index);
+        }
+        for lower in ch.to_lowercase() {
+            match remaining.split_first() {
+                Some((&expected, rest)) if expected == lower => remaining = rest,
+                _ => return None,
+            }
+        }
+    }
+    remaining.is_empty().then_some(s.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorter_and_exact_length_are_unchanged() {
+        assert_eq!(truncate_words("short", 10), "short");
+        assert_eq!(truncate_words("exactly10!", 10), "exactly10!");
+        assert_eq!(truncate_words("", 10), "");
+    }
+
+    #[test]
+    fn test_breaks_on_word_boundary() {
+        assert_eq!(truncate_words("a fast command line tool", 12), "a fast...");
+        // A cut that lands right before a space keeps the whole last word
+        assert_eq!(truncate_words("a fast command line tool", 14), "a fast command...");
+    }
+
+    #[test]
+    fn test_single_long_word_is_cut() {
+        assert_eq!(truncate_words("supercalifragilistic", 5), "super...");
+    }
+
+    #[test]
+    fn test_multibyte_text() {
+        assert_eq!(truncate_words("日本語のテキスト です", 5), "日本語のテ...");
+        assert_eq!(truncate_words("héllo wörld ünïcode", 13), "héllo wörld...");
+    }
+
+    #[test]
+    fn test_match_segments_is_case_insensitive() {
+        assert_eq!(
+            match_segments("BurntSushi/ripgrep", "SUSHI"),
+            vec![("Burnt", false), ("Sushi", true), ("/ripgrep", false)]
+        );
+        assert_eq!(
+            match_segments("rust-lang/rust", "rust"),
+            vec![("rust", true), ("-lang/", false), ("rust", true)]
+        );
+    }
+
+    #[test]
+    fn test_match_segments_without_a_match() {
+        assert_eq!(match_segments("sharkdp/fd", "ripgrep"), vec![("sharkdp/fd", false)]);
+        assert_eq!(match_segments("sharkdp/fd", "  "), vec![("sharkdp/fd", false)]);
+        assert_eq!(match_segments("", "fd"), vec![("", false)]);
+    }
+
+    #[test]
+    fn test_match_segments_multibyte() {
+        assert_eq!(
+            match_segments("Ünïcode/Straße", "strasse"),
+            vec![("Ünïcode/Straße", false)]
+        );
+        assert_eq!(
+            match_segments("ünïcode/ÜBER", "über"),
+            vec![("ünïcode/", false), ("ÜBER", true)]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_score_matches_in_order() {
+        assert!(fuzzy_score("unsel", "Unstar Selected").is_some());
+        assert!(fuzzy_score("SBS", "Sort by Stars").is_some());
+        assert!(fuzzy_score("toggle theme", "Toggle Theme").is_some());
+        assert!(fuzzy_score("rqwst", "seanmonstar/reqwest").is_some());
+        assert_eq!(fuzzy_score("", "Logout"), Some(0));
+
+        assert_eq!(fuzzy_score("lgx", "Logout"), None);
+        assert_eq!(fuzzy_score("las", "Select All"), None);
+        assert_eq!(fuzzy_score("reqwest", "reqw"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts_and_runs() {
+        // Both letters start words in the first
+        assert!(fuzzy_score("ts", "Toggle Stats") > fuzzy_score("ts", "Unstar Selected"));
+        // Consecutive letters beat the same letters spread out
+        assert!(fuzzy_score("re", "Reload") > fuzzy_score("re", "Row Density"));
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranks_closer_matches_higher() {
+        let exact = fuzzy_score("reqw", "seanmonstar/reqwest");
+        let spread = fuzzy_score("reqw", "owner/ready-quick-write");
+        let scattered = fuzzy_score("reqw", "owner/ready-quick-and-slow-write");
+        assert!(exact > spread);
+        assert!(spread > scattered);
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+
+/// Human-friendly age of `dt` relative to `now`, e.g. "3 days ago".
+/// Months are approximated as 30 days and years as 365.
+pub fn relative_time(dt: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now - dt;
+    let (count, unit) = if elapsed.num_minutes() < 1 {
+        return "just now".to_string();
+    } else if elapsed.num_hours() < 1 {
+        (elapsed.num_minutes(), "minute")
+    } else if elapsed.num_days() < 1 {
+        (elapsed.num_hours(), "hour")
+    } else if elapsed.num_days() < 30 {
+        (elapsed.num_days(), "day")
+    } else if elapsed.num_days() < 365 {
+        (elapsed.num_days() / 30, "month")
+    } else {
+        (elapsed.num_days() / 365, "year")
+    };
+
+    if count == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ago(elapsed: Duration) -> String {
+        let now = Utc::now();
+        relative_time(now - elapsed, now)
+    }
+
+    #[test]
+    fn test_under_a_minute_is_just_now() {
+        assert_eq!(ago(Duration::seconds(59)), "just now");
+        // Future timestamps (clock skew) shouldn't read as negative ages
+        assert_eq!(ago(Duration::minutes(-5)), "just now");
+    }
+
+    #[test]
+    fn test_minutes_and_hours() {
+        assert_eq!(ago(Duration::minutes(1)), "1 minute ago");
+        assert_eq!(ago(Duration::minutes(59)), "59 minutes ago");
+        assert_eq!(ago(Duration::minutes(60)), "1 hour ago");
+        assert_eq!(ago(Duration::hours(23)), "23 hours ago");
+    }
+
+    #[test]
+    fn test_days_and_months() {
+        assert_eq!(ago(Duration::hours(24)), "1 day ago");
+        assert_eq!(ago(Duration::days(29)), "29 days ago");
+        assert_eq!(ago(Duration::days(30)), "1 month ago");
+        assert_eq!(ago(Duration::days(364)), "12 months ago");
+    }
+
+    #[test]
+    fn test_years() {
+        assert_eq!(ago(Duration::days(365)), "1 year ago");
+        assert_eq!(ago(Duration::days(800)), "2 years ago");
+    }
+}